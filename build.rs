@@ -4,12 +4,25 @@
 //! Adapted from
 //! https://github.com/dtolnay/syn/blob/a54fb0098c6679f1312113ae2eec0305c51c7390/build.rs.
 
-use std::{env, process::Command, str};
+use std::{env, fs, path::PathBuf, process::Command, str};
 
 // The rustc-cfg strings below are *not* public API. Please let us know by
 // opening a GitHub issue if your build environment requires some way to enable
 // these cfgs other than by executing our build script.
 fn main() {
+    // Declare every cfg this crate uses, so that compilers which check cfgs don't warn about them.
+    // doc_cfg is set by docs.rs rather than by this script.
+    for cfg in &[
+        "can_vector",
+        "const_fn_trait_bounds",
+        "doc_cfg",
+        "read_buf",
+        "seek_convenience",
+        "seek_relative",
+    ] {
+        println!("cargo:rustc-check-cfg=cfg({})", cfg);
+    }
+
     let compiler = match rustc_version() {
         Some(compiler) => compiler,
         None => return,
@@ -30,6 +43,81 @@ fn main() {
     {
         println!("cargo:rustc-cfg=const_fn_trait_bounds");
     }
+    if (compiler.minor >= 80
+        && (compiler.channel == ReleaseChannel::Stable || compiler.channel == ReleaseChannel::Beta))
+        || compiler.minor >= 81
+    {
+        println!("cargo:rustc-cfg=seek_relative");
+    }
+    // Read::read_buf and Read::is_read_vectored are unstable, so their APIs may change. Only
+    // enable them on nightly compilers where the versions of the APIs this crate uses still
+    // compile.
+    if compiler.channel == ReleaseChannel::Nightly {
+        if probe("read_buf", READ_BUF_PROBE) {
+            println!("cargo:rustc-cfg=read_buf");
+        }
+        if probe("can_vector", CAN_VECTOR_PROBE) {
+            println!("cargo:rustc-cfg=can_vector");
+        }
+    }
+}
+
+const READ_BUF_PROBE: &str = r#"
+#![feature(read_buf, core_io_borrowed_buf)]
+use std::io::{self, BorrowedCursor, Read};
+
+pub struct Probe;
+
+impl Read for Probe {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> io::Result<()> {
+        let n = cursor.capacity();
+        cursor.append(&[0][..n.min(1)]);
+        Ok(())
+    }
+}
+"#;
+
+const CAN_VECTOR_PROBE: &str = r#"
+#![feature(can_vector)]
+use std::io::{self, Read};
+
+pub struct Probe;
+
+impl Read for Probe {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
+}
+"#;
+
+/// Returns true if `source` compiles as a library crate.
+fn probe(name: &str, source: &str) -> bool {
+    let (rustc, out_dir) = match (env::var_os("RUSTC"), env::var_os("OUT_DIR")) {
+        (Some(rustc), Some(out_dir)) => (rustc, PathBuf::from(out_dir)),
+        _ => return false,
+    };
+    let probe_path = out_dir.join(format!("{}_probe.rs", name));
+    if fs::write(&probe_path, source).is_err() {
+        return false;
+    }
+    Command::new(rustc)
+        .arg("--edition=2018")
+        .arg("--crate-type=lib")
+        .arg("--emit=metadata")
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .arg(&probe_path)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
 }
 
 struct Compiler {
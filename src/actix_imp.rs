@@ -0,0 +1,38 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use actix_http_3::body::{BodySize, MessageBody};
+use bytes::{Buf, Bytes};
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Each chunk in the list is sent as it is, without coalescing or copying chunks.
+impl MessageBody for BufList {
+    type Error = Infallible;
+
+    #[inline]
+    fn size(&self) -> BodySize {
+        BodySize::Sized(self.total_len())
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        Poll::Ready(self.pop_front_chunk().map(Ok))
+    }
+
+    fn try_into_bytes(mut self) -> Result<Bytes, Self> {
+        // Lists with more than one chunk would have to be copied into a single `Bytes`.
+        if self.num_chunks() <= 1 {
+            let len = self.remaining();
+            Ok(self.copy_to_bytes(len))
+        } else {
+            Err(self)
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BufList, BufListBody};
+use axum_core_05::{
+    body::Body,
+    response::{IntoResponse, Response},
+};
+use http_1::{header, HeaderValue};
+
+/// Responds with the list's chunks, without copying them.
+///
+/// As with `Bytes`, the `content-type` header is set to `application/octet-stream`. Since the
+/// length of the body is known up front, it's sent with a `content-length` header.
+impl IntoResponse for BufList {
+    fn into_response(self) -> Response {
+        BufListBody::new(self).into_response()
+    }
+}
+
+/// Responds with the list's chunks, followed by trailers if any were set.
+///
+/// This behaves the same as the implementation for [`BufList`].
+impl IntoResponse for BufListBody {
+    fn into_response(self) -> Response {
+        let mut res = Body::new(self).into_response();
+        res.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        );
+        res
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compression::{decode_to_end, encode_chunks},
+    BufList, Writer,
+};
+use base64_022::{
+    engine::general_purpose::STANDARD, read::DecoderReader, write::EncoderWriter, DecodeError,
+    Engine,
+};
+
+impl BufList {
+    /// Encodes the bytes in this list as base64, using the standard alphabet with padding.
+    ///
+    /// The list is encoded [chunk by chunk](crate#encoding-and-compression).
+    ///
+    /// Requires the `base64022` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"hel"[..], &b"lo"[..]].into_iter().collect();
+    /// let encoded = buf_list.encode_base64();
+    /// assert_eq!(encoded.to_str_lossy(), "aGVsbG8=");
+    ///
+    /// let decoded = encoded.decode_base64().unwrap();
+    /// assert_eq!(decoded.to_str_lossy(), "hello");
+    /// ```
+    pub fn encode_base64(&self) -> BufList {
+        self.encode_base64_with(&STANDARD)
+    }
+
+    /// Encodes the bytes in this list as base64, using the provided `base64` engine.
+    ///
+    /// This behaves like [`encode_base64`](Self::encode_base64), except that the engine
+    /// determines the alphabet and padding.
+    ///
+    /// Requires the `base64022` feature.
+    pub fn encode_base64_with<E: Engine>(&self, engine: &E) -> BufList {
+        encode_chunks(self, EncoderWriter::new(Writer::new(), engine))
+            .and_then(|mut encoder| encoder.finish())
+            .expect("writing to a Writer without a limit is infallible")
+            .into_inner()
+    }
+
+    /// Decodes the bytes in this list as base64, using the standard alphabet with padding.
+    ///
+    /// The list is decoded [chunk by chunk](crate#encoding-and-compression).
+    ///
+    /// Requires the `base64022` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list isn't valid base64. Offsets in the error are relative to the
+    /// start of the list.
+    pub fn decode_base64(&self) -> Result<BufList, DecodeError> {
+        self.decode_base64_with(&STANDARD)
+    }
+
+    /// Decodes the bytes in this list as base64, using the provided `base64` engine.
+    ///
+    /// This behaves like [`decode_base64`](Self::decode_base64), except that the engine
+    /// determines the alphabet and padding.
+    ///
+    /// Requires the `base64022` feature.
+    pub fn decode_base64_with<E: Engine>(&self, engine: &E) -> Result<BufList, DecodeError> {
+        decode_to_end(self, |reader| Ok(DecoderReader::new(reader, engine))).map_err(|error| {
+            // Reading from a list and writing to a Writer without a limit can't fail, so the only
+            // errors are decoding errors.
+            *error
+                .into_inner()
+                .and_then(|error| error.downcast::<DecodeError>().ok())
+                .expect("the only errors are decoding errors")
+        })
+    }
+}
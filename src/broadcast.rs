@@ -0,0 +1,281 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{sync::lock, BufList};
+use bytes::{Buf, Bytes};
+#[cfg(any(feature = "futures03", feature = "tokio1"))]
+use std::io;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// Creates an append-only [`BufList`] which any number of readers can read from as it grows.
+///
+/// Chunks added through the [`BroadcastWriter`] are shared with every [`BroadcastReader`] without
+/// copying them. Each reader starts at the beginning of the list and reads through it at its own
+/// pace, waiting for more chunks once it has read all of them. This makes it possible to fan out a
+/// stream of data to several consumers, like an in-memory log that's being tailed.
+///
+/// Since readers can start reading at any time, chunks are kept around until the writer and all
+/// the readers have been dropped.
+///
+/// More readers can be created with [`BroadcastWriter::subscribe`], or by cloning an existing
+/// reader, which creates a new reader at the same position. Once the writer is dropped, readers
+/// reach the end of the list after reading all the chunks.
+///
+/// With the `futures03` feature, [`BroadcastReader`] implements `Stream<Item = Bytes>`.
+///
+/// Readers also implement the `AsyncRead` and `AsyncBufRead` traits from `tokio` (with the
+/// `tokio1` feature) and `futures` (with the `futures03` feature). Reads wait for more data at the
+/// current end of the list instead of returning end-of-file, which makes a broadcast list usable
+/// as a staging buffer between a producer task and a consumer task. End-of-file is only reported
+/// once the writer calls [`BroadcastWriter::finish`]: if the writer is dropped without finishing,
+/// reads fail with [`UnexpectedEof`](std::io::ErrorKind::UnexpectedEof) instead.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let (mut writer, mut reader) = buf_list::broadcast();
+/// let mut other = writer.subscribe();
+///
+/// writer.push_chunk(&b"hello "[..]);
+/// assert_eq!(reader.next_chunk().await, Some(Bytes::from_static(b"hello ")));
+///
+/// writer.push_chunk(&b"world"[..]);
+/// drop(writer);
+/// assert_eq!(reader.next_chunk().await, Some(Bytes::from_static(b"world")));
+/// assert_eq!(reader.next_chunk().await, None);
+///
+/// // Each reader reads all the chunks, independently of the others.
+/// assert_eq!(other.next_chunk().await, Some(Bytes::from_static(b"hello ")));
+/// # }
+/// ```
+pub fn broadcast() -> (BroadcastWriter, BroadcastReader) {
+    let shared = Arc::new(Mutex::new(Shared {
+        list: BufList::new(),
+        writer_closed: false,
+        finished: false,
+        wakers: Vec::new(),
+    }));
+    let reader = BroadcastReader {
+        shared: shared.clone(),
+        next_chunk: 0,
+        current: Bytes::new(),
+    };
+    (BroadcastWriter { shared }, reader)
+}
+
+/// The state shared between the writer and readers.
+#[derive(Debug)]
+struct Shared {
+    list: BufList,
+    writer_closed: bool,
+    // Whether the writer was closed by calling finish, rather than by being dropped.
+    finished: bool,
+    // The readers waiting for more chunks.
+    wakers: Vec<Waker>,
+}
+
+impl Shared {
+    fn wake_all(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// The writing half of a [`broadcast`] list.
+#[derive(Debug)]
+pub struct BroadcastWriter {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl BroadcastWriter {
+    /// Adds a chunk to the end of the list, waking up any readers waiting for it.
+    ///
+    /// As with [`BufList::push_chunk`], the chunk isn't copied if it's [`Bytes`], and a
+    /// zero-length [`Buf`] isn't added to the list.
+    pub fn push_chunk<B: Buf>(&mut self, data: B) {
+        if !data.has_remaining() {
+            return;
+        }
+        let mut shared = lock(&self.shared);
+        shared.list.push_chunk(data);
+        shared.wake_all();
+    }
+
+    /// Creates a new reader which starts at the beginning of the list.
+    pub fn subscribe(&self) -> BroadcastReader {
+        BroadcastReader {
+            shared: self.shared.clone(),
+            next_chunk: 0,
+            current: Bytes::new(),
+        }
+    }
+
+    /// Marks the end of the list, and drops this writer.
+    ///
+    /// Once they've read all the chunks, readers report end-of-file through their `AsyncRead`
+    /// implementations. Dropping the writer without calling this makes them return an error
+    /// instead.
+    pub fn finish(self) {
+        lock(&self.shared).finished = true;
+    }
+
+    /// Returns the total number of bytes added to the list so far.
+    pub fn num_bytes(&self) -> usize {
+        lock(&self.shared).list.num_bytes()
+    }
+
+    /// Returns a copy of the list as it currently is.
+    ///
+    /// This is cheap: the chunks are shared with the returned list rather than copied.
+    pub fn to_buf_list(&self) -> BufList {
+        lock(&self.shared).list.clone()
+    }
+}
+
+impl Drop for BroadcastWriter {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.writer_closed = true;
+        shared.wake_all();
+    }
+}
+
+/// A reader for a [`broadcast`] list.
+///
+/// Cloning a reader creates a new reader at the same position.
+#[derive(Clone, Debug)]
+pub struct BroadcastReader {
+    shared: Arc<Mutex<Shared>>,
+    // The index of the next chunk to take from the list.
+    next_chunk: usize,
+    // The rest of the chunk currently being read through AsyncRead.
+    current: Bytes,
+}
+
+impl BroadcastReader {
+    /// Returns the number of chunks this reader has started reading so far.
+    #[inline]
+    pub fn chunk_index(&self) -> usize {
+        self.next_chunk
+    }
+
+    /// Returns the next chunk in the list, waiting for one to be added if necessary.
+    ///
+    /// The returned future resolves to `None` once the writer has been dropped and all the chunks
+    /// have been read.
+    pub fn next_chunk(&mut self) -> NextChunk<'_> {
+        NextChunk { reader: self }
+    }
+
+    /// Returns the next chunk in the list if one is available, without waiting.
+    ///
+    /// If a chunk was partly read through `AsyncRead`, the rest of it is returned.
+    pub fn try_next_chunk(&mut self) -> Option<Bytes> {
+        if !self.current.is_empty() {
+            return Some(std::mem::replace(&mut self.current, Bytes::new()));
+        }
+        let chunk = lock(&self.shared).list.get_chunk(self.next_chunk).cloned();
+        if chunk.is_some() {
+            self.next_chunk += 1;
+        }
+        chunk
+    }
+
+    pub(crate) fn poll_next_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        if !self.current.is_empty() {
+            return Poll::Ready(Some(std::mem::replace(&mut self.current, Bytes::new())));
+        }
+        self.poll_next_chunk_impl(cx).map(|(chunk, _)| chunk)
+    }
+
+    /// Takes the next chunk from the list, or waits for one to be added.
+    ///
+    /// Also returns whether the writer finished the list, which is only relevant if there are no
+    /// chunks left.
+    fn poll_next_chunk_impl(&mut self, cx: &mut Context<'_>) -> Poll<(Option<Bytes>, bool)> {
+        let mut shared = lock(&self.shared);
+        if let Some(chunk) = shared.list.get_chunk(self.next_chunk) {
+            let chunk = chunk.clone();
+            self.next_chunk += 1;
+            return Poll::Ready((Some(chunk), shared.finished));
+        }
+        if shared.writer_closed {
+            return Poll::Ready((None, shared.finished));
+        }
+        let waker = cx.waker();
+        if !shared.wakers.iter().any(|w| w.will_wake(waker)) {
+            shared.wakers.push(waker.clone());
+        }
+        Poll::Pending
+    }
+}
+
+// These are used by the AsyncRead and AsyncBufRead implementations.
+#[cfg(any(feature = "futures03", feature = "tokio1"))]
+impl BroadcastReader {
+    pub(crate) fn poll_fill_buf_impl(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        if self.current.is_empty() {
+            match self.poll_next_chunk_impl(cx) {
+                Poll::Ready((Some(chunk), _)) => self.current = chunk,
+                Poll::Ready((None, true)) => {}
+                Poll::Ready((None, false)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "broadcast writer was dropped without calling finish",
+                    )));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(&self.current))
+    }
+
+    pub(crate) fn consume_impl(&mut self, amt: usize) {
+        let amt = amt.min(self.current.len());
+        self.current.advance(amt);
+    }
+
+    #[cfg(feature = "futures03")]
+    pub(crate) fn poll_read_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let chunk = match self.poll_fill_buf_impl(cx) {
+            Poll::Ready(Ok(chunk)) => chunk,
+            Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let n = chunk.len().min(buf.len());
+        buf[..n].copy_from_slice(&chunk[..n]);
+        self.consume_impl(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+/// A future which returns the next chunk in a [`broadcast`] list.
+///
+/// Returned by [`BroadcastReader::next_chunk`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct NextChunk<'a> {
+    reader: &'a mut BroadcastReader,
+}
+
+impl<'a> Future for NextChunk<'a> {
+    type Output = Option<Bytes>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.reader.poll_next_chunk(cx)
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{writer::DEFAULT_CHUNK_SIZE, BufList, ChunkPool, Writer};
+
+/// A builder for a [`BufList`] with limits and a chunking policy.
+///
+/// A `BufList` is a plain list of chunks, so the policies configured here are enforced while data
+/// is being written: [`build`](Self::build) returns a [`Writer`], and
+/// [`Writer::into_inner`] returns the configured `BufList` once writing is done. The returned
+/// `BufList` doesn't carry the policies along: for example, chunks added to it later through
+/// [`BufList::push_chunk`] aren't coalesced.
+///
+/// Created with [`BufList::builder`].
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::BufList;
+/// use std::io::Write;
+///
+/// let mut writer = BufList::builder()
+///     .max_bytes(8)
+///     .chunk_size(4)
+///     .build();
+///
+/// // Writes are cut short at the limit.
+/// assert_eq!(writer.write(b"hello world").unwrap(), 8);
+/// assert!(writer.write(b"!").is_err());
+///
+/// let buf_list = writer.into_inner();
+/// assert_eq!(buf_list.num_chunks(), 2);
+/// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"hell"[..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BufListBuilder {
+    max_bytes: Option<u64>,
+    min_chunk_size: usize,
+    chunk_size: usize,
+    chunk_capacity: usize,
+    pool: Option<ChunkPool>,
+}
+
+impl BufListBuilder {
+    /// Creates a new builder with the default settings.
+    ///
+    /// By default, there's no limit on the number of bytes, chunks are never coalesced, and
+    /// [`Writer`]'s default chunk size of 8 KiB is used.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            max_bytes: None,
+            min_chunk_size: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            chunk_capacity: 0,
+            pool: None,
+        }
+    }
+
+    /// Sets the maximum total number of bytes that can be written.
+    ///
+    /// See [`Writer::max_bytes`] for how this limit is enforced.
+    #[inline]
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the minimum size of chunks that are added to the list as is.
+    ///
+    /// Chunks smaller than this that are passed into [`Writer::push_chunk`] (or
+    /// [`Writer`]'s `Extend` implementation) are coalesced by copying them into the current block.
+    /// Setting this to 0, the default, disables coalescing.
+    #[inline]
+    pub fn min_chunk_size(mut self, min_chunk_size: usize) -> Self {
+        self.min_chunk_size = min_chunk_size;
+        self
+    }
+
+    /// Sets the target size of the blocks that writes are coalesced into.
+    ///
+    /// This is ignored if a [`pool`](Self::pool) is set.
+    #[inline]
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Reserves space for `chunk_capacity` chunks in the list up front.
+    #[inline]
+    pub fn chunk_capacity(mut self, chunk_capacity: usize) -> Self {
+        self.chunk_capacity = chunk_capacity;
+        self
+    }
+
+    /// Draws the blocks that writes are coalesced into from `pool`.
+    ///
+    /// The chunk size is the pool's [`block_size`](ChunkPool::block_size).
+    #[inline]
+    pub fn pool(mut self, pool: ChunkPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Builds a [`Writer`] with these settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the chunk size is 0, or if the minimum chunk size is greater than the chunk size.
+    pub fn build(self) -> Writer {
+        let chunk_size = match &self.pool {
+            Some(pool) => pool.block_size(),
+            None => self.chunk_size,
+        };
+        let mut writer = Writer::with_chunk_sizes(self.min_chunk_size, chunk_size);
+        writer.configure(
+            BufList::with_capacity(self.chunk_capacity),
+            self.max_bytes,
+            self.pool,
+        );
+        writer
+    }
+}
+
+impl Default for BufListBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BufList {
+    /// Returns a [`BufListBuilder`] for configuring limits and a chunking policy.
+    #[inline]
+    pub fn builder() -> BufListBuilder {
+        BufListBuilder::new()
+    }
+}
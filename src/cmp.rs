@@ -0,0 +1,119 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Comparisons between `BufList`s.
+
+use crate::BufList;
+
+impl BufList {
+    /// Returns the number of leading bytes that this list has in common with `other`.
+    ///
+    /// The chunks of both lists are walked in lockstep, so neither list is flattened, and the
+    /// lists don't need to have the same chunk boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let a = vec![&b"hello "[..], &b"world"[..]].into_iter().collect::<BufList>();
+    /// let b = vec![&b"hel"[..], &b"lo wo"[..], &b"rms"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(a.common_prefix_len(&b), 9);
+    /// ```
+    pub fn common_prefix_len(&self, other: &BufList) -> u64 {
+        let mut self_chunks = self.iter();
+        let mut other_chunks = other.iter();
+        let mut self_chunk: &[u8] = &[];
+        let mut other_chunk: &[u8] = &[];
+        let mut common = 0u64;
+
+        loop {
+            if self_chunk.is_empty() {
+                match self_chunks.next() {
+                    Some(chunk) => self_chunk = chunk.as_ref(),
+                    None => return common,
+                }
+            }
+            if other_chunk.is_empty() {
+                match other_chunks.next() {
+                    Some(chunk) => other_chunk = chunk.as_ref(),
+                    None => return common,
+                }
+            }
+
+            let n = self_chunk.len().min(other_chunk.len());
+            let (self_head, self_tail) = self_chunk.split_at(n);
+            let (other_head, other_tail) = other_chunk.split_at(n);
+            if self_head != other_head {
+                let matching = self_head
+                    .iter()
+                    .zip(other_head)
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                return common + matching as u64;
+            }
+
+            common += n as u64;
+            self_chunk = self_tail;
+            other_chunk = other_tail;
+        }
+    }
+
+    /// Returns the position of the first byte at which this list differs from `other`, or `None`
+    /// if the two lists hold the same bytes.
+    ///
+    /// If one list is a strict prefix of the other, the position returned is the length of the
+    /// shorter list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let a = vec![&b"hello "[..], &b"world"[..]].into_iter().collect::<BufList>();
+    /// let b = vec![&b"hel"[..], &b"lo wo"[..], &b"rms"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(a.first_mismatch(&b), Some(9));
+    ///
+    /// let c = BufList::from(&b"hello world"[..]);
+    /// assert_eq!(a.first_mismatch(&c), None);
+    /// ```
+    pub fn first_mismatch(&self, other: &BufList) -> Option<u64> {
+        let common = self.common_prefix_len(other);
+        if common == self.total_len() && common == other.total_len() {
+            None
+        } else {
+            Some(common)
+        }
+    }
+
+    /// Returns true if this list and `other` are made up of the same chunks, without reading the
+    /// bytes in them.
+    ///
+    /// Two chunks are considered the same if they point to the same memory and have the same
+    /// length, which is the case for lists cloned from a common source. A return value of `false`
+    /// doesn't mean that the lists hold different bytes -- only that this couldn't be proven
+    /// cheaply. Use [`first_mismatch`](Self::first_mismatch) to compare contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Bytes;
+    ///
+    /// let a = BufList::from(Bytes::from(b"hello".to_vec()));
+    /// let b = a.clone();
+    /// assert!(a.shallow_eq(&b));
+    ///
+    /// // `c` holds the same bytes as `a`, but in different memory.
+    /// let c = BufList::from(Bytes::from(b"hello".to_vec()));
+    /// assert!(!a.shallow_eq(&c));
+    /// assert_eq!(a.first_mismatch(&c), None);
+    /// ```
+    pub fn shallow_eq(&self, other: &BufList) -> bool {
+        self.num_chunks() == other.num_chunks()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.as_ptr() == b.as_ptr() && a.len() == b.len())
+    }
+}
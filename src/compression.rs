@@ -0,0 +1,34 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plumbing shared by the streaming encoding and compression adapters.
+//!
+//! The behavior these helpers provide is documented in the "Encoding and compression" section of
+//! the crate documentation, which the adapters link to.
+
+use crate::{BufList, Cursor, Writer};
+use std::io::{self, Read, Write};
+
+/// Feeds the chunks in `buf_list` into `encoder` one at a time, returning the encoder so that the
+/// caller can finish it.
+pub(crate) fn encode_chunks<W: Write>(buf_list: &BufList, mut encoder: W) -> io::Result<W> {
+    for chunk in buf_list.iter() {
+        encoder.write_all(chunk)?;
+    }
+    Ok(encoder)
+}
+
+/// Creates a decoder over `buf_list` with `new_decoder`, and reads it to the end.
+///
+/// The decoder reads from a [`Cursor`], so the list isn't modified or copied. Its output is
+/// written into a new list in chunks of up to 8 KiB.
+pub(crate) fn decode_to_end<'a, R, F>(buf_list: &'a BufList, new_decoder: F) -> io::Result<BufList>
+where
+    R: Read,
+    F: FnOnce(Cursor<&'a BufList>) -> io::Result<R>,
+{
+    let mut decoder = new_decoder(Cursor::new(buf_list))?;
+    let mut writer = Writer::new();
+    io::copy(&mut decoder, &mut writer)?;
+    Ok(writer.into_inner())
+}
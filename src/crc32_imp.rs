@@ -0,0 +1,55 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+
+#[cfg(feature = "crc32fast1")]
+impl BufList {
+    /// Computes the CRC-32 (IEEE) checksum of the bytes in this list, using the `crc32fast`
+    /// crate.
+    ///
+    /// The checksum is computed one chunk at a time, and is the same regardless of how the list is
+    /// split into chunks. `crc32fast` uses hardware acceleration where it's available.
+    ///
+    /// Requires the `crc32fast1` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"1234"[..], &b"56789"[..]].into_iter().collect();
+    /// assert_eq!(buf_list.crc32(), 0xcbf4_3926);
+    /// ```
+    pub fn crc32(&self) -> u32 {
+        let mut hasher = crc32fast_1::Hasher::new();
+        for chunk in self.iter() {
+            hasher.update(chunk);
+        }
+        hasher.finalize()
+    }
+}
+
+#[cfg(feature = "crc32c06")]
+impl BufList {
+    /// Computes the CRC-32C (Castagnoli) checksum of the bytes in this list, using the `crc32c`
+    /// crate.
+    ///
+    /// The checksum is computed one chunk at a time, and is the same regardless of how the list is
+    /// split into chunks. `crc32c` uses hardware acceleration where it's available.
+    ///
+    /// Requires the `crc32c06` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"1234"[..], &b"56789"[..]].into_iter().collect();
+    /// assert_eq!(buf_list.crc32c(), 0xe306_9283);
+    /// ```
+    pub fn crc32c(&self) -> u32 {
+        self.iter()
+            .fold(0, |crc, chunk| crc32c_06::crc32c_append(crc, chunk))
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BufList, Cursor};
+use bytes::{Buf, Bytes};
+use std::io::IoSlice;
+
+impl<T: AsRef<BufList>> Cursor<T> {
+    /// Returns a [`Buf`] which reads from the current position of this cursor.
+    ///
+    /// Advancing the returned [`CursorBuf`] advances the cursor, and the underlying list is left
+    /// untouched. This makes it possible to pass the rest of a list to APIs that consume a `Buf`
+    /// without giving up the list or the cursor's position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use bytes::Buf;
+    ///
+    /// let buf_list: BufList = vec![&b"hello"[..], &b" world"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    /// cursor.set_position(3);
+    ///
+    /// let mut buf = cursor.as_buf();
+    /// assert_eq!(buf.get_u16(), u16::from_be_bytes(*b"lo"));
+    /// assert_eq!(buf.chunk(), b" world");
+    ///
+    /// assert_eq!(cursor.position(), 5);
+    /// ```
+    #[inline]
+    pub fn as_buf(&mut self) -> CursorBuf<'_, T> {
+        CursorBuf { cursor: self }
+    }
+}
+
+/// A [`Buf`] which reads from a [`Cursor`], advancing it as data is consumed.
+///
+/// Returned by [`Cursor::as_buf`].
+pub struct CursorBuf<'a, T> {
+    cursor: &'a mut Cursor<T>,
+}
+
+impl<'a, T: AsRef<BufList>> CursorBuf<'a, T> {
+    /// Returns the current position of the underlying cursor.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.cursor.position()
+    }
+
+    /// Gets a reference to the underlying cursor.
+    #[inline]
+    pub fn get_ref(&self) -> &Cursor<T> {
+        self.cursor
+    }
+}
+
+impl<T: AsRef<BufList>> Buf for CursorBuf<'_, T> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        // Saturate rather than overflow on 32-bit targets, like `BufList` does.
+        let remaining = self.cursor.remaining();
+        if remaining > std::usize::MAX as u64 {
+            std::usize::MAX
+        } else {
+            remaining as usize
+        }
+    }
+
+    fn chunk(&self) -> &[u8] {
+        let cursor = &*self.cursor;
+        cursor.data.fill_buf_impl(cursor.inner.as_ref())
+    }
+
+    fn chunks_vectored<'iovs>(&'iovs self, iovs: &mut [IoSlice<'iovs>]) -> usize {
+        if iovs.is_empty() {
+            return 0;
+        }
+
+        let cursor = &*self.cursor;
+        let list = cursor.inner.as_ref();
+        let first = cursor.data.fill_buf_impl(list);
+        if first.is_empty() {
+            return 0;
+        }
+        iovs[0] = IoSlice::new(first);
+
+        // The rest of the chunks are returned in full.
+        let mut filled = 1;
+        for (iov, chunk) in iovs[1..]
+            .iter_mut()
+            .zip(list.iter().skip(cursor.data.chunk + 1))
+        {
+            *iov = IoSlice::new(chunk);
+            filled += 1;
+        }
+        filled
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        let cursor = &mut *self.cursor;
+        let list = cursor.inner.as_ref();
+        let remaining = cursor.data.remaining(list);
+        assert!(
+            cnt as u64 <= remaining,
+            "cannot advance past `remaining`: {} <= {}",
+            cnt,
+            remaining
+        );
+        cursor.data.consume_impl(list, cnt);
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        // read_bytes_impl is zero-copy if the bytes are within a single chunk.
+        let cursor = &mut *self.cursor;
+        let list = cursor.inner.as_ref();
+        match cursor.data.read_bytes_impl(list, len) {
+            Ok(bytes) => bytes,
+            Err(_) => panic!(
+                "`len` ({}) greater than remaining ({})",
+                len,
+                cursor.data.remaining(list)
+            ),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{errors::ReadExactError, BufList, Cursor};
+use std::io::{self, SeekFrom};
+
+impl<T: AsRef<BufList>> Cursor<T> {
+    /// Creates an adapter which reads the rest of this cursor, then the rest of `next`.
+    ///
+    /// The returned [`Chain`] treats the two lists as one continuous list: positions and seeks
+    /// span both of them, with the second list starting at the end of the first. This is useful
+    /// to parse, for example, a header that was buffered separately from the body.
+    ///
+    /// Unlike [`Read::chain`](io::Read::chain), the returned adapter can seek.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::{BufRead, Seek, SeekFrom};
+    ///
+    /// let header = BufList::from(&b"Content-Length: 5\n"[..]);
+    /// let body: BufList = vec![&b"hel"[..], &b"lo"[..]].into_iter().collect();
+    ///
+    /// let mut chain = Cursor::new(header).chain_cursor(Cursor::new(body));
+    /// let mut line = String::new();
+    /// chain.read_line(&mut line).unwrap();
+    /// assert_eq!(line, "Content-Length: 5\n");
+    ///
+    /// // Seeks span both lists.
+    /// assert_eq!(chain.seek(SeekFrom::End(-4)).unwrap(), 19);
+    /// line.clear();
+    /// chain.read_line(&mut line).unwrap();
+    /// assert_eq!(line, "ello");
+    /// ```
+    pub fn chain_cursor<U: AsRef<BufList>>(self, next: Cursor<U>) -> Chain<T, U> {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+}
+
+/// A reader over the concatenation of two [`Cursor`]s.
+///
+/// Returned by [`Cursor::chain_cursor`].
+#[derive(Clone)]
+pub struct Chain<T, U> {
+    // Reads come from first until it's exhausted, then from second.
+    first: Cursor<T>,
+    second: Cursor<U>,
+}
+
+impl<T: AsRef<BufList>, U: AsRef<BufList>> Chain<T, U> {
+    /// Returns the current position within the combined list.
+    ///
+    /// While reading the first list, this is the position within it. Afterwards, this is the
+    /// length of the first list plus the position within the second list.
+    pub fn position(&self) -> u64 {
+        if self.first.is_empty() {
+            self.first.total_len() + self.second.position()
+        } else {
+            self.first.position()
+        }
+    }
+
+    /// Returns the combined length of both lists.
+    #[inline]
+    pub fn total_len(&self) -> u64 {
+        self.first.total_len() + self.second.total_len()
+    }
+
+    /// Gets references to the underlying cursors.
+    #[inline]
+    pub fn get_ref(&self) -> (&Cursor<T>, &Cursor<U>) {
+        (&self.first, &self.second)
+    }
+
+    /// Gets mutable references to the underlying cursors.
+    ///
+    /// Care should be taken when moving the cursors, since that affects the position of this
+    /// instance.
+    #[inline]
+    pub fn get_mut(&mut self) -> (&mut Cursor<T>, &mut Cursor<U>) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes this instance, returning the underlying cursors.
+    #[inline]
+    pub fn into_inner(self) -> (Cursor<T>, Cursor<U>) {
+        (self.first, self.second)
+    }
+}
+
+impl<T: AsRef<BufList>, U: AsRef<BufList>> io::Read for Chain<T, U> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.first.is_empty() {
+            io::Read::read(&mut self.second, buf)
+        } else {
+            io::Read::read(&mut self.first, buf)
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        let remaining = self.first.remaining() + self.second.remaining();
+        if remaining < buf.len() as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                ReadExactError {
+                    remaining,
+                    buf_len: buf.len(),
+                },
+            ));
+        }
+
+        // A single read from the first cursor may return fewer bytes than it has left (for
+        // example, with a maximum read size set), so read exactly what's needed from each.
+        let n = self.first.remaining().min(buf.len() as u64) as usize;
+        io::Read::read_exact(&mut self.first, &mut buf[..n])?;
+        io::Read::read_exact(&mut self.second, &mut buf[n..])
+    }
+}
+
+impl<T: AsRef<BufList>, U: AsRef<BufList>> io::BufRead for Chain<T, U> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.first.is_empty() {
+            io::BufRead::fill_buf(&mut self.second)
+        } else {
+            io::BufRead::fill_buf(&mut self.first)
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.first.is_empty() {
+            io::BufRead::consume(&mut self.second, amt);
+        } else {
+            io::BufRead::consume(&mut self.first, amt);
+        }
+    }
+}
+
+impl<T: AsRef<BufList>, U: AsRef<BufList>> io::Seek for Chain<T, U> {
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        let (base_pos, offset) = match style {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (self.total_len(), n),
+            SeekFrom::Current(n) => (self.position(), n),
+        };
+        // This is the same as CursorData::seek_impl.
+        let new_pos = if offset >= 0 {
+            base_pos.checked_add(offset as u64)
+        } else {
+            base_pos.checked_sub(offset.wrapping_neg() as u64)
+        };
+        match new_pos {
+            Some(n) => {
+                let first_len = self.first.total_len();
+                if n < first_len {
+                    self.first.set_position(n);
+                    self.second.set_position(0);
+                } else {
+                    self.first.set_position(first_len);
+                    self.second.set_position(n - first_len);
+                }
+                Ok(n)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{BufList, Cursor};
+use bytes::Bytes;
+use futures_core_03::Stream;
 use futures_io_03::{AsyncBufRead, AsyncRead, AsyncSeek};
 use std::{
     io::{self, IoSliceMut, SeekFrom},
@@ -46,3 +48,17 @@ impl<T: AsRef<BufList> + Unpin> AsyncBufRead for Cursor<T> {
         io::BufRead::consume(&mut *self, amt)
     }
 }
+
+impl<T: AsRef<BufList> + Unpin> Stream for Cursor<T> {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        Poll::Ready(this.data.next_chunk_impl(this.inner.as_ref()).map(Ok))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.data.remaining_chunks_impl(self.inner.as_ref());
+        (n, Some(n))
+    }
+}
@@ -1,18 +1,35 @@
 // Copyright (c) The buf-list Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+mod buf;
+mod chain;
 #[cfg(feature = "futures03")]
 mod futures_imp;
+mod take;
 #[cfg(test)]
 mod tests;
 #[cfg(feature = "tokio1")]
 mod tokio_imp;
+mod typed;
 
-use crate::{errors::ReadExactError, BufList};
-use bytes::Bytes;
+pub use buf::*;
+pub use chain::*;
+pub use take::*;
+
+use crate::{
+    errors::{ReadExactError, VarintError},
+    search::find_in_chunks,
+    split::ChunkPos,
+    utf8::{Utf8Piece, Utf8Pieces},
+    BufList, BufListMut, SegmentedBuf,
+};
+use bytes::{Buf, Bytes};
 use std::{
     cmp::Ordering,
+    convert::TryFrom,
     io::{self, IoSliceMut, SeekFrom},
+    iter::{self, FusedIterator},
+    ops::RangeBounds,
 };
 
 /// A `Cursor` wraps an in-memory `BufList` and provides it with a [`Seek`] implementation.
@@ -23,6 +40,43 @@ use std::{
 /// The cursor may either own or borrow a `BufList`: both `Cursor<BufList>` and `Cursor<&BufList>`
 /// are supported.
 ///
+/// A cursor only stores its position: the index of chunk start positions used for seeking is
+/// cached on the `BufList` itself. To have several readers share one large list, for example
+/// to read ranges of it in parallel, use `Cursor<Arc<BufList>>`. Cloning such a cursor is O(1),
+/// and all the clones share the same list and index.
+///
+/// ```
+/// use buf_list::{BufList, Cursor};
+/// use std::{io::Read, sync::Arc, thread};
+///
+/// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+/// let cursor = Cursor::new(Arc::new(buf_list));
+///
+/// let handles: Vec<_> = [0, 6]
+///     .iter()
+///     .map(|&pos| {
+///         let mut cursor = cursor.clone();
+///         thread::spawn(move || {
+///             cursor.set_position(pos);
+///             let mut buf = [0; 5];
+///             cursor.read_exact(&mut buf).unwrap();
+///             buf
+///         })
+///     })
+///     .collect();
+///
+/// let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+/// assert_eq!(results, vec![*b"hello", *b"world"]);
+/// ```
+///
+/// A `Cursor<&mut BufListMut>` implements [`Write`] and [`Seek`], and can be used to overwrite
+/// data in place and to append data at the end of a [`BufListMut`], similar to a
+/// `std::io::Cursor<&mut Vec<u8>>`.
+///
+/// To pass the rest of a list to an API that consumes a [`Buf`], use [`as_buf`](Self::as_buf).
+/// Unlike reading from the `BufList` directly, this doesn't modify or clone the list, and the
+/// cursor can be seeked back afterwards.
+///
 /// # Optional features
 ///
 /// * `tokio1`: With this feature enabled, [`Cursor`] implements the `tokio` crate's
@@ -30,9 +84,12 @@ use std::{
 ///   [`AsyncBufRead`](tokio::io::AsyncBufRead).
 /// * `futures03`: With this feature enabled, [`Cursor`] implements the `futures` crate's
 ///   [`AsyncSeek`](futures_io_03::AsyncSeek), [`AsyncRead`](futures_io_03::AsyncRead) and
-///   [`AsyncBufRead`](futures_io_03::AsyncBufRead).
+///   [`AsyncBufRead`](futures_io_03::AsyncBufRead). It also implements
+///   [`Stream`](futures_core_03::Stream), yielding the rest of the current chunk and then each
+///   remaining chunk, which makes it possible to seek to a position and then stream from there.
 ///
 /// [`Read`]: std::io::Read
+/// [`Write`]: std::io::Write
 /// [`BufRead`]: std::io::BufRead
 /// [`Seek`]: std::io::Seek
 pub struct Cursor<T> {
@@ -40,11 +97,22 @@ pub struct Cursor<T> {
 
     /// Data associated with the cursor.
     data: CursorData,
+
+    /// Scratch space for `fill_buf_at_least` (used when the requested bytes span chunks) and
+    /// `read_line`.
+    scratch: Vec<u8>,
+
+    /// The most bytes a single read may return. Set by `with_max_read_size`.
+    max_read_size: usize,
 }
 
-impl<T: AsRef<BufList>> Cursor<T> {
+impl<T> Cursor<T> {
     /// Creates a new cursor wrapping the provided `BufList`.
     ///
+    /// This is O(1). Seeking within the list uses an index of chunk positions that's cached on the
+    /// `BufList` itself, so it's built at most once no matter how many cursors are created over
+    /// the same list.
+    ///
     /// # Examples
     ///
     /// ```
@@ -54,7 +122,50 @@ impl<T: AsRef<BufList>> Cursor<T> {
     /// ```
     pub fn new(inner: T) -> Cursor<T> {
         let data = CursorData::new();
-        Cursor { inner, data }
+        Cursor {
+            inner,
+            data,
+            scratch: Vec::new(),
+            max_read_size: std::usize::MAX,
+        }
+    }
+
+    /// Limits the number of bytes returned by each read to at most `max_read_size`.
+    ///
+    /// Readers are allowed to return fewer bytes than requested, and code that assumes a single
+    /// `read` fills the whole buffer is a common source of bugs. Since a `Cursor` normally fills
+    /// buffers as far as its data allows, this is meant to be used in tests to simulate the short
+    /// reads that sockets and pipes produce.
+    ///
+    /// This applies to [`Read::read`](io::Read::read),
+    /// [`Read::read_vectored`](io::Read::read_vectored),
+    /// [`BufRead::fill_buf`](io::BufRead::fill_buf) and the `poll_read` methods of the async
+    /// traits. Methods that are defined to read everything they ask for, like `read_exact` and
+    /// `read_to_end`, aren't affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_read_size` is 0, since a read that returns 0 bytes signals end-of-file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::Read;
+    ///
+    /// let mut cursor = Cursor::new(BufList::from(&b"hello world"[..])).with_max_read_size(4);
+    /// let mut buf = [0; 8];
+    /// assert_eq!(cursor.read(&mut buf).unwrap(), 4);
+    /// assert_eq!(&buf[..4], b"hell");
+    ///
+    /// // read_exact still fills the whole buffer.
+    /// cursor.read_exact(&mut buf[..7]).unwrap();
+    /// assert_eq!(&buf[..7], b"o world");
+    /// ```
+    pub fn with_max_read_size(mut self, max_read_size: usize) -> Self {
+        assert!(max_read_size > 0, "max_read_size must be greater than 0");
+        self.max_read_size = max_read_size;
+        self
     }
 
     /// Consumes this cursor, returning the underlying value.
@@ -104,6 +215,33 @@ impl<T: AsRef<BufList>> Cursor<T> {
         &self.inner
     }
 
+    /// Gets a mutable reference to the underlying value in this cursor.
+    ///
+    /// After modifying the underlying list, call [`refresh`](Self::refresh) before using the
+    /// cursor again. Otherwise, the cursor may return incorrect data or panic.
+    ///
+    /// # Examples
+    ///
+    /// Interleave reading from a list with appending newly received chunks to it:
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::Read;
+    ///
+    /// let mut cursor = Cursor::new(BufList::from(&b"hello"[..]));
+    /// let mut buf = [0; 5];
+    /// cursor.read_exact(&mut buf).unwrap();
+    /// assert_eq!(&buf, b"hello");
+    ///
+    /// cursor.get_mut().push_chunk(&b"world"[..]);
+    /// cursor.refresh();
+    /// cursor.read_exact(&mut buf).unwrap();
+    /// assert_eq!(&buf, b"world");
+    /// ```
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
     /// Returns the current position of this cursor.
     ///
     /// # Examples
@@ -151,7 +289,9 @@ impl<T: AsRef<BufList>> Cursor<T> {
     pub fn position(&self) -> u64 {
         self.data.pos
     }
+}
 
+impl<T: AsRef<BufList>> Cursor<T> {
     /// Sets the position of this cursor.
     ///
     /// # Examples
@@ -173,6 +313,463 @@ impl<T: AsRef<BufList>> Cursor<T> {
         self.data.set_pos(self.inner.as_ref(), pos);
     }
 
+    /// Returns the total number of bytes in the underlying list, regardless of the current
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let mut cursor = Cursor::new(BufList::from(&[1, 2, 3, 4, 5][..]));
+    /// cursor.set_position(2);
+    /// assert_eq!(cursor.total_len(), 5);
+    /// ```
+    #[inline]
+    pub fn total_len(&self) -> u64 {
+        self.data.num_bytes(self.inner.as_ref())
+    }
+
+    /// Returns the number of bytes remaining between the current position and the end of the
+    /// list.
+    ///
+    /// If the position is past the end of the list, this is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let mut cursor = Cursor::new(BufList::from(&[1, 2, 3, 4, 5][..]));
+    /// cursor.set_position(2);
+    /// assert_eq!(cursor.remaining(), 3);
+    ///
+    /// cursor.set_position(8);
+    /// assert_eq!(cursor.remaining(), 0);
+    /// ```
+    #[inline]
+    pub fn remaining(&self) -> u64 {
+        self.data.remaining(self.inner.as_ref())
+    }
+
+    /// Returns true if there are no bytes remaining between the current position and the end of
+    /// the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let mut cursor = Cursor::new(BufList::from(&[1, 2, 3, 4, 5][..]));
+    /// assert!(!cursor.is_empty());
+    ///
+    /// cursor.set_position(5);
+    /// assert!(cursor.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Returns the index of the chunk the cursor is currently in.
+    ///
+    /// If the cursor is at or past the end of the list, this is the number of chunks in the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let buf_list: BufList = vec![&b"hello"[..], &b"world"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    /// assert_eq!(cursor.chunk_index(), 0);
+    ///
+    /// cursor.set_position(5);
+    /// assert_eq!(cursor.chunk_index(), 1);
+    ///
+    /// cursor.set_position(10);
+    /// assert_eq!(cursor.chunk_index(), 2);
+    /// ```
+    #[inline]
+    pub fn chunk_index(&self) -> usize {
+        self.data.chunk
+    }
+
+    /// Moves the cursor to the start of the chunk at `index`, returning the new position.
+    ///
+    /// This is O(1), since the start positions of chunks are cached. Seeking to `index` equal to
+    /// the number of chunks moves the cursor to the end of the list.
+    ///
+    /// # Errors
+    ///
+    /// If `index` is greater than the number of chunks, this returns an error of kind
+    /// [`io::ErrorKind::InvalidInput`], and the cursor is not moved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::BufRead;
+    ///
+    /// // One record per chunk.
+    /// let buf_list: BufList = vec![&b"first"[..], &b"second"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    ///
+    /// assert_eq!(cursor.seek_to_chunk(1).unwrap(), 5);
+    /// assert_eq!(cursor.fill_buf().unwrap(), b"second");
+    /// assert!(cursor.seek_to_chunk(3).is_err());
+    /// ```
+    pub fn seek_to_chunk(&mut self, index: usize) -> io::Result<u64> {
+        self.data.seek_to_chunk_impl(self.inner.as_ref(), index)
+    }
+
+    /// Copies data from the current position into `buf` without advancing the cursor, returning
+    /// the number of bytes copied.
+    ///
+    /// This is like [`Read::read`](io::Read::read), except that the position doesn't change. The
+    /// number of bytes copied is less than `buf.len()` only if the end of the list is reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let buf_list: BufList = vec![&b"hello"[..], &b"world"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    /// cursor.set_position(3);
+    ///
+    /// let mut buf = [0; 4];
+    /// assert_eq!(cursor.peek(&mut buf), 4);
+    /// assert_eq!(&buf, b"lowo");
+    /// assert_eq!(cursor.position(), 3);
+    ///
+    /// let mut buf = [0; 16];
+    /// assert_eq!(cursor.peek(&mut buf), 7);
+    /// ```
+    pub fn peek(&self, buf: &mut [u8]) -> usize {
+        self.data.clone().read_impl(self.inner.as_ref(), buf)
+    }
+
+    /// Fills `buf` with data from the current position without advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `buf.len()` bytes are remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let cursor = Cursor::new(BufList::from(&b"\x01\x02rest"[..]));
+    ///
+    /// let mut tag = [0; 2];
+    /// cursor.peek_exact(&mut tag).unwrap();
+    /// assert_eq!(tag, [1, 2]);
+    /// assert_eq!(cursor.position(), 0);
+    ///
+    /// let mut buf = [0; 8];
+    /// assert!(cursor.peek_exact(&mut buf).is_err());
+    /// ```
+    pub fn peek_exact(&self, buf: &mut [u8]) -> io::Result<()> {
+        self.data.clone().read_exact_impl(self.inner.as_ref(), buf)
+    }
+
+    /// Returns at least `n` contiguous bytes starting from the current position, without
+    /// advancing the cursor.
+    ///
+    /// This is like [`BufRead::fill_buf`](io::BufRead::fill_buf), except that the returned slice
+    /// is guaranteed to be at least `n` bytes long. If the rest of the current chunk has at least
+    /// `n` bytes, it is returned without copying. Otherwise, exactly `n` bytes are copied into a
+    /// scratch buffer owned by the cursor, which is reused across calls.
+    ///
+    /// Call [`BufRead::consume`](io::BufRead::consume) to advance the cursor past the bytes that
+    /// were used.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `n` bytes are remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::BufRead;
+    ///
+    /// let buf_list: BufList = vec![&b"hea"[..], &b"der!body"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    ///
+    /// // The header spans two chunks, so it's copied.
+    /// assert_eq!(cursor.fill_buf_at_least(7).unwrap(), b"header!");
+    /// cursor.consume(7);
+    ///
+    /// // The rest of the current chunk is long enough, so it's borrowed.
+    /// assert_eq!(cursor.fill_buf_at_least(2).unwrap(), b"body");
+    /// assert!(cursor.fill_buf_at_least(5).is_err());
+    /// ```
+    pub fn fill_buf_at_least(&mut self, n: usize) -> io::Result<&[u8]> {
+        let list = self.inner.as_ref();
+        let remaining = self.data.remaining(list);
+        if remaining < n as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                ReadExactError {
+                    remaining,
+                    buf_len: n,
+                },
+            ));
+        }
+
+        let chunk = self.data.fill_buf_impl(list);
+        if chunk.len() >= n {
+            return Ok(chunk);
+        }
+
+        // The window straddles chunks, so copy it into the scratch buffer.
+        self.scratch.clear();
+        self.scratch.resize(n, 0);
+        self.data.clone().read_impl(list, &mut self.scratch);
+        Ok(&self.scratch)
+    }
+
+    /// Returns the position of the next occurrence of `byte` at or after the current position,
+    /// without moving the cursor.
+    ///
+    /// The returned position is absolute, i.e. relative to the start of the list. Each chunk is
+    /// scanned with `memchr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let buf_list: BufList = vec![&b"a\nb"[..], &b"c\nd"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    /// assert_eq!(cursor.position_of(b'\n'), Some(1));
+    ///
+    /// cursor.set_position(2);
+    /// assert_eq!(cursor.position_of(b'\n'), Some(4));
+    /// assert_eq!(cursor.position_of(b'a'), None);
+    /// ```
+    pub fn position_of(&self, byte: u8) -> Option<u64> {
+        let list = self.inner.as_ref();
+        let mut pos = self.data.pos;
+        for chunk in self.data.remaining_slices(list) {
+            if let Some(index) = memchr::memchr(byte, chunk) {
+                return Some(pos + index as u64);
+            }
+            pos += chunk.len() as u64;
+        }
+        None
+    }
+
+    /// Returns the position of the next occurrence of `needle` at or after the current position,
+    /// without moving the cursor.
+    ///
+    /// The returned position is absolute, i.e. relative to the start of the list. Matches that
+    /// straddle chunk boundaries are found as well. An empty `needle` matches at the current
+    /// position, as long as it isn't past the end of the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let buf_list: BufList = vec![&b"a\r\nb\r"[..], &b"\nc"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    /// cursor.set_position(2);
+    /// assert_eq!(cursor.position_of_slice(b"\r\n"), Some(4));
+    /// ```
+    pub fn position_of_slice(&self, needle: &[u8]) -> Option<u64> {
+        let list = self.inner.as_ref();
+        if self.data.pos > list.total_len() {
+            return None;
+        }
+        find_in_chunks(self.data.remaining_slices(list), needle).map(|index| self.data.pos + index)
+    }
+
+    /// Reads the next `n` bytes as an owned [`Bytes`], advancing the cursor past them.
+    ///
+    /// If the bytes are all within a single chunk, this is zero-copy: the returned `Bytes` is a
+    /// slice of the chunk. Otherwise, the bytes are copied into a new buffer. To avoid copying
+    /// even across chunk boundaries, use [`read_buf_list`](Self::read_buf_list).
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `n` bytes are remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let buf_list: BufList = vec![&b"hello"[..], &b"world"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    ///
+    /// // This is within the first chunk, so it doesn't copy.
+    /// let hell = cursor.read_bytes(4).unwrap();
+    /// assert_eq!(hell, &b"hell"[..]);
+    /// assert_eq!(hell.as_ptr(), buf_list.get_chunk(0).unwrap().as_ptr());
+    ///
+    /// // This crosses a chunk boundary, so it's copied.
+    /// assert_eq!(cursor.read_bytes(3).unwrap(), &b"owo"[..]);
+    ///
+    /// // Not enough bytes are left.
+    /// assert!(cursor.read_bytes(4).is_err());
+    /// assert_eq!(cursor.position(), 7);
+    /// ```
+    pub fn read_bytes(&mut self, n: usize) -> io::Result<Bytes> {
+        self.data.read_bytes_impl(self.inner.as_ref(), n)
+    }
+
+    /// Reads the next `n` bytes as a [`BufList`], advancing the cursor past them.
+    ///
+    /// This never copies any data: the chunks in the returned list are slices of the chunks in the
+    /// underlying list, even if the bytes span several chunks.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `n` bytes are remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+    ///
+    /// # Examples
+    ///
+    /// Split a buffered stream of length-prefixed messages into one `BufList` per message:
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::Read;
+    ///
+    /// let buf_list: BufList = vec![&b"\x03ab"[..], &b"c\x02de"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    ///
+    /// let mut messages = Vec::new();
+    /// let mut len = [0];
+    /// while cursor.read_exact(&mut len).is_ok() {
+    ///     messages.push(cursor.read_buf_list(len[0] as usize).unwrap());
+    /// }
+    ///
+    /// assert_eq!(messages.len(), 2);
+    /// assert_eq!(messages[0].num_chunks(), 2);
+    /// assert_eq!(messages[0].to_str_lossy(), "abc");
+    /// assert_eq!(messages[1].to_str_lossy(), "de");
+    /// ```
+    pub fn read_buf_list(&mut self, n: usize) -> io::Result<BufList> {
+        self.data.read_buf_list_impl(self.inner.as_ref(), n)
+    }
+
+    /// Returns an iterator over the remaining data, as chunks starting from the current position.
+    ///
+    /// This doesn't copy any data: the first chunk is sliced at the current position, and the
+    /// rest are cheap clones of the chunks in the underlying list. The cursor is not advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::Read;
+    ///
+    /// let buf_list: BufList = vec![&b"hello"[..], &b"world"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(&buf_list);
+    ///
+    /// let mut buf = [0; 3];
+    /// cursor.read_exact(&mut buf).unwrap();
+    ///
+    /// let chunks: Vec<_> = cursor.remaining_chunks().collect();
+    /// assert_eq!(chunks, vec![&b"lo"[..], &b"world"[..]]);
+    /// ```
+    pub fn remaining_chunks(&self) -> RemainingChunks<'_> {
+        let list = self.inner.as_ref();
+        let (first, next_chunk) = match self.data.get_chunk_and_pos(list) {
+            // The current chunk is returned separately, sliced at the current position.
+            Some((chunk, chunk_pos)) => (Some(chunk.slice(chunk_pos..)), self.data.chunk + 1),
+            None => (None, list.num_chunks()),
+        };
+        RemainingChunks {
+            list,
+            first,
+            next_chunk,
+            end_chunk: list.num_chunks(),
+        }
+    }
+
+    /// Consumes this cursor, splitting the underlying list at the current position into the data
+    /// before it and the data after it.
+    ///
+    /// This doesn't copy any data: the chunks in the returned lists are slices of the chunks in the
+    /// underlying list. If the position is past the end of the list, the second list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::{BufRead, Read};
+    ///
+    /// let buf_list: BufList = vec![&b"Header: "[..], &b"1\nbody"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(buf_list);
+    ///
+    /// let mut header = String::new();
+    /// cursor.read_line(&mut header).unwrap();
+    ///
+    /// let (consumed, rest) = cursor.split_at_current();
+    /// assert_eq!(consumed.to_str_lossy(), "Header: 1\n");
+    /// assert_eq!(rest.to_str_lossy(), "body");
+    /// ```
+    pub fn split_at_current(self) -> (BufList, BufList) {
+        let list = self.inner.as_ref();
+        let pos = self.data.pos.min(self.data.num_bytes(list));
+        (list.slice(..pos), list.slice(pos..))
+    }
+
+    /// Returns a new cursor over the bytes in `range` of the underlying list.
+    ///
+    /// The new cursor starts at position 0, which corresponds to the start of the range.
+    /// Seeking to [`SeekFrom::End`] is relative to the end of the range, and reads report
+    /// end-of-file there. This cursor's position is left unchanged.
+    ///
+    /// This doesn't copy any data: like [`BufList::slice`], the chunks in the new cursor's list
+    /// are slices of the chunks in the underlying list.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the end of the range is
+    /// past the end of the list.
+    ///
+    /// # Examples
+    ///
+    /// Serving an HTTP range request for bytes 4-8 (inclusive) of a buffered object:
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::{Read, Seek, SeekFrom};
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let cursor = Cursor::new(&buf_list);
+    ///
+    /// let mut range = cursor.slice(4..=8);
+    /// let mut s = String::new();
+    /// range.read_to_string(&mut s).unwrap();
+    /// assert_eq!(s, "o wor");
+    ///
+    /// assert_eq!(range.seek(SeekFrom::End(-2)).unwrap(), 3);
+    /// assert_eq!(range.read_u8().unwrap(), b'o');
+    /// ```
+    pub fn slice(&self, range: impl RangeBounds<u64>) -> Cursor<BufList> {
+        Cursor::new(self.inner.as_ref().slice(range))
+    }
+
+    /// Brings this cursor up to date with changes made to the underlying list through
+    /// [`get_mut`](Self::get_mut), keeping its current position.
+    ///
+    /// This is O(log n) in the number of chunks. The list's index of chunk positions is extended
+    /// as chunks are pushed, so interleaving appends with reads doesn't rebuild it.
+    pub fn refresh(&mut self) {
+        self.data.refresh(self.inner.as_ref());
+    }
+
     // ---
     // Helper methods
     // ---
@@ -191,6 +788,9 @@ where
         Cursor {
             inner: self.inner.clone(),
             data: self.data.clone(),
+            // The scratch space is only used for the duration of a borrow, so don't copy it.
+            scratch: Vec::new(),
+            max_read_size: self.max_read_size,
         }
     }
 
@@ -198,6 +798,7 @@ where
     fn clone_from(&mut self, other: &Self) {
         self.inner.clone_from(&other.inner);
         self.data = other.data.clone();
+        self.max_read_size = other.max_read_size;
     }
 }
 
@@ -210,34 +811,217 @@ impl<T: AsRef<BufList>> io::Seek for Cursor<T> {
     fn stream_position(&mut self) -> io::Result<u64> {
         Ok(self.data.pos)
     }
+
+    #[cfg(seek_relative)]
+    fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        self.data.seek_relative_impl(self.inner.as_ref(), offset)
+    }
 }
 
 impl<T: AsRef<BufList>> io::Read for Cursor<T> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        Ok(self.data.read_impl(self.inner.as_ref(), buf))
+        let len = buf.len().min(self.max_read_size);
+        Ok(self.data.read_impl(self.inner.as_ref(), &mut buf[..len]))
     }
 
     fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let total_len: usize = bufs.iter().map(|buf| buf.len()).sum();
+        if total_len > self.max_read_size {
+            // Like the default implementation, read into the first non-empty buffer.
+            let buf = bufs
+                .iter_mut()
+                .find(|buf| !buf.is_empty())
+                .map_or(&mut [][..], |buf| &mut **buf);
+            return io::Read::read(self, buf);
+        }
         Ok(self.data.read_vectored_impl(self.inner.as_ref(), bufs))
     }
 
-    // TODO: is_read_vectored once that's available on stable Rust.
+    // read_vectored is efficient: each copy spans as much of a chunk as fits in a buffer.
+    #[cfg(can_vector)]
+    fn is_read_vectored(&self) -> bool {
+        true
+    }
 
     fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
         self.data.read_exact_impl(self.inner.as_ref(), buf)
     }
+
+    #[cfg(read_buf)]
+    fn read_buf(&mut self, cursor: io::BorrowedCursor<'_>) -> io::Result<()> {
+        self.data
+            .read_buf_impl(self.inner.as_ref(), cursor, self.max_read_size);
+        Ok(())
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        Ok(self.data.read_to_end_impl(self.inner.as_ref(), buf))
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.data.read_to_string_impl(self.inner.as_ref(), buf)
+    }
 }
 
 impl<T: AsRef<BufList>> io::BufRead for Cursor<T> {
     fn fill_buf(&mut self) -> io::Result<&[u8]> {
-        Ok(self.data.fill_buf_impl(self.inner.as_ref()))
+        let chunk = self.data.fill_buf_impl(self.inner.as_ref());
+        Ok(&chunk[..chunk.len().min(self.max_read_size)])
     }
 
     fn consume(&mut self, amt: usize) {
         self.data.consume_impl(self.inner.as_ref(), amt);
     }
+
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        Ok(self.data.read_until_impl(self.inner.as_ref(), byte, buf))
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        // Read the line into scratch space first, so that only the new bytes need to be validated
+        // (and so that buf is unchanged if they aren't valid UTF-8, like std's implementation).
+        let mut line = std::mem::replace(&mut self.scratch, Vec::new());
+        line.clear();
+        let n = self
+            .data
+            .read_until_impl(self.inner.as_ref(), b'\n', &mut line);
+        let res = match std::str::from_utf8(&line) {
+            Ok(s) => {
+                buf.push_str(s);
+                Ok(n)
+            }
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            )),
+        };
+        self.scratch = line;
+        res
+    }
+}
+
+impl Cursor<&mut BufListMut> {
+    /// Sets the position of this cursor.
+    ///
+    /// The position may be past the end of the list. Writing at such a position fills the gap
+    /// with zeroes, as with [`std::io::Cursor`].
+    ///
+    /// # Examples
+    ///
+    /// Backpatch a length prefix after writing a body:
+    ///
+    /// ```
+    /// use buf_list::{BufListMut, Cursor};
+    /// use std::io::Write;
+    ///
+    /// let mut buf_list = BufListMut::new();
+    /// let mut cursor = Cursor::new(&mut buf_list);
+    ///
+    /// // Skip over the length prefix, and write the body.
+    /// cursor.set_position(2);
+    /// cursor.write_all(b"hello").unwrap();
+    ///
+    /// // Now go back and fill in the length.
+    /// let len = cursor.position() as u16 - 2;
+    /// cursor.set_position(0);
+    /// cursor.write_all(&len.to_be_bytes()).unwrap();
+    ///
+    /// assert_eq!(buf_list.freeze().to_str_lossy(), "\0\x05hello");
+    /// ```
+    pub fn set_position(&mut self, pos: u64) {
+        self.data.set_pos(&*self.inner, pos);
+    }
+}
+
+impl io::Seek for Cursor<&mut BufListMut> {
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        self.data.seek_impl(&*self.inner, style)
+    }
+}
+
+/// Overwrites data in place at the current position, and appends any data that doesn't fit to the
+/// end of the list.
+///
+/// Data appended to the end of the list is added to its last chunk.
+impl io::Write for Cursor<&mut BufListMut> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.write_impl(self.inner, buf)?;
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An iterator over the data remaining in a [`Cursor`], as [`Bytes`] chunks.
+///
+/// Returned by [`Cursor::remaining_chunks`].
+#[derive(Clone, Debug)]
+pub struct RemainingChunks<'a> {
+    list: &'a BufList,
+    first: Option<Bytes>,
+    // The indexes of the chunks after `first` that haven't been returned yet.
+    next_chunk: usize,
+    end_chunk: usize,
+}
+
+impl<'a> RemainingChunks<'a> {
+    #[inline]
+    fn chunk_at(&self, index: usize) -> Bytes {
+        self.list
+            .get_chunk(index)
+            .expect("index is less than the number of chunks")
+            .clone()
+    }
+}
+
+impl<'a> Iterator for RemainingChunks<'a> {
+    type Item = Bytes;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first.take() {
+            return Some(first);
+        }
+        if self.next_chunk < self.end_chunk {
+            let chunk = self.chunk_at(self.next_chunk);
+            self.next_chunk += 1;
+            Some(chunk)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
 }
 
+impl<'a> DoubleEndedIterator for RemainingChunks<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next_chunk < self.end_chunk {
+            self.end_chunk -= 1;
+            Some(self.chunk_at(self.end_chunk))
+        } else {
+            self.first.take()
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for RemainingChunks<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.first.is_some() as usize + (self.end_chunk - self.next_chunk)
+    }
+}
+
+impl<'a> FusedIterator for RemainingChunks<'a> {}
+
 #[derive(Clone, Debug)]
 struct CursorData {
     /// The chunk number the cursor is pointing to. Kept in sync with pos.
@@ -256,7 +1040,7 @@ impl CursorData {
     }
 
     #[cfg(test)]
-    fn assert_invariants(&self, list: &BufList) -> anyhow::Result<()> {
+    fn assert_invariants<B: Buf>(&self, list: &SegmentedBuf<B>) -> anyhow::Result<()> {
         use anyhow::ensure;
 
         ensure!(
@@ -279,7 +1063,26 @@ impl CursorData {
         Ok(())
     }
 
-    fn seek_impl(&mut self, list: &BufList, style: SeekFrom) -> io::Result<u64> {
+    fn refresh(&mut self, list: &BufList) {
+        self.chunk = ChunkPos::at(list, self.pos).chunk();
+    }
+
+    fn seek_to_chunk_impl(&mut self, list: &BufList, index: usize) -> io::Result<u64> {
+        // start_pos has an additional entry at the end, for the end of the list.
+        match list.get_start_pos().get(index) {
+            Some(&pos) => {
+                self.chunk = index;
+                self.pos = pos;
+                Ok(pos)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunk index out of range",
+            )),
+        }
+    }
+
+    fn seek_impl<B: Buf>(&mut self, list: &SegmentedBuf<B>, style: SeekFrom) -> io::Result<u64> {
         let (base_pos, offset) = match style {
             SeekFrom::Start(n) => {
                 self.set_pos(list, n);
@@ -288,24 +1091,22 @@ impl CursorData {
             SeekFrom::End(n) => (self.num_bytes(list), n),
             SeekFrom::Current(n) => (self.pos, n),
         };
-        // Can't use checked_add_signed since it was only stabilized in Rust 1.66. This is adapted
-        // from
-        // https://github.com/rust-lang/rust/blame/ed937594d3/library/std/src/io/cursor.rs#L295-L299.
-        let new_pos = if offset >= 0 {
-            base_pos.checked_add(offset as u64)
-        } else {
-            base_pos.checked_sub(offset.wrapping_neg() as u64)
-        };
-        match new_pos {
-            Some(n) => {
-                self.set_pos(list, n);
-                Ok(self.pos)
-            }
-            None => Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "invalid seek to a negative or overflowing position",
-            )),
-        }
+        let new_pos = offset_pos(base_pos, offset)?;
+        self.set_pos(list, new_pos);
+        Ok(self.pos)
+    }
+
+    #[cfg(seek_relative)]
+    fn seek_relative_impl<B: Buf>(
+        &mut self,
+        list: &SegmentedBuf<B>,
+        offset: i64,
+    ) -> io::Result<()> {
+        // set_pos doesn't do a binary search if the new position is within the current chunk,
+        // which is the common case for small hops.
+        let new_pos = offset_pos(self.pos, offset)?;
+        self.set_pos(list, new_pos);
+        Ok(())
     }
 
     fn read_impl(&mut self, list: &BufList, buf: &mut [u8]) -> usize {
@@ -339,21 +1140,38 @@ impl CursorData {
     }
 
     fn read_vectored_impl(&mut self, list: &BufList, bufs: &mut [IoSliceMut<'_>]) -> usize {
+        // Walk the chunks and the buffers together, so that each copy is as large as possible: it
+        // ends either at a chunk boundary or at a buffer boundary. The position is only updated
+        // once at the end.
+        let mut chunk = self.fill_buf_impl(list);
+        let mut rest = list.iter().skip(self.chunk + 1);
         let mut nread = 0;
-        for buf in bufs {
-            // Copy data from the buffer until we run out of bytes to copy.
-            let n = self.read_impl(list, buf);
-            nread += n;
-            if n < buf.len() {
-                break;
+        'bufs: for buf in bufs.iter_mut() {
+            let mut buf: &mut [u8] = &mut *buf;
+            while !buf.is_empty() {
+                if chunk.is_empty() {
+                    match rest.next() {
+                        Some(next) => chunk = next.as_ref(),
+                        // The end of the list has been reached.
+                        None => break 'bufs,
+                    }
+                }
+                let n = chunk.len().min(buf.len());
+                buf[..n].copy_from_slice(&chunk[..n]);
+                chunk = &chunk[n..];
+                let tmp = buf;
+                buf = &mut tmp[n..];
+                nread += n;
             }
         }
+
+        self.consume_impl(list, nread);
         nread
     }
 
     fn read_exact_impl(&mut self, list: &BufList, buf: &mut [u8]) -> io::Result<()> {
         // This is the same as read_impl as long as there's enough space.
-        let remaining = self.num_bytes(list).saturating_sub(self.pos);
+        let remaining = self.remaining(list);
         let buf_len = buf.len();
         if remaining < buf_len as u64 {
             return Err(io::Error::new(
@@ -366,6 +1184,272 @@ impl CursorData {
         Ok(())
     }
 
+    fn write_impl(&mut self, list: &mut BufListMut, mut buf: &[u8]) -> io::Result<()> {
+        let num_bytes = self.num_bytes(list);
+        if self.pos > num_bytes {
+            // Fill the gap with zeroes. Like std::io::Cursor, do this even if buf is empty.
+            let gap = usize::try_from(self.pos - num_bytes).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "cursor position exceeds maximum possible buffer length",
+                )
+            })?;
+            list.extend_back(&vec![0; gap]);
+            self.chunk = list.num_chunks();
+        }
+
+        // Overwrite existing data in place.
+        while !buf.is_empty() {
+            let chunk_pos = match self.get_chunk_and_pos(list) {
+                Some((_, chunk_pos)) => chunk_pos,
+                None => break,
+            };
+            let chunk = list
+                .segment_mut(self.chunk)
+                .expect("get_chunk_and_pos returned a chunk");
+            let dest = &mut chunk[chunk_pos..];
+            let n_to_copy = dest.len().min(buf.len());
+            dest[..n_to_copy].copy_from_slice(&buf[..n_to_copy]);
+            buf = &buf[n_to_copy..];
+
+            // Increment the position.
+            self.pos += n_to_copy as u64;
+            // If we've finished writing through the chunk, move to the next chunk.
+            if n_to_copy == dest.len() {
+                self.chunk += 1;
+            }
+        }
+
+        if !buf.is_empty() {
+            // The cursor is at the end of the list, so append the rest of the data.
+            list.extend_back(buf);
+
+            // The cursor is now at the end of the list.
+            self.pos += buf.len() as u64;
+            self.chunk = list.num_chunks();
+        }
+
+        Ok(())
+    }
+
+    fn read_bytes_impl(&mut self, list: &BufList, n: usize) -> io::Result<Bytes> {
+        let remaining = self.remaining(list);
+        if remaining < n as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                ReadExactError {
+                    remaining,
+                    buf_len: n,
+                },
+            ));
+        }
+        if n == 0 {
+            return Ok(Bytes::new());
+        }
+
+        let (chunk, chunk_pos) = self
+            .get_chunk_and_pos(list)
+            .expect("there's at least one byte remaining");
+        if chunk.len() - chunk_pos >= n {
+            // Within a single chunk: slice it without copying.
+            let bytes = chunk.slice(chunk_pos..(chunk_pos + n));
+            self.set_pos(list, self.pos + n as u64);
+            Ok(bytes)
+        } else {
+            let mut buf = vec![0; n];
+            self.read_impl(list, &mut buf);
+            Ok(buf.into())
+        }
+    }
+
+    fn read_buf_list_impl(&mut self, list: &BufList, n: usize) -> io::Result<BufList> {
+        let remaining = self.remaining(list);
+        if remaining < n as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                ReadExactError {
+                    remaining,
+                    buf_len: n,
+                },
+            ));
+        }
+
+        if n == 0 {
+            return Ok(BufList::new());
+        }
+
+        let end = self.pos + n as u64;
+        let buf_list = list.slice(self.pos..end);
+        self.set_pos(list, end);
+        Ok(buf_list)
+    }
+
+    fn read_varint_impl(&mut self, list: &BufList) -> io::Result<u64> {
+        // Work on a copy so that the cursor isn't advanced on errors.
+        let mut data = self.clone();
+        let mut value = 0u64;
+        let mut shift = 0;
+        loop {
+            let chunk = data.fill_buf_impl(list);
+            if chunk.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    VarintError::Truncated {
+                        remaining: self.remaining(list),
+                    },
+                ));
+            }
+
+            // The number of bytes in this chunk that are part of the varint, if it ends here.
+            let mut end = None;
+            for (i, &byte) in chunk.iter().enumerate() {
+                // The 10th byte can only contribute the top bit of a u64.
+                if shift == 63 && byte > 1 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        VarintError::Overflow,
+                    ));
+                }
+                value |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    end = Some(i + 1);
+                    break;
+                }
+                shift += 7;
+            }
+
+            match end {
+                Some(n) => {
+                    data.consume_impl(list, n);
+                    *self = data;
+                    return Ok(value);
+                }
+                None => {
+                    // The varint continues into the next chunk.
+                    let n = chunk.len();
+                    data.consume_impl(list, n);
+                }
+            }
+        }
+    }
+
+    #[cfg(read_buf)]
+    fn read_buf_impl(
+        &mut self,
+        list: &BufList,
+        mut cursor: io::BorrowedCursor<'_>,
+        mut max_read_size: usize,
+    ) {
+        // Copy straight into the unfilled part of the buffer, without initializing it first.
+        while cursor.capacity() > 0 && max_read_size > 0 {
+            let chunk = self.fill_buf_impl(list);
+            if chunk.is_empty() {
+                break;
+            }
+            let n = chunk.len().min(cursor.capacity()).min(max_read_size);
+            cursor.append(&chunk[..n]);
+            self.consume_impl(list, n);
+            max_read_size -= n;
+        }
+    }
+
+    fn read_to_end_impl(&mut self, list: &BufList, buf: &mut Vec<u8>) -> usize {
+        // The number of bytes to be read is known exactly, so reserve space for all of them up
+        // front rather than growing buf as the default implementation does.
+        let remaining = self.remaining(list);
+        buf.reserve_exact(usize::try_from(remaining).unwrap_or(std::usize::MAX));
+
+        let mut nread = 0;
+        loop {
+            let chunk = self.fill_buf_impl(list);
+            if chunk.is_empty() {
+                return nread;
+            }
+            buf.extend_from_slice(chunk);
+            let n = chunk.len();
+            self.consume_impl(list, n);
+            nread += n;
+        }
+    }
+
+    fn read_to_string_impl(&mut self, list: &BufList, buf: &mut String) -> io::Result<usize> {
+        let remaining = self.remaining(list);
+        let old_len = buf.len();
+        buf.reserve_exact(usize::try_from(remaining).unwrap_or(std::usize::MAX));
+
+        // Validate each chunk as it's copied over, stitching together code points that straddle
+        // chunk boundaries.
+        let mut valid = true;
+        for piece in Utf8Pieces::new(self.remaining_slices(list)) {
+            match piece {
+                Utf8Piece::Str(s) => buf.push_str(s),
+                Utf8Piece::Char(c) => buf.push(c),
+                Utf8Piece::Invalid { .. } => {
+                    valid = false;
+                    break;
+                }
+            }
+        }
+
+        if valid {
+            self.set_pos(list, self.pos + remaining);
+            Ok(remaining as usize)
+        } else {
+            // Like std::io::Cursor, leave both buf and the position unchanged.
+            buf.truncate(old_len);
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ))
+        }
+    }
+
+    fn read_until_impl(&mut self, list: &BufList, byte: u8, buf: &mut Vec<u8>) -> usize {
+        let mut nread = 0;
+        loop {
+            let (done, used) = {
+                let chunk = self.fill_buf_impl(list);
+                // Scan the whole chunk with memchr, then copy it over in bulk.
+                match memchr::memchr(byte, chunk) {
+                    Some(index) => {
+                        buf.extend_from_slice(&chunk[..=index]);
+                        (true, index + 1)
+                    }
+                    None => {
+                        buf.extend_from_slice(chunk);
+                        // An empty chunk means the end of the list has been reached.
+                        (chunk.is_empty(), chunk.len())
+                    }
+                }
+            };
+            self.consume_impl(list, used);
+            nread += used;
+            if done {
+                return nread;
+            }
+        }
+    }
+
+    #[cfg(feature = "futures03")]
+    fn next_chunk_impl(&mut self, list: &BufList) -> Option<Bytes> {
+        let (chunk, chunk_pos) = self.get_chunk_and_pos(list)?;
+        // Move to the start of the next chunk.
+        self.pos += (chunk.len() - chunk_pos) as u64;
+        self.chunk += 1;
+        Some(chunk.slice(chunk_pos..))
+    }
+
+    #[cfg(feature = "futures03")]
+    fn remaining_chunks_impl(&self, list: &BufList) -> usize {
+        list.num_chunks().saturating_sub(self.chunk)
+    }
+
+    /// Returns the rest of the current chunk, followed by the remaining chunks.
+    fn remaining_slices<'a>(&'a self, list: &'a BufList) -> impl Iterator<Item = &'a [u8]> {
+        iter::once(self.fill_buf_impl(list))
+            .chain(list.iter().skip(self.chunk + 1).map(|chunk| chunk.as_ref()))
+    }
+
     fn fill_buf_impl<'a>(&'a self, list: &'a BufList) -> &[u8] {
         const EMPTY_SLICE: &[u8] = &[];
         match self.get_chunk_and_pos(list) {
@@ -379,7 +1463,7 @@ impl CursorData {
         self.set_pos(list, self.pos + amt as u64);
     }
 
-    fn set_pos(&mut self, list: &BufList, new_pos: u64) {
+    fn set_pos<B: Buf>(&mut self, list: &SegmentedBuf<B>, new_pos: u64) {
         match new_pos.cmp(&self.pos) {
             Ordering::Greater => {
                 let start_pos = list.get_start_pos();
@@ -441,7 +1525,7 @@ impl CursorData {
     }
 
     #[inline]
-    fn get_chunk_and_pos<'b>(&self, list: &'b BufList) -> Option<(&'b Bytes, usize)> {
+    fn get_chunk_and_pos<'b, B: Buf>(&self, list: &'b SegmentedBuf<B>) -> Option<(&'b B, usize)> {
         match list.get_chunk(self.chunk) {
             Some(chunk) => {
                 // This guarantees that pos is not past the end of the list.
@@ -463,12 +1547,32 @@ impl CursorData {
         }
     }
 
-    fn num_bytes(&self, list: &BufList) -> u64 {
-        *list
-            .get_start_pos()
-            .last()
-            .expect("start_pos always has at least one element")
+    #[inline]
+    fn num_bytes<B>(&self, list: &SegmentedBuf<B>) -> u64 {
+        list.total_len()
     }
+
+    #[inline]
+    fn remaining<B>(&self, list: &SegmentedBuf<B>) -> u64 {
+        self.num_bytes(list).saturating_sub(self.pos)
+    }
+}
+
+fn offset_pos(base_pos: u64, offset: i64) -> io::Result<u64> {
+    // Can't use checked_add_signed since it was only stabilized in Rust 1.66. This is adapted
+    // from
+    // https://github.com/rust-lang/rust/blame/ed937594d3/library/std/src/io/cursor.rs#L295-L299.
+    let new_pos = if offset >= 0 {
+        base_pos.checked_add(offset as u64)
+    } else {
+        base_pos.checked_sub(offset.wrapping_neg() as u64)
+    };
+    new_pos.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
 }
 
 /// This is the same as Option<T> except Offset and Eof are reversed in ordering, i.e. Eof >
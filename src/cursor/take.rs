@@ -0,0 +1,177 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{errors::ReadExactError, BufList, Cursor};
+use std::io::{self, SeekFrom};
+
+impl<T: AsRef<BufList>> Cursor<T> {
+    /// Creates an adapter which reads at most `limit` bytes from the current position of this
+    /// cursor.
+    ///
+    /// The returned [`Take`] reports end-of-file once `limit` bytes have been read, and seeks
+    /// within it are relative to the window and clamped to it. This makes it safe to hand a
+    /// length-delimited section of a list to a sub-parser.
+    ///
+    /// Unlike [`Read::take`](io::Read::take), the returned adapter can seek, and the cursor can be
+    /// recovered with [`Take::into_inner`], positioned wherever the sub-parser stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    /// use std::io::{Read, Seek, SeekFrom};
+    ///
+    /// let buf_list: BufList = vec![&b"\x05hel"[..], &b"lo world"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(buf_list);
+    ///
+    /// let len = cursor.read_u8().unwrap();
+    /// let mut section = cursor.take_window(len.into());
+    ///
+    /// let mut s = String::new();
+    /// section.read_to_string(&mut s).unwrap();
+    /// assert_eq!(s, "hello");
+    ///
+    /// // Seeks are relative to the start of the window.
+    /// assert_eq!(section.seek(SeekFrom::End(-2)).unwrap(), 3);
+    ///
+    /// let cursor = section.into_inner();
+    /// assert_eq!(cursor.position(), 4);
+    /// ```
+    pub fn take_window(self, limit: u64) -> Take<T> {
+        let start = self.position();
+        Take {
+            inner: self,
+            start,
+            end: start.saturating_add(limit),
+        }
+    }
+}
+
+/// A reader over a window of bytes from a [`Cursor`].
+///
+/// Returned by [`Cursor::take_window`].
+#[derive(Clone)]
+pub struct Take<T> {
+    inner: Cursor<T>,
+    // The window is [start, end), in terms of positions in the underlying list.
+    start: u64,
+    end: u64,
+}
+
+impl<T: AsRef<BufList>> Take<T> {
+    /// Returns the number of bytes that can be read before this instance reports end-of-file.
+    ///
+    /// This may be more than the number of bytes remaining in the underlying list.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.end.saturating_sub(self.inner.position())
+    }
+
+    /// Sets the number of bytes that can be read before this instance reports end-of-file,
+    /// starting from the current position.
+    ///
+    /// The start of the window isn't changed.
+    #[inline]
+    pub fn set_limit(&mut self, limit: u64) {
+        self.end = self.inner.position().saturating_add(limit);
+    }
+
+    /// Returns the current position within the window.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.inner.position().saturating_sub(self.start)
+    }
+
+    /// Gets a reference to the underlying cursor.
+    #[inline]
+    pub fn get_ref(&self) -> &Cursor<T> {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying cursor.
+    ///
+    /// Care should be taken to avoid moving the cursor, since reads and seeks are relative to the
+    /// window.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut Cursor<T> {
+        &mut self.inner
+    }
+
+    /// Consumes this instance, returning the underlying cursor.
+    ///
+    /// The cursor is left wherever reads and seeks through this instance moved it.
+    #[inline]
+    pub fn into_inner(self) -> Cursor<T> {
+        self.inner
+    }
+
+    /// Returns the length of the window, which is clamped to the end of the underlying list.
+    fn window_len(&self) -> u64 {
+        self.end
+            .min(self.inner.total_len())
+            .saturating_sub(self.start)
+    }
+}
+
+impl<T: AsRef<BufList>> io::Read for Take<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max = self.limit().min(buf.len() as u64) as usize;
+        io::Read::read(&mut self.inner, &mut buf[..max])
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        let limit = self.limit();
+        if limit < buf.len() as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                ReadExactError {
+                    remaining: limit.min(self.inner.remaining()),
+                    buf_len: buf.len(),
+                },
+            ));
+        }
+        io::Read::read_exact(&mut self.inner, buf)
+    }
+}
+
+impl<T: AsRef<BufList>> io::BufRead for Take<T> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let limit = self.limit();
+        let buf = io::BufRead::fill_buf(&mut self.inner)?;
+        let max = limit.min(buf.len() as u64) as usize;
+        Ok(&buf[..max])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Don't let the cursor go past the end of the window.
+        let amt = self.limit().min(amt as u64) as usize;
+        io::BufRead::consume(&mut self.inner, amt);
+    }
+}
+
+impl<T: AsRef<BufList>> io::Seek for Take<T> {
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        let (base_pos, offset) = match style {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (self.window_len(), n),
+            SeekFrom::Current(n) => (self.position(), n),
+        };
+        // This is the same as CursorData::seek_impl.
+        let new_pos = if offset >= 0 {
+            base_pos.checked_add(offset as u64)
+        } else {
+            base_pos.checked_sub(offset.wrapping_neg() as u64)
+        };
+        match new_pos {
+            Some(n) => {
+                let n = n.min(self.window_len());
+                self.inner.set_position(self.start + n);
+                Ok(n)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
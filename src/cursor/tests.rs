@@ -5,11 +5,11 @@
 
 use crate::BufList;
 use anyhow::{bail, ensure, Context, Result};
-use bytes::{Buf, Bytes};
+use bytes::{Buf, BytesMut};
 use proptest::prelude::*;
 use std::{
     fmt,
-    io::{self, BufRead, IoSliceMut, Read, Seek, SeekFrom},
+    io::{self, BufRead, IoSliceMut, Read, Seek, SeekFrom, Write},
 };
 use test_strategy::{proptest, Arbitrary};
 
@@ -36,9 +36,110 @@ fn proptest_cursor_ops(
     eprintln!("**** success");
 }
 
+/// Assert that a writable cursor over a BufListMut behaves identically to std::io::Cursor over a
+/// Vec<u8>.
+#[proptest]
+fn proptest_cursor_write_ops(
+    #[strategy(buf_list_strategy())] buf_list: BufList,
+    #[strategy(prop::collection::vec(any::<CursorWriteOp>(), 0..64))] ops: Vec<CursorWriteOp>,
+) {
+    let mut oracle_vec = buf_list
+        .clone()
+        .copy_to_bytes(buf_list.remaining())
+        .to_vec();
+    let mut buf_list_mut = crate::BufListMut::new();
+    for chunk in &buf_list {
+        buf_list_mut.push_segment(BytesMut::from(&chunk[..]));
+    }
+    let mut buf_list_cursor = crate::Cursor::new(&mut buf_list_mut);
+    let mut oracle_cursor = io::Cursor::new(&mut oracle_vec);
+
+    for (index, op) in ops.into_iter().enumerate() {
+        let num_bytes = oracle_cursor.get_ref().len();
+        match op {
+            CursorWriteOp::SetPosition(index) => {
+                // Allow going past the end of the list a bit.
+                let index = index.index(1 + num_bytes * 5 / 4) as u64;
+                buf_list_cursor.set_position(index);
+                oracle_cursor.set_position(index);
+            }
+            CursorWriteOp::SeekCurrent(index) => {
+                let index = index.index(1 + num_bytes * 3 / 2) as i64;
+                // Center the index at roughly 0.
+                let style = SeekFrom::Current(index - (num_bytes * 3 / 4) as i64);
+                CursorOp::assert_io_result_eq(
+                    buf_list_cursor.seek(style),
+                    oracle_cursor.seek(style),
+                )
+                .with_context(|| format!("for index {}", index))
+                .unwrap();
+            }
+            CursorWriteOp::Write(data) => {
+                CursorOp::assert_io_result_eq(
+                    buf_list_cursor.write(&data),
+                    oracle_cursor.write(&data),
+                )
+                .with_context(|| format!("for index {}", index))
+                .unwrap();
+            }
+        }
+        assert_eq!(buf_list_cursor.position(), oracle_cursor.position());
+        buf_list_cursor
+            .data
+            .assert_invariants(&*buf_list_cursor.inner)
+            .with_context(|| format!("for index {}", index))
+            .unwrap();
+    }
+
+    let num_bytes = buf_list_mut.num_bytes();
+    assert!(
+        buf_list_mut.copy_to_bytes(num_bytes) == oracle_vec,
+        "contents didn't match"
+    );
+}
+
+#[cfg(read_buf)]
+#[test]
+fn test_cursor_read_buf() {
+    use std::{io::BorrowedBuf, mem::MaybeUninit};
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..], &b"fghij"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = crate::Cursor::new(&buf_list);
+    cursor.set_position(1);
+
+    let mut storage = [MaybeUninit::uninit(); 6];
+    let mut buf: BorrowedBuf<'_> = (&mut storage[..]).into();
+    cursor.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"bcdefg");
+    assert_eq!(cursor.position(), 7);
+
+    let mut storage = [MaybeUninit::uninit(); 6];
+    let mut buf: BorrowedBuf<'_> = (&mut storage[..]).into();
+    cursor.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"hij");
+    cursor.read_buf(buf.unfilled()).unwrap();
+    assert_eq!(buf.filled(), b"hij");
+}
+
+#[derive(Arbitrary, Clone, Debug)]
+enum CursorWriteOp {
+    SetPosition(prop::sample::Index),
+    SeekCurrent(prop::sample::Index),
+    Write(#[strategy(prop::collection::vec(any::<u8>(), 0..64))] Vec<u8>),
+}
+
+#[cfg(feature = "proptest1")]
+fn buf_list_strategy() -> impl Strategy<Value = BufList> {
+    crate::proptest_strategies::buf_list()
+}
+
+// Keep in sync with proptest_strategies::buf_list.
+#[cfg(not(feature = "proptest1"))]
 fn buf_list_strategy() -> impl Strategy<Value = BufList> {
     prop::collection::vec(prop::collection::vec(any::<u8>(), 1..128), 0..32)
-        .prop_map(|chunks| chunks.into_iter().map(Bytes::from).collect())
+        .prop_map(|chunks| chunks.into_iter().map(bytes::Bytes::from).collect())
 }
 
 #[derive(Arbitrary, Clone, Debug)]
@@ -47,12 +148,22 @@ enum CursorOp {
     SeekStart(prop::sample::Index),
     SeekEnd(prop::sample::Index),
     SeekCurrent(prop::sample::Index),
+    #[cfg(seek_relative)]
+    SeekRelative(prop::sample::Index),
     Read(prop::sample::Index),
     ReadVectored(
         #[strategy(prop::collection::vec(any::<prop::sample::Index>(), 0..8))]
         Vec<prop::sample::Index>,
     ),
     ReadExact(prop::sample::Index),
+    ReadBytes(prop::sample::Index),
+    ReadBufList(prop::sample::Index),
+    Peek(prop::sample::Index),
+    PeekExact(prop::sample::Index),
+    ReadToEnd,
+    ReadToString,
+    ReadUntil(u8),
+    ReadLine,
     // fill_buf can't be tested here because oracle is a contiguous block. Instead, we check its
     // return value separately.
     Consume(prop::sample::Index),
@@ -113,6 +224,18 @@ impl CursorOp {
                 Self::assert_io_result_eq(buf_list_res, oracle_res)
                     .context("operation result didn't match")?;
             }
+            #[cfg(seek_relative)]
+            Self::SeekRelative(index) => {
+                let index = index.index(1 + num_bytes * 3 / 2) as i64;
+                // Center the offset at roughly 0.
+                let offset = index - (num_bytes * 3 / 4) as i64;
+                eprintln!("offset: {}", offset);
+
+                let buf_list_res = buf_list.seek_relative(offset);
+                let oracle_res = oracle.seek_relative(offset);
+                Self::assert_io_result_eq(buf_list_res, oracle_res)
+                    .context("operation result didn't match")?;
+            }
             Self::Read(index) => {
                 let buf_size = index.index(1 + num_bytes * 5 / 4);
                 eprintln!("buf_size: {}", buf_size);
@@ -176,6 +299,104 @@ impl CursorOp {
                     .context("operation result didn't match")?;
                 ensure!(buf_list_buf == oracle_buf, "read buffer matches");
             }
+            Self::ReadBytes(index) => {
+                let n = index.index(1 + num_bytes * 5 / 4);
+                eprintln!("n: {}", n);
+
+                let mut oracle_buf = vec![0u8; n];
+                let buf_list_res = buf_list.read_bytes(n);
+                let oracle_res = oracle.read_exact(&mut oracle_buf);
+                if let Ok(bytes) = &buf_list_res {
+                    ensure!(bytes == &oracle_buf, "read bytes match");
+                }
+                Self::assert_io_result_eq(buf_list_res.map(drop), oracle_res)
+                    .context("operation result didn't match")?;
+            }
+            Self::ReadBufList(index) => {
+                let n = index.index(1 + num_bytes * 5 / 4);
+                eprintln!("n: {}", n);
+
+                let mut oracle_buf = vec![0u8; n];
+                let buf_list_res = buf_list.read_buf_list(n);
+                let oracle_res = oracle.read_exact(&mut oracle_buf);
+                if let Ok(read) = &buf_list_res {
+                    let bytes = read.clone().copy_to_bytes(read.remaining());
+                    ensure!(bytes == oracle_buf, "read buf list matches");
+                }
+                Self::assert_io_result_eq(buf_list_res.map(drop), oracle_res)
+                    .context("operation result didn't match")?;
+            }
+            Self::Peek(index) => {
+                let buf_size = index.index(1 + num_bytes * 5 / 4);
+                eprintln!("buf_size: {}", buf_size);
+
+                let mut buf_list_buf = vec![0u8; buf_size];
+                let mut oracle_buf = vec![0u8; buf_size];
+
+                let buf_list_n = buf_list.peek(&mut buf_list_buf);
+                let oracle_res = oracle.clone().read(&mut oracle_buf);
+                Self::assert_io_result_eq(Ok(buf_list_n), oracle_res)
+                    .context("operation result didn't match")?;
+                ensure!(buf_list_buf == oracle_buf, "peek buffer matches");
+            }
+            Self::PeekExact(index) => {
+                let buf_size = index.index(1 + num_bytes * 5 / 4);
+                eprintln!("buf_size: {}", buf_size);
+
+                let mut buf_list_buf = vec![0u8; buf_size];
+                let mut oracle_buf = vec![0u8; buf_size];
+
+                let buf_list_res = buf_list.peek_exact(&mut buf_list_buf);
+                let oracle_res = oracle.clone().read_exact(&mut oracle_buf);
+                Self::assert_io_result_eq(buf_list_res, oracle_res)
+                    .context("operation result didn't match")?;
+                if buf_list_buf.len() as u64 <= buf_list.remaining() {
+                    ensure!(buf_list_buf == oracle_buf, "peek buffer matches");
+                }
+            }
+            Self::ReadToEnd => {
+                // Start with some data in the buffer to check that it's preserved.
+                let mut buf_list_buf = b"prefix".to_vec();
+                let mut oracle_buf = buf_list_buf.clone();
+
+                let buf_list_res = buf_list.read_to_end(&mut buf_list_buf);
+                let oracle_res = oracle.read_to_end(&mut oracle_buf);
+                Self::assert_io_result_eq(buf_list_res, oracle_res)
+                    .context("operation result didn't match")?;
+                ensure!(buf_list_buf == oracle_buf, "read_to_end buffer matches");
+            }
+            Self::ReadToString => {
+                // Start with some data in the buffer to check that it's preserved.
+                let mut buf_list_buf = String::from("prefix");
+                let mut oracle_buf = buf_list_buf.clone();
+
+                let buf_list_res = buf_list.read_to_string(&mut buf_list_buf);
+                let oracle_res = oracle.read_to_string(&mut oracle_buf);
+                Self::assert_io_result_eq(buf_list_res, oracle_res)
+                    .context("operation result didn't match")?;
+                ensure!(buf_list_buf == oracle_buf, "read_to_string buffer matches");
+            }
+            Self::ReadUntil(byte) => {
+                let mut buf_list_buf = Vec::new();
+                let mut oracle_buf = Vec::new();
+
+                let buf_list_res = buf_list.read_until(byte, &mut buf_list_buf);
+                let oracle_res = oracle.read_until(byte, &mut oracle_buf);
+                Self::assert_io_result_eq(buf_list_res, oracle_res)
+                    .context("operation result didn't match")?;
+                ensure!(buf_list_buf == oracle_buf, "read_until buffer matches");
+            }
+            Self::ReadLine => {
+                // Start with some data in the buffer to check that it's preserved.
+                let mut buf_list_buf = String::from("prefix");
+                let mut oracle_buf = buf_list_buf.clone();
+
+                let buf_list_res = buf_list.read_line(&mut buf_list_buf);
+                let oracle_res = oracle.read_line(&mut oracle_buf);
+                Self::assert_io_result_eq(buf_list_res, oracle_res)
+                    .context("operation result didn't match")?;
+                ensure!(buf_list_buf == oracle_buf, "read_line buffer matches");
+            }
             Self::Consume(index) => {
                 let amt = index.index(1 + num_bytes * 5 / 4);
                 eprintln!("amt: {}", amt);
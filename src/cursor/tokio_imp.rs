@@ -27,7 +27,8 @@ impl<T: AsRef<BufList> + Unpin> AsyncRead for Cursor<T> {
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
         let this = &mut *self;
-        this.data.tokio_poll_read_impl(this.inner.as_ref(), buf)
+        this.data
+            .tokio_poll_read_impl(this.inner.as_ref(), buf, this.max_read_size)
     }
 }
 
@@ -46,9 +47,10 @@ impl CursorData {
         &mut self,
         list: &BufList,
         buf: &mut ReadBuf<'_>,
+        mut max_read_size: usize,
     ) -> Poll<io::Result<()>> {
         // This is really similar to Self::read_impl, except it's written against the ReadBuf API.
-        while buf.remaining() > 0 {
+        while buf.remaining() > 0 && max_read_size > 0 {
             let (chunk, chunk_pos) = match self.get_chunk_and_pos(list) {
                 Some(value) => value,
                 None => break,
@@ -56,7 +58,10 @@ impl CursorData {
             // The number of bytes to copy is the smaller of the two:
             // - the length of the chunk - the position in it.
             // - the number of bytes remaining.
-            let n_to_copy = (chunk.len() - chunk_pos).min(buf.remaining());
+            // - the number of bytes this read is still allowed to return.
+            let n_to_copy = (chunk.len() - chunk_pos)
+                .min(buf.remaining())
+                .min(max_read_size);
             let chunk_bytes = chunk.as_ref();
 
             let bytes_to_copy = &chunk_bytes[chunk_pos..(chunk_pos + n_to_copy)];
@@ -64,6 +69,7 @@ impl CursorData {
 
             // Increment the position.
             self.pos += n_to_copy as u64;
+            max_read_size -= n_to_copy;
             // If we've finished reading through the chunk, move to the next chunk.
             if n_to_copy == chunk.len() - chunk_pos {
                 self.chunk += 1;
@@ -0,0 +1,344 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed reads and peeks on `Cursor`, similar to the `get_*` methods on `Buf`, along with varint
+//! decoding.
+
+use crate::{BufList, Cursor};
+use std::{io, mem};
+
+macro_rules! typed_reads {
+    ($($(#[$attr:meta])* fn $name:ident -> $ty:ty = $from:ident;)*) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// # Errors
+            ///
+            /// If there aren't enough bytes remaining, this returns an error of kind
+            /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+            #[inline]
+            pub fn $name(&mut self) -> io::Result<$ty> {
+                let mut buf = [0; mem::size_of::<$ty>()];
+                self.data.read_exact_impl(self.inner.as_ref(), &mut buf)?;
+                Ok(<$ty>::$from(buf))
+            }
+        )*
+    };
+}
+
+macro_rules! typed_peeks {
+    ($($(#[$attr:meta])* fn $name:ident -> $ty:ty = $from:ident;)*) => {
+        $(
+            $(#[$attr])*
+            ///
+            /// # Errors
+            ///
+            /// If there aren't enough bytes remaining, this returns an error of kind
+            /// [`io::ErrorKind::UnexpectedEof`].
+            #[inline]
+            pub fn $name(&self) -> io::Result<$ty> {
+                let mut buf = [0; mem::size_of::<$ty>()];
+                self.peek_exact(&mut buf)?;
+                Ok(<$ty>::$from(buf))
+            }
+        )*
+    };
+}
+
+impl<T: AsRef<BufList>> Cursor<T> {
+    typed_reads! {
+        /// Reads an unsigned 8-bit integer, advancing the cursor by 1 byte.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use buf_list::{BufList, Cursor};
+        ///
+        /// let buf_list: BufList = vec![&b"\x01\x02"[..], &b"\x03"[..]].into_iter().collect();
+        /// let mut cursor = Cursor::new(buf_list);
+        ///
+        /// assert_eq!(cursor.read_u8().unwrap(), 1);
+        /// // Values can span chunk boundaries.
+        /// assert_eq!(cursor.read_u16_be().unwrap(), 0x0203);
+        /// assert!(cursor.read_u8().is_err());
+        /// ```
+        fn read_u8 -> u8 = from_be_bytes;
+        /// Reads a signed 8-bit integer, advancing the cursor by 1 byte.
+        fn read_i8 -> i8 = from_be_bytes;
+        /// Reads an unsigned 16-bit integer in big-endian byte order, advancing the cursor by 2
+        /// bytes.
+        fn read_u16_be -> u16 = from_be_bytes;
+        /// Reads an unsigned 16-bit integer in little-endian byte order, advancing the cursor by 2
+        /// bytes.
+        fn read_u16_le -> u16 = from_le_bytes;
+        /// Reads a signed 16-bit integer in big-endian byte order, advancing the cursor by 2
+        /// bytes.
+        fn read_i16_be -> i16 = from_be_bytes;
+        /// Reads a signed 16-bit integer in little-endian byte order, advancing the cursor by 2
+        /// bytes.
+        fn read_i16_le -> i16 = from_le_bytes;
+        /// Reads an unsigned 32-bit integer in big-endian byte order, advancing the cursor by 4
+        /// bytes.
+        fn read_u32_be -> u32 = from_be_bytes;
+        /// Reads an unsigned 32-bit integer in little-endian byte order, advancing the cursor by 4
+        /// bytes.
+        fn read_u32_le -> u32 = from_le_bytes;
+        /// Reads a signed 32-bit integer in big-endian byte order, advancing the cursor by 4
+        /// bytes.
+        fn read_i32_be -> i32 = from_be_bytes;
+        /// Reads a signed 32-bit integer in little-endian byte order, advancing the cursor by 4
+        /// bytes.
+        fn read_i32_le -> i32 = from_le_bytes;
+        /// Reads an unsigned 64-bit integer in big-endian byte order, advancing the cursor by 8
+        /// bytes.
+        fn read_u64_be -> u64 = from_be_bytes;
+        /// Reads an unsigned 64-bit integer in little-endian byte order, advancing the cursor by 8
+        /// bytes.
+        fn read_u64_le -> u64 = from_le_bytes;
+        /// Reads a signed 64-bit integer in big-endian byte order, advancing the cursor by 8
+        /// bytes.
+        fn read_i64_be -> i64 = from_be_bytes;
+        /// Reads a signed 64-bit integer in little-endian byte order, advancing the cursor by 8
+        /// bytes.
+        fn read_i64_le -> i64 = from_le_bytes;
+        /// Reads an unsigned 128-bit integer in big-endian byte order, advancing the cursor by 16
+        /// bytes.
+        fn read_u128_be -> u128 = from_be_bytes;
+        /// Reads an unsigned 128-bit integer in little-endian byte order, advancing the cursor by
+        /// 16 bytes.
+        fn read_u128_le -> u128 = from_le_bytes;
+        /// Reads a signed 128-bit integer in big-endian byte order, advancing the cursor by 16
+        /// bytes.
+        fn read_i128_be -> i128 = from_be_bytes;
+        /// Reads a signed 128-bit integer in little-endian byte order, advancing the cursor by 16
+        /// bytes.
+        fn read_i128_le -> i128 = from_le_bytes;
+    }
+
+    /// Reads an IEEE 754 single-precision floating point number in big-endian byte order,
+    /// advancing the cursor by 4 bytes.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+    #[inline]
+    pub fn read_f32_be(&mut self) -> io::Result<f32> {
+        self.read_u32_be().map(f32::from_bits)
+    }
+
+    /// Reads an IEEE 754 single-precision floating point number in little-endian byte order,
+    /// advancing the cursor by 4 bytes.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+    #[inline]
+    pub fn read_f32_le(&mut self) -> io::Result<f32> {
+        self.read_u32_le().map(f32::from_bits)
+    }
+
+    /// Reads an IEEE 754 double-precision floating point number in big-endian byte order,
+    /// advancing the cursor by 8 bytes.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+    #[inline]
+    pub fn read_f64_be(&mut self) -> io::Result<f64> {
+        self.read_u64_be().map(f64::from_bits)
+    }
+
+    /// Reads an IEEE 754 double-precision floating point number in little-endian byte order,
+    /// advancing the cursor by 8 bytes.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`], and the cursor is not advanced.
+    #[inline]
+    pub fn read_f64_le(&mut self) -> io::Result<f64> {
+        self.read_u64_le().map(f64::from_bits)
+    }
+
+    typed_peeks! {
+        /// Reads an unsigned 8-bit integer without advancing the cursor.
+        ///
+        /// This is useful for dispatching on a tag byte.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use buf_list::{BufList, Cursor};
+        ///
+        /// let buf_list: BufList = vec![&b"\x01\x02"[..], &b"\x03"[..]].into_iter().collect();
+        /// let mut cursor = Cursor::new(buf_list);
+        ///
+        /// match cursor.peek_u8().unwrap() {
+        ///     1 => {
+        ///         cursor.set_position(1);
+        ///         assert_eq!(cursor.peek_u16_be().unwrap(), 0x0203);
+        ///         assert_eq!(cursor.position(), 1);
+        ///     }
+        ///     _ => unreachable!(),
+        /// }
+        /// ```
+        fn peek_u8 -> u8 = from_be_bytes;
+        /// Reads a signed 8-bit integer without advancing the cursor.
+        fn peek_i8 -> i8 = from_be_bytes;
+        /// Reads an unsigned 16-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_u16_be -> u16 = from_be_bytes;
+        /// Reads an unsigned 16-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_u16_le -> u16 = from_le_bytes;
+        /// Reads a signed 16-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_i16_be -> i16 = from_be_bytes;
+        /// Reads a signed 16-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_i16_le -> i16 = from_le_bytes;
+        /// Reads an unsigned 32-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_u32_be -> u32 = from_be_bytes;
+        /// Reads an unsigned 32-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_u32_le -> u32 = from_le_bytes;
+        /// Reads a signed 32-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_i32_be -> i32 = from_be_bytes;
+        /// Reads a signed 32-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_i32_le -> i32 = from_le_bytes;
+        /// Reads an unsigned 64-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_u64_be -> u64 = from_be_bytes;
+        /// Reads an unsigned 64-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_u64_le -> u64 = from_le_bytes;
+        /// Reads a signed 64-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_i64_be -> i64 = from_be_bytes;
+        /// Reads a signed 64-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_i64_le -> i64 = from_le_bytes;
+        /// Reads an unsigned 128-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_u128_be -> u128 = from_be_bytes;
+        /// Reads an unsigned 128-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_u128_le -> u128 = from_le_bytes;
+        /// Reads a signed 128-bit integer in big-endian byte order without advancing the
+        /// cursor.
+        fn peek_i128_be -> i128 = from_be_bytes;
+        /// Reads a signed 128-bit integer in little-endian byte order without advancing the
+        /// cursor.
+        fn peek_i128_le -> i128 = from_le_bytes;
+    }
+
+    /// Reads an IEEE 754 single-precision floating point number in big-endian byte order,
+    /// without advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    #[inline]
+    pub fn peek_f32_be(&self) -> io::Result<f32> {
+        self.peek_u32_be().map(f32::from_bits)
+    }
+
+    /// Reads an IEEE 754 single-precision floating point number in little-endian byte order,
+    /// without advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    #[inline]
+    pub fn peek_f32_le(&self) -> io::Result<f32> {
+        self.peek_u32_le().map(f32::from_bits)
+    }
+
+    /// Reads an IEEE 754 double-precision floating point number in big-endian byte order,
+    /// without advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    #[inline]
+    pub fn peek_f64_be(&self) -> io::Result<f64> {
+        self.peek_u64_be().map(f64::from_bits)
+    }
+
+    /// Reads an IEEE 754 double-precision floating point number in little-endian byte order,
+    /// without advancing the cursor.
+    ///
+    /// # Errors
+    ///
+    /// If there aren't enough bytes remaining, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`].
+    #[inline]
+    pub fn peek_f64_le(&self) -> io::Result<f64> {
+        self.peek_u64_le().map(f64::from_bits)
+    }
+
+    /// Reads an unsigned LEB128 varint, as used by Protocol Buffers, advancing the cursor past it.
+    ///
+    /// Varints may span chunk boundaries. To decode a protobuf `int64` or `int32`, cast the result
+    /// with `as i64`; for `sint64` and `sint32`, use [`read_varint_i64`](Self::read_varint_i64).
+    ///
+    /// # Errors
+    ///
+    /// If the list ends partway through the varint, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`]. If the varint doesn't fit in a `u64`, this returns an
+    /// error of kind [`io::ErrorKind::InvalidData`]. In both cases, the cursor is not advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// // 300 is encoded as [0xac, 0x02].
+    /// let buf_list: BufList = vec![&b"\x96\x01\xac"[..], &b"\x02"[..]].into_iter().collect();
+    /// let mut cursor = Cursor::new(buf_list);
+    ///
+    /// assert_eq!(cursor.read_varint_u64().unwrap(), 150);
+    /// assert_eq!(cursor.read_varint_u64().unwrap(), 300);
+    /// assert!(cursor.read_varint_u64().is_err());
+    /// ```
+    pub fn read_varint_u64(&mut self) -> io::Result<u64> {
+        self.data.read_varint_impl(self.inner.as_ref())
+    }
+
+    /// Reads a zigzag-encoded signed LEB128 varint, as used by Protocol Buffers for `sint64` and
+    /// `sint32`, advancing the cursor past it.
+    ///
+    /// Zigzag encoding maps signed integers to unsigned ones so that values with a small absolute
+    /// value have a short encoding: 0 is encoded as 0, -1 as 1, 1 as 2, and so on.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`read_varint_u64`](Self::read_varint_u64).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, Cursor};
+    ///
+    /// let buf_list = BufList::from(&b"\x00\x01\x02\x03"[..]);
+    /// let mut cursor = Cursor::new(buf_list);
+    ///
+    /// assert_eq!(cursor.read_varint_i64().unwrap(), 0);
+    /// assert_eq!(cursor.read_varint_i64().unwrap(), -1);
+    /// assert_eq!(cursor.read_varint_i64().unwrap(), 1);
+    /// assert_eq!(cursor.read_varint_i64().unwrap(), -2);
+    /// ```
+    pub fn read_varint_i64(&mut self) -> io::Result<i64> {
+        let n = self.read_varint_u64()?;
+        Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+    }
+}
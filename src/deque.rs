@@ -0,0 +1,441 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage for the segments in a `SegmentedBuf`, with a small inline capacity.
+
+use std::{
+    collections::{vec_deque, VecDeque},
+    fmt,
+    iter::FusedIterator,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+/// The number of segments that can be stored without allocating.
+///
+/// Most lists hold just a handful of segments, so keeping them inline means that creating and
+/// cloning such lists doesn't allocate.
+const INLINE_CAPACITY: usize = 3;
+
+/// A double-ended queue of segments, which is stored inline until it grows past
+/// [`INLINE_CAPACITY`] segments.
+///
+/// Once the queue has spilled onto the heap, it stays there.
+#[derive(Clone)]
+pub(crate) enum ChunkDeque<T> {
+    /// Invariant: `slots[..len]` are all `Some`, and `slots[len..]` are all `None`.
+    Inline {
+        slots: [Option<T>; INLINE_CAPACITY],
+        len: usize,
+    },
+    Heap(VecDeque<T>),
+}
+
+impl<T> ChunkDeque<T> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        ChunkDeque::Inline {
+            slots: [None, None, None],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        if capacity <= INLINE_CAPACITY {
+            Self::new()
+        } else {
+            ChunkDeque::Heap(VecDeque::with_capacity(capacity))
+        }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            ChunkDeque::Inline { len, .. } => *len,
+            ChunkDeque::Heap(segments) => segments.len(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => slots[..*len].get(index).map(occupied),
+            ChunkDeque::Heap(segments) => segments.get(index),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => slots[..*len].get_mut(index).map(occupied_mut),
+            ChunkDeque::Heap(segments) => segments.get_mut(index),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    #[inline]
+    pub(crate) fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    #[inline]
+    pub(crate) fn back_mut(&mut self) -> Option<&mut T> {
+        match self.len() {
+            0 => None,
+            len => self.get_mut(len - 1),
+        }
+    }
+
+    pub(crate) fn push_back(&mut self, segment: T) {
+        match self {
+            ChunkDeque::Inline { slots, len } if *len < INLINE_CAPACITY => {
+                slots[*len] = Some(segment);
+                *len += 1;
+            }
+            ChunkDeque::Inline { .. } => {
+                let mut heap = self.spill(INLINE_CAPACITY * 2);
+                heap.push_back(segment);
+                *self = ChunkDeque::Heap(heap);
+            }
+            ChunkDeque::Heap(segments) => segments.push_back(segment),
+        }
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        match self {
+            ChunkDeque::Inline { len, .. } => {
+                let capacity = len.saturating_add(additional);
+                if capacity > INLINE_CAPACITY {
+                    *self = ChunkDeque::Heap(self.spill(capacity));
+                }
+            }
+            ChunkDeque::Heap(segments) => segments.reserve(additional),
+        }
+    }
+
+    /// Moves the inline segments into a new `VecDeque` with the given capacity, leaving the inline
+    /// storage empty.
+    fn spill(&mut self, capacity: usize) -> VecDeque<T> {
+        let mut heap = VecDeque::with_capacity(capacity);
+        if let ChunkDeque::Inline { slots, len } = self {
+            heap.extend(slots.iter_mut().filter_map(Option::take));
+            *len = 0;
+        }
+        heap
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => {
+                if *len == 0 {
+                    return None;
+                }
+                let front = slots[0].take();
+                // Move the now-empty slot to the end.
+                slots[..*len].rotate_left(1);
+                *len -= 1;
+                front
+            }
+            ChunkDeque::Heap(segments) => segments.pop_front(),
+        }
+    }
+
+    pub(crate) fn pop_back(&mut self) -> Option<T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => {
+                if *len == 0 {
+                    return None;
+                }
+                *len -= 1;
+                slots[*len].take()
+            }
+            ChunkDeque::Heap(segments) => segments.pop_back(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => Iter::Inline(slots[..*len].iter()),
+            ChunkDeque::Heap(segments) => Iter::Heap(segments.iter()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => IterMut::Inline(slots[..*len].iter_mut()),
+            ChunkDeque::Heap(segments) => IterMut::Heap(segments.iter_mut()),
+        }
+    }
+}
+
+#[inline]
+fn occupied<T>(slot: &Option<T>) -> &T {
+    slot.as_ref().expect("slots before len are occupied")
+}
+
+#[inline]
+fn occupied_mut<T>(slot: &mut Option<T>) -> &mut T {
+    slot.as_mut().expect("slots before len are occupied")
+}
+
+impl<T> Default for ChunkDeque<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ChunkDeque<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Index<usize> for ChunkDeque<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: usize) -> &T {
+        self.get(index).expect("out of bounds access")
+    }
+}
+
+impl<T> IndexMut<usize> for ChunkDeque<T> {
+    #[inline]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).expect("out of bounds access")
+    }
+}
+
+impl<T> IntoIterator for ChunkDeque<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> IntoIter<T> {
+        match self {
+            ChunkDeque::Inline { slots, len } => IntoIter::Inline {
+                slots,
+                start: 0,
+                end: len,
+            },
+            ChunkDeque::Heap(segments) => IntoIter::Heap(segments.into_iter()),
+        }
+    }
+}
+
+/// A borrowed iterator over the segments in a [`ChunkDeque`].
+#[derive(Clone, Debug)]
+pub(crate) enum Iter<'a, T> {
+    /// Invariant: all the slots in the iterator are occupied.
+    Inline(slice::Iter<'a, Option<T>>),
+    Heap(vec_deque::Iter<'a, T>),
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Inline(iter) => iter.next().map(occupied),
+            Iter::Heap(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self {
+            Iter::Inline(iter) => iter.fold(init, |acc, slot| f(acc, occupied(slot))),
+            Iter::Heap(iter) => iter.fold(init, f),
+        }
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            Iter::Inline(iter) => iter.nth(n).map(occupied),
+            Iter::Heap(iter) => iter.nth(n),
+        }
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        match self {
+            Iter::Inline(iter) => iter.last().map(occupied),
+            Iter::Heap(iter) => iter.last(),
+        }
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Inline(iter) => iter.next_back().map(occupied),
+            Iter::Heap(iter) => iter.next_back(),
+        }
+    }
+
+    #[inline]
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self {
+            Iter::Inline(iter) => iter.rfold(init, |acc, slot| f(acc, occupied(slot))),
+            Iter::Heap(iter) => iter.rfold(init, f),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            Iter::Inline(iter) => iter.len(),
+            Iter::Heap(iter) => iter.len(),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// A mutable iterator over the segments in a [`ChunkDeque`].
+#[derive(Debug)]
+pub(crate) enum IterMut<'a, T> {
+    /// Invariant: all the slots in the iterator are occupied.
+    Inline(slice::IterMut<'a, Option<T>>),
+    Heap(vec_deque::IterMut<'a, T>),
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            IterMut::Inline(iter) => iter.next().map(occupied_mut),
+            IterMut::Heap(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            IterMut::Inline(iter) => iter.next_back().map(occupied_mut),
+            IterMut::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            IterMut::Inline(iter) => iter.len(),
+            IterMut::Heap(iter) => iter.len(),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+/// An owned iterator over the segments in a [`ChunkDeque`].
+#[derive(Clone)]
+pub(crate) enum IntoIter<T> {
+    /// Invariant: `slots[start..end]` are all `Some`, and the other slots are all `None`.
+    Inline {
+        slots: [Option<T>; INLINE_CAPACITY],
+        start: usize,
+        end: usize,
+    },
+    Heap(vec_deque::IntoIter<T>),
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntoIter::Inline { slots, start, end } => {
+                let remaining: Vec<&T> = slots[*start..*end].iter().map(occupied).collect();
+                f.debug_tuple("IntoIter").field(&remaining).finish()
+            }
+            IntoIter::Heap(iter) => iter.fmt(f),
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            IntoIter::Inline { slots, start, end } => {
+                if start == end {
+                    return None;
+                }
+                *start += 1;
+                slots[*start - 1].take()
+            }
+            IntoIter::Heap(iter) => iter.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            IntoIter::Inline { slots, start, end } => {
+                if start == end {
+                    return None;
+                }
+                *end -= 1;
+                slots[*end].take()
+            }
+            IntoIter::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        match self {
+            IntoIter::Inline { start, end, .. } => *end - *start,
+            IntoIter::Heap(iter) => iter.len(),
+        }
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
@@ -0,0 +1,52 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use digest_010::{Digest, Output, Update};
+
+impl BufList {
+    /// Feeds the bytes in this list into a hasher, one chunk at a time.
+    ///
+    /// This works with any type that implements `digest`'s [`Update`] trait, such as
+    /// cryptographic hash functions and MACs, and never flattens the list.
+    ///
+    /// Requires the `digest010` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let mut hasher = Sha256::new();
+    /// buf_list.update_digest(&mut hasher);
+    /// assert_eq!(hasher.finalize(), Sha256::digest(b"hello world"));
+    /// ```
+    pub fn update_digest<D: Update>(&self, digest: &mut D) {
+        for chunk in self.iter() {
+            digest.update(chunk);
+        }
+    }
+
+    /// Computes the hash of the bytes in this list with the hash function `D`.
+    ///
+    /// The result is the same regardless of how the list is split into chunks.
+    ///
+    /// Requires the `digest010` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use sha2::{Digest, Sha256};
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// assert_eq!(buf_list.digest::<Sha256>(), Sha256::digest(b"hello world"));
+    /// ```
+    pub fn digest<D: Digest + Update>(&self) -> Output<D> {
+        let mut digest = D::new();
+        self.update_digest(&mut digest);
+        digest.finalize()
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adapters for formatting the contents of a `BufList`.
+
+use crate::{
+    utf8::{Utf8Piece, Utf8Pieces},
+    BufList,
+};
+use std::{ascii, fmt, fmt::Write};
+
+impl BufList {
+    /// Returns an adapter that displays the contents of this list as UTF-8, replacing invalid
+    /// sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// The contents are written to the formatter one piece at a time, without flattening the list
+    /// into a single string first. The output is the same as [`to_str_lossy`](Self::to_str_lossy).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"caf\xc3"[..], &b"\xa9 \xff"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(format!("{}", buf_list.display_lossy()), "café \u{fffd}");
+    /// ```
+    #[inline]
+    pub fn display_lossy(&self) -> DisplayLossy<'_> {
+        DisplayLossy { list: self }
+    }
+
+    /// Returns an adapter that displays the contents of this list with non-printable and
+    /// non-ASCII bytes escaped.
+    ///
+    /// Each byte is escaped with [`std::ascii::escape_default`], so the output is the same as
+    /// `escape_ascii` on the flattened bytes. This is useful for logging binary data safely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"GET /\r"[..], &b"\n\x00\xff"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(format!("{}", buf_list.escape_ascii()), "GET /\\r\\n\\x00\\xff");
+    /// ```
+    #[inline]
+    pub fn escape_ascii(&self) -> EscapeAscii<'_> {
+        EscapeAscii { list: self }
+    }
+
+    /// Returns an adapter whose `Debug` output summarizes this list, showing the number of chunks
+    /// and bytes along with a hex and ASCII preview of the first and last `n` bytes.
+    ///
+    /// If the list has `2 * n` bytes or fewer, all of its contents are shown.
+    ///
+    /// The `Debug` implementation for `BufList` itself shows the full contents of every chunk,
+    /// which can be unwieldy for large lists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello, "[..], &b"world!"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(
+    ///     format!("{:?}", buf_list.debug_preview(2)),
+    ///     "BufList { num_chunks: 2, num_bytes: 13, head: 68 65 |he|, \
+    ///      omitted_bytes: 9, tail: 64 21 |d!| }",
+    /// );
+    /// assert_eq!(
+    ///     format!("{:?}", buf_list.debug_preview(8)),
+    ///     "BufList { num_chunks: 2, num_bytes: 13, contents: \
+    ///      68 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 |hello, world!| }",
+    /// );
+    /// ```
+    #[inline]
+    pub fn debug_preview(&self, n: usize) -> DebugPreview<'_> {
+        DebugPreview { list: self, n }
+    }
+}
+
+/// Formats the contents of the list as lowercase hex, two digits per byte.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::BufList;
+///
+/// let buf_list = vec![&b"\x01\xab"[..], &b"\xcd"[..]].into_iter().collect::<BufList>();
+/// assert_eq!(format!("{:x}", buf_list), "01abcd");
+/// ```
+impl fmt::LowerHex for BufList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.iter() {
+            for byte in chunk.as_ref() {
+                write!(f, "{:02x}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats the contents of the list as uppercase hex, two digits per byte.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::BufList;
+///
+/// let buf_list = vec![&b"\x01\xab"[..], &b"\xcd"[..]].into_iter().collect::<BufList>();
+/// assert_eq!(format!("{:X}", buf_list), "01ABCD");
+/// ```
+impl fmt::UpperHex for BufList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.iter() {
+            for byte in chunk.as_ref() {
+                write!(f, "{:02X}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays the contents of a [`BufList`] as lossily-decoded UTF-8.
+///
+/// Returned by [`BufList::display_lossy`].
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayLossy<'a> {
+    list: &'a BufList,
+}
+
+impl<'a> fmt::Display for DisplayLossy<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for piece in Utf8Pieces::new(self.list.iter().map(|chunk| chunk.as_ref())) {
+            match piece {
+                Utf8Piece::Str(s) => f.write_str(s)?,
+                Utf8Piece::Char(c) => f.write_char(c)?,
+                Utf8Piece::Invalid { .. } => f.write_char('\u{fffd}')?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Displays the contents of a [`BufList`] with non-printable and non-ASCII bytes escaped.
+///
+/// Returned by [`BufList::escape_ascii`].
+#[derive(Clone, Copy, Debug)]
+pub struct EscapeAscii<'a> {
+    list: &'a BufList,
+}
+
+impl<'a> fmt::Display for EscapeAscii<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in self.list.iter() {
+            for &byte in chunk.as_ref() {
+                for escaped in ascii::escape_default(byte) {
+                    f.write_char(escaped as char)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Summarizes a [`BufList`] in its `Debug` output, with a preview of its first and last bytes.
+///
+/// Returned by [`BufList::debug_preview`].
+#[derive(Clone, Copy)]
+pub struct DebugPreview<'a> {
+    list: &'a BufList,
+    n: usize,
+}
+
+impl<'a> fmt::Debug for DebugPreview<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let num_bytes = self.list.num_bytes();
+        let mut debug = f.debug_struct("BufList");
+        debug
+            .field("num_chunks", &self.list.num_chunks())
+            .field("num_bytes", &num_bytes);
+
+        if num_bytes <= self.n.saturating_mul(2) {
+            let mut contents = Vec::with_capacity(num_bytes);
+            for chunk in self.list.iter() {
+                contents.extend_from_slice(chunk);
+            }
+            debug.field("contents", &HexAscii(&contents));
+        } else {
+            let mut head = Vec::with_capacity(self.n);
+            for chunk in self.list.iter() {
+                let len = chunk.len().min(self.n - head.len());
+                head.extend_from_slice(&chunk[..len]);
+                if head.len() == self.n {
+                    break;
+                }
+            }
+
+            let mut tail = Vec::with_capacity(self.n);
+            for chunk in self.list.iter().rev() {
+                let len = chunk.len().min(self.n - tail.len());
+                tail.splice(..0, chunk[chunk.len() - len..].iter().copied());
+                if tail.len() == self.n {
+                    break;
+                }
+            }
+
+            debug
+                .field("head", &HexAscii(&head))
+                .field("omitted_bytes", &(num_bytes - 2 * self.n))
+                .field("tail", &HexAscii(&tail));
+        }
+
+        debug.finish()
+    }
+}
+
+/// Formats bytes as space-separated hex followed by their printable ASCII representation, similar
+/// to `hexdump -C`.
+struct HexAscii<'a>(&'a [u8]);
+
+impl<'a> fmt::Debug for HexAscii<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x} ", byte)?;
+        }
+        f.write_char('|')?;
+        for &byte in self.0 {
+            let c = if byte == b' ' || byte.is_ascii_graphic() {
+                byte as char
+            } else {
+                '.'
+            };
+            f.write_char(c)?;
+        }
+        f.write_char('|')
+    }
+}
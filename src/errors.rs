@@ -31,3 +31,202 @@ impl fmt::Display for ReadExactError {
         )
     }
 }
+
+/// An error returned if a [`Cursor`](crate::Cursor) couldn't decode a varint.
+///
+/// This is private because the varint methods return an `io::Error`.
+#[derive(Debug)]
+pub(crate) enum VarintError {
+    /// The list ended partway through the varint.
+    Truncated { remaining: u64 },
+    /// The varint was too long to fit into a `u64`.
+    Overflow,
+}
+
+impl error::Error for VarintError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for VarintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarintError::Truncated { remaining } => write!(
+                f,
+                "Cursor had {} bytes remaining but varint wasn't terminated",
+                remaining
+            ),
+            VarintError::Overflow => write!(f, "varint overflows a 64-bit integer"),
+        }
+    }
+}
+
+/// An error returned by [`Frames`](crate::Frames) if a `BufList` ends partway through a frame.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TruncatedFrameError {
+    pub(crate) position: u64,
+    pub(crate) needed: u64,
+    pub(crate) available: u64,
+}
+
+impl TruncatedFrameError {
+    /// Returns the position at which the truncated frame starts, including its length prefix.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the number of bytes needed to complete the frame, including its length prefix.
+    ///
+    /// If the length prefix itself was truncated, this is the size of the length prefix.
+    #[inline]
+    pub fn needed(&self) -> u64 {
+        self.needed
+    }
+
+    /// Returns the number of bytes that were available starting at [`position`](Self::position).
+    #[inline]
+    pub fn available(&self) -> u64 {
+        self.available
+    }
+}
+
+impl error::Error for TruncatedFrameError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for TruncatedFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "frame at position {} needed {} bytes but only {} bytes were available",
+            self.position, self.needed, self.available
+        )
+    }
+}
+
+/// An error returned when the bytes in a `BufList` aren't valid UTF-8.
+///
+/// This is similar to [`std::str::Utf8Error`], except that positions are `u64`s.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Utf8Error {
+    pub(crate) valid_up_to: u64,
+    pub(crate) error_len: Option<usize>,
+}
+
+impl Utf8Error {
+    /// Returns the position up to which valid UTF-8 was verified.
+    #[inline]
+    pub fn valid_up_to(&self) -> u64 {
+        self.valid_up_to
+    }
+
+    /// Returns the length of the invalid byte sequence, or `None` if the input ended partway
+    /// through a code point.
+    ///
+    /// This has the same meaning as [`std::str::Utf8Error::error_len`].
+    #[inline]
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len
+    }
+}
+
+impl error::Error for Utf8Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for Utf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.error_len {
+            Some(error_len) => write!(
+                f,
+                "invalid utf-8 sequence of {} bytes from index {}",
+                error_len, self.valid_up_to
+            ),
+            None => write!(
+                f,
+                "incomplete utf-8 byte sequence from index {}",
+                self.valid_up_to
+            ),
+        }
+    }
+}
+
+/// An error returned if adding data to a `BufList` would exceed a limit on its size.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LimitExceededError {
+    pub(crate) limit: u64,
+    pub(crate) attempted: u64,
+}
+
+impl LimitExceededError {
+    /// Returns the maximum number of bytes that was allowed.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Returns the number of bytes the list would have had if the data had been added.
+    #[inline]
+    pub fn attempted(&self) -> u64 {
+        self.attempted
+    }
+}
+
+impl error::Error for LimitExceededError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for LimitExceededError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "adding data would grow BufList to {} bytes, exceeding the limit of {} bytes",
+            self.attempted, self.limit
+        )
+    }
+}
+
+/// An error returned by [`BufList::try_collect_limited`](crate::BufList::try_collect_limited).
+///
+/// Requires the `futures03` feature.
+#[cfg(feature = "futures03")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TryCollectLimitedError<E> {
+    /// The stream returned an error.
+    Stream(E),
+
+    /// The stream produced more bytes than the limit allows.
+    ///
+    /// Collection stops as soon as the limit is exceeded, so the rest of the stream isn't read.
+    LimitExceeded(LimitExceededError),
+}
+
+#[cfg(feature = "futures03")]
+impl<E: error::Error + 'static> error::Error for TryCollectLimitedError<E> {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            TryCollectLimitedError::Stream(err) => Some(err),
+            TryCollectLimitedError::LimitExceeded(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "futures03")]
+impl<E> fmt::Display for TryCollectLimitedError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryCollectLimitedError::Stream(_) => write!(f, "error reading from stream"),
+            TryCollectLimitedError::LimitExceeded(_) => {
+                write!(f, "stream exceeded the limit on its size")
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compression::{decode_to_end, encode_chunks},
+    BufList, Writer,
+};
+use flate2_1::{bufread::GzDecoder, write::GzEncoder, Compression};
+use std::io;
+
+impl BufList {
+    /// Compresses the bytes in this list with gzip, using the default compression level.
+    ///
+    /// The list is compressed [chunk by chunk](crate#encoding-and-compression).
+    ///
+    /// Requires the `flate21` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let compressed = buf_list.compress_gzip();
+    ///
+    /// let decompressed = compressed.decompress_gzip().unwrap();
+    /// assert_eq!(decompressed.to_str_lossy(), "hello world");
+    /// ```
+    pub fn compress_gzip(&self) -> BufList {
+        self.compress_gzip_with(Compression::default())
+    }
+
+    /// Compresses the bytes in this list with gzip, using the provided compression level.
+    ///
+    /// This behaves like [`compress_gzip`](Self::compress_gzip), except that the level is
+    /// configurable.
+    ///
+    /// Requires the `flate21` feature.
+    pub fn compress_gzip_with(&self, level: Compression) -> BufList {
+        let encoder = GzEncoder::new(Writer::new(), level);
+        encode_chunks(self, encoder)
+            .and_then(GzEncoder::finish)
+            .expect("writing to a Writer without a limit is infallible")
+            .into_inner()
+    }
+
+    /// Decompresses the bytes in this list as a single gzip member.
+    ///
+    /// The list is decompressed [chunk by chunk](crate#encoding-and-compression).
+    ///
+    /// Requires the `flate21` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list isn't valid gzip data, or if it ends before the gzip member
+    /// does.
+    pub fn decompress_gzip(&self) -> io::Result<BufList> {
+        decode_to_end(self, |reader| Ok(GzDecoder::new(reader)))
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A wire format that preserves chunk boundaries.
+
+use crate::{errors::VarintError, BufList};
+use bytes::Bytes;
+use std::io::{self, Read, Write};
+
+impl BufList {
+    /// Writes this list to `writer` in a format that records its chunk boundaries.
+    ///
+    /// The format consists of the number of chunks, followed by the length of each chunk, followed
+    /// by the contents of the chunks. The number of chunks and the lengths are encoded as unsigned
+    /// LEB128 varints. A list written this way can be read back with
+    /// [`read_framed`](Self::read_framed), producing a list with the same chunks.
+    ///
+    /// This is useful to persist a list, for example to spill buffered data to disk and later
+    /// replay it with the same segmentation. Since this performs many small writes, `writer`
+    /// should usually be buffered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let mut out = Vec::new();
+    /// buf_list.write_framed(&mut out).unwrap();
+    /// assert_eq!(out, b"\x02\x06\x05hello world");
+    ///
+    /// let read_back = BufList::read_framed(&out[..]).unwrap();
+    /// assert_eq!(read_back.num_chunks(), 2);
+    /// assert_eq!(read_back.get_chunk(1).unwrap(), &b"world"[..]);
+    /// ```
+    pub fn write_framed<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write_varint(&mut writer, self.num_chunks() as u64)?;
+        for chunk in self.iter() {
+            write_varint(&mut writer, chunk.len() as u64)?;
+        }
+        for chunk in self.iter() {
+            writer.write_all(chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a list written by [`write_framed`](Self::write_framed) from `reader`, with the same
+    /// chunks as the list that was written.
+    ///
+    /// Exactly as many bytes as were written are read, so framed lists can be followed by other
+    /// data. Since this performs many small reads, `reader` should usually be buffered.
+    ///
+    /// # Errors
+    ///
+    /// If `reader` ends before the whole list has been read, this returns an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`]. If a varint doesn't fit in a `u64`, this returns an error
+    /// of kind [`io::ErrorKind::InvalidData`]. Errors returned by `reader` are passed through.
+    ///
+    /// Memory is allocated as data is read rather than based on the lengths recorded in the input,
+    /// so corrupted input doesn't cause huge allocations up front.
+    pub fn read_framed<R: Read>(mut reader: R) -> io::Result<BufList> {
+        let num_chunks = read_varint(&mut reader)?;
+        // Cap the preallocation, since the number of chunks can't be trusted.
+        let mut lens = Vec::with_capacity(num_chunks.min(1024) as usize);
+        for _ in 0..num_chunks {
+            lens.push(read_varint(&mut reader)?);
+        }
+
+        let mut buf_list = BufList::with_capacity(lens.len());
+        for len in lens {
+            let mut chunk = Vec::new();
+            reader.by_ref().take(len).read_to_end(&mut chunk)?;
+            if (chunk.len() as u64) < len {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!(
+                        "reader ended after {} bytes of a {}-byte chunk",
+                        chunk.len(),
+                        len
+                    ),
+                ));
+            }
+            buf_list.push_chunk(Bytes::from(chunk));
+        }
+        Ok(buf_list)
+    }
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut n: u64) -> io::Result<()> {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut n = 0_u64;
+    for i in 0..10 {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        let byte = byte[0];
+        // The 10th byte can only contribute the top bit of a u64.
+        if i == 9 && byte > 1 {
+            break;
+        }
+        n |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        VarintError::Overflow,
+    ))
+}
@@ -0,0 +1,601 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    errors::TryCollectLimitedError, writer::DEFAULT_CHUNK_SIZE, BroadcastReader, BufList, IntoIter,
+    IntoReader, PipeReader, PipeWriter, SegmentedBuf, Writer,
+};
+use bytes::{Buf, Bytes};
+use futures_core_03::{stream::FusedStream, Stream};
+use futures_io_03::{AsyncBufRead, AsyncRead, AsyncWrite};
+use std::{
+    fmt,
+    future::Future,
+    io::{self, IoSlice},
+    marker::PhantomData,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl BufList {
+    /// Collects a fallible stream of chunks into a new list, failing if the list would be longer
+    /// than `max_bytes`.
+    ///
+    /// This is a safer version of `TryStreamExt::try_collect` for streams that come from untrusted
+    /// sources, such as HTTP request bodies. As soon as a chunk would make the list exceed the
+    /// limit, the returned future resolves to
+    /// [`TryCollectLimitedError::LimitExceeded`], without reading the rest of the stream.
+    ///
+    /// The stream must be [`Unpin`]. Streams that aren't can be pinned with `Box::pin` or
+    /// `futures::pin_mut!`.
+    ///
+    /// Requires the `futures03` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, TryCollectLimitedError};
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let chunks = vec![
+    ///     Ok::<_, ()>(Bytes::from_static(&b"laputa, "[..])),
+    ///     Ok(Bytes::from_static(&b"castle "[..])),
+    ///     Ok(Bytes::from_static(&b"in the sky"[..])),
+    /// ];
+    ///
+    /// let buf_list = BufList::try_collect_limited(futures::stream::iter(chunks.clone()), 32)
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(buf_list.num_bytes(), 25);
+    ///
+    /// let err = BufList::try_collect_limited(futures::stream::iter(chunks), 16)
+    ///     .await
+    ///     .unwrap_err();
+    /// match err {
+    ///     TryCollectLimitedError::LimitExceeded(err) => assert_eq!(err.attempted(), 25),
+    ///     TryCollectLimitedError::Stream(()) => unreachable!("the stream doesn't fail"),
+    /// }
+    /// # }
+    /// ```
+    pub fn try_collect_limited<S, B, E>(stream: S, max_bytes: u64) -> TryCollectLimited<S>
+    where
+        S: Stream<Item = Result<B, E>> + Unpin,
+        B: Buf,
+    {
+        TryCollectLimited {
+            stream,
+            buf_list: BufList::new(),
+            max_bytes,
+        }
+    }
+
+    /// Collects a fallible stream of chunks into a new list, merging chunks smaller than
+    /// `min_chunk_size` together.
+    ///
+    /// Some transports deliver data in lots of tiny frames. Collecting them with
+    /// `TryStreamExt::try_collect` produces a list with one chunk per frame, which makes the list
+    /// slow to read from and wastes memory on per-chunk overhead. This instead copies chunks
+    /// smaller than `min_chunk_size` into blocks of at least `min_chunk_size` bytes (8 KiB, or
+    /// `min_chunk_size` if that's larger), the same way [`Writer::push_chunk`] does. Chunks that
+    /// are large enough are added to the list without copying them.
+    ///
+    /// The stream must be [`Unpin`]. Streams that aren't can be pinned with `Box::pin` or
+    /// `futures::pin_mut!`.
+    ///
+    /// Requires the `futures03` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Bytes;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let frames = (0..100).map(|_| Ok::<_, ()>(Bytes::from_static(&b"ab"[..])));
+    ///
+    /// let buf_list = BufList::try_collect_coalesced(futures::stream::iter(frames), 64)
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(buf_list.num_bytes(), 200);
+    /// assert_eq!(buf_list.num_chunks(), 1);
+    /// # }
+    /// ```
+    pub fn try_collect_coalesced<S, B, E>(
+        stream: S,
+        min_chunk_size: usize,
+    ) -> TryCollectCoalesced<S>
+    where
+        S: Stream<Item = Result<B, E>> + Unpin,
+        B: Buf,
+    {
+        TryCollectCoalesced {
+            stream,
+            writer: Some(Writer::with_chunk_sizes(
+                min_chunk_size,
+                DEFAULT_CHUNK_SIZE.max(min_chunk_size),
+            )),
+        }
+    }
+}
+
+/// A future which collects a fallible stream of chunks into a [`BufList`], up to a limit.
+///
+/// Returned by [`BufList::try_collect_limited`].
+///
+/// Requires the `futures03` feature.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryCollectLimited<S> {
+    stream: S,
+    buf_list: BufList,
+    max_bytes: u64,
+}
+
+impl<S, B, E> Future for TryCollectLimited<S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+{
+    type Output = Result<BufList, TryCollectLimitedError<E>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    if let Err(err) = this.buf_list.try_push_chunk(chunk, this.max_bytes) {
+                        return Poll::Ready(Err(TryCollectLimitedError::LimitExceeded(err)));
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    return Poll::Ready(Err(TryCollectLimitedError::Stream(err)));
+                }
+                Poll::Ready(None) => {
+                    return Poll::Ready(Ok(mem::replace(&mut this.buf_list, BufList::new())));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A future which collects a fallible stream of chunks into a [`BufList`], merging small chunks
+/// together.
+///
+/// Returned by [`BufList::try_collect_coalesced`].
+///
+/// Requires the `futures03` feature.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TryCollectCoalesced<S> {
+    stream: S,
+    // This is None once the future has completed.
+    writer: Option<Writer>,
+}
+
+impl<S, B, E> Future for TryCollectCoalesced<S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf,
+{
+    type Output = Result<BufList, E>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let writer = this
+            .writer
+            .as_mut()
+            .expect("TryCollectCoalesced polled after completion");
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => writer.push_chunk(chunk),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err)),
+                Poll::Ready(None) => {
+                    let writer = this.writer.take().expect("checked above");
+                    return Poll::Ready(Ok(writer.into_inner()));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T> SegmentedBuf<T> {
+    /// Converts this list into a [`Stream`] of its chunks.
+    ///
+    /// This is the same as `futures::stream::iter(self)`, except that the returned [`IntoStream`]
+    /// can be named, so it can be stored in structs and returned from functions.
+    ///
+    /// Requires the `futures03` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, IntoStream};
+    /// use futures::StreamExt;
+    ///
+    /// struct Response {
+    ///     body: IntoStream,
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let mut response = Response { body: buf_list.into_stream() };
+    ///
+    /// assert_eq!(response.body.next().await.unwrap(), &b"hello "[..]);
+    /// assert_eq!(response.body.next().await.unwrap(), &b"world"[..]);
+    /// assert_eq!(response.body.next().await, None);
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> IntoStream<T> {
+        IntoStream {
+            iter: self.into_iter(),
+        }
+    }
+
+    /// Converts this list into a fallible stream of its chunks, which never fails.
+    ///
+    /// The returned [`IntoTryStream`] implements `TryStream<Ok = T, Error = E>`. This is useful to
+    /// feed buffered data back into APIs, such as HTTP request bodies, that expect a fallible
+    /// stream. `E` is usually inferred from the context the stream is passed into.
+    ///
+    /// Requires the `futures03` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use futures::TryStreamExt;
+    /// use std::io;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let stream = buf_list.into_try_stream::<io::Error>();
+    ///
+    /// let chunks: Vec<_> = stream.try_collect().await.unwrap();
+    /// assert_eq!(chunks, vec![&b"hello "[..], &b"world"[..]]);
+    /// # }
+    /// ```
+    pub fn into_try_stream<E>(self) -> IntoTryStream<T, E> {
+        IntoTryStream {
+            iter: self.into_iter(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// An owned [`Stream`] over chunks in a [`SegmentedBuf`], such as a [`BufList`].
+///
+/// Returned by [`SegmentedBuf::into_stream`]. The stream is always ready, and yields the same
+/// chunks as [`IntoIter`].
+///
+/// Requires the `futures03` feature.
+#[derive(Clone, Debug)]
+pub struct IntoStream<T = Bytes> {
+    iter: IntoIter<T>,
+}
+
+impl<T> IntoStream<T> {
+    /// Consumes this stream, returning an iterator over the remaining chunks.
+    #[inline]
+    pub fn into_inner(self) -> IntoIter<T> {
+        self.iter
+    }
+}
+
+// The chunks are never pinned.
+impl<T> Unpin for IntoStream<T> {}
+
+impl<T> Stream for IntoStream<T> {
+    type Item = T;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.iter.next())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> FusedStream for IntoStream<T> {
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        self.iter.len() == 0
+    }
+}
+
+/// An owned fallible stream over chunks in a [`SegmentedBuf`], such as a [`BufList`].
+///
+/// Returned by [`SegmentedBuf::into_try_stream`]. The stream is always ready, and never yields an
+/// error.
+///
+/// Requires the `futures03` feature.
+pub struct IntoTryStream<T = Bytes, E = io::Error> {
+    iter: IntoIter<T>,
+    // fn() -> E so that the stream is Send and Sync regardless of E.
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<T, E> IntoTryStream<T, E> {
+    /// Consumes this stream, returning an iterator over the remaining chunks.
+    #[inline]
+    pub fn into_inner(self) -> IntoIter<T> {
+        self.iter
+    }
+}
+
+// Implemented manually to avoid requiring E: Clone or E: Debug.
+impl<T: Clone, E> Clone for IntoTryStream<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: fmt::Debug, E> fmt::Debug for IntoTryStream<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoTryStream")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
+
+impl<T, E> Unpin for IntoTryStream<T, E> {}
+
+impl<T, E> Stream for IntoTryStream<T, E> {
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.iter.next().map(Ok))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, E> FusedStream for IntoTryStream<T, E> {
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        self.iter.len() == 0
+    }
+}
+
+impl BufList {
+    /// Converts this list into a [`Stream`] of chunks which are at most `max_frame` bytes long.
+    ///
+    /// Chunks longer than `max_frame` are split into several items, without copying them. Shorter
+    /// chunks are returned as they are. This is useful for protocols with a maximum message size,
+    /// such as WebSocket or gRPC.
+    ///
+    /// Requires the `futures03` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_frame` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use futures::StreamExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let buf_list: BufList = vec![&b"hello world"[..], &b"!"[..]].into_iter().collect();
+    /// let frames: Vec<_> = buf_list.into_stream_chunked(4).collect().await;
+    /// assert_eq!(frames, vec![&b"hell"[..], &b"o wo"[..], &b"rld"[..], &b"!"[..]]);
+    /// # }
+    /// ```
+    pub fn into_stream_chunked(self, max_frame: usize) -> IntoStreamChunked {
+        assert!(max_frame > 0, "max_frame must be greater than 0");
+        IntoStreamChunked {
+            iter: self.into_iter(),
+            current: Bytes::new(),
+            max_frame,
+        }
+    }
+}
+
+/// An owned [`Stream`] over chunks in a [`BufList`], split so that they're at most a maximum
+/// length.
+///
+/// Returned by [`BufList::into_stream_chunked`].
+///
+/// Requires the `futures03` feature.
+#[derive(Clone, Debug)]
+pub struct IntoStreamChunked {
+    iter: IntoIter<Bytes>,
+    // The rest of the chunk currently being split up.
+    current: Bytes,
+    max_frame: usize,
+}
+
+impl IntoStreamChunked {
+    /// Returns the maximum length of the items returned by this stream.
+    #[inline]
+    pub fn max_frame(&self) -> usize {
+        self.max_frame
+    }
+}
+
+impl Stream for IntoStreamChunked {
+    type Item = Bytes;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.current.is_empty() {
+            match this.iter.next() {
+                Some(chunk) => this.current = chunk,
+                None => return Poll::Ready(None),
+            }
+        }
+        let n = this.current.len().min(this.max_frame);
+        Poll::Ready(Some(this.current.split_to(n)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each remaining chunk produces at least one item.
+        let current = (self.current.len() + self.max_frame - 1) / self.max_frame;
+        (current + self.iter.len(), None)
+    }
+}
+
+impl FusedStream for IntoStreamChunked {
+    #[inline]
+    fn is_terminated(&self) -> bool {
+        self.current.is_empty() && self.iter.len() == 0
+    }
+}
+
+/// Reading from a `BufList` consumes the chunks that are read, the same way [`Buf::advance`] does.
+///
+/// Unlike a [`Cursor`](crate::Cursor), this doesn't build an index of chunk positions, and chunks
+/// are freed as soon as they've been read. Use a `Cursor` to read from a list without consuming it.
+impl AsyncRead for BufList {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Ok(self.read_consuming(buf)))
+    }
+}
+
+impl AsyncRead for IntoReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(io::Read::read(&mut *self, buf))
+    }
+}
+
+impl AsyncBufRead for IntoReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        Poll::Ready(io::BufRead::fill_buf(self.get_mut()))
+    }
+
+    fn consume(mut self: Pin<&mut Self>, amt: usize) {
+        io::BufRead::consume(&mut *self, amt)
+    }
+}
+
+/// Appends written data to the end of the list.
+///
+/// Like the [`io::Write`] impl, writes go through [`BufList::extend_from_slice`], so small writes
+/// are coalesced into a tail buffer rather than each becoming a chunk of its own.
+impl AsyncWrite for BufList {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(io::Write::write(&mut *self, buf))
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(io::Write::write_vectored(&mut *self, bufs))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for PipeWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_write_impl(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().close_impl();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for PipeReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_read_impl(cx, buf)
+    }
+}
+
+impl AsyncBufRead for PipeReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.get_mut().poll_fill_buf_impl(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().consume_impl(amt)
+    }
+}
+
+impl Stream for PipeReader {
+    type Item = Bytes;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_impl(cx)
+    }
+}
+
+impl AsyncRead for BroadcastReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_read_impl(cx, buf)
+    }
+}
+
+impl AsyncBufRead for BroadcastReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.get_mut().poll_fill_buf_impl(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().consume_impl(amt)
+    }
+}
+
+impl Stream for BroadcastReader {
+    type Item = Bytes;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_chunk(cx)
+    }
+}
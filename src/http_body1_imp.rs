@@ -0,0 +1,216 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::{Buf, Bytes};
+use http_1::HeaderMap;
+use http_body_1::{Body, Frame, SizeHint};
+use std::{
+    convert::Infallible,
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+impl BufList {
+    /// Collects an `http-body` 1.0 [`Body`] into a new list, preserving chunk boundaries.
+    ///
+    /// Each data frame becomes a chunk in the list. If the body's data is [`Bytes`], chunks aren't
+    /// copied. Trailers are ignored.
+    ///
+    /// This replaces `hyper::body::aggregate`, which was removed in hyper 1.0. Unlike
+    /// `BodyExt::collect` from `http-body-util`, the returned list can be passed around and
+    /// read from without copying the body's data into a single buffer.
+    ///
+    /// The body must be [`Unpin`]. Bodies that aren't can be pinned with `Box::pin`.
+    ///
+    /// Requires the `http-body1` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, BufListBody};
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let body: BufListBody = vec![&b"hello "[..], &b"world"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>()
+    ///     .into();
+    ///
+    /// let buf_list = BufList::from_body(body).await.unwrap();
+    /// assert_eq!(buf_list.num_chunks(), 2);
+    /// assert_eq!(buf_list.num_bytes(), 11);
+    /// # }
+    /// ```
+    pub fn from_body<B>(body: B) -> FromBody<B>
+    where
+        B: Body + Unpin,
+    {
+        FromBody {
+            body,
+            buf_list: BufList::new(),
+        }
+    }
+}
+
+/// Each chunk in the list is returned as a separate data frame, without copying it.
+///
+/// To send trailers after the data, use [`BufListBody`].
+impl Body for BufList {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        Poll::Ready(self.pop_front_chunk().map(|chunk| Ok(Frame::data(chunk))))
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        !self.has_remaining()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.total_len())
+    }
+}
+
+/// An `http-body` 1.0 [`Body`] made up of a [`BufList`], optionally followed by trailers.
+///
+/// Each chunk in the list is returned as a separate data frame without copying it, followed by a
+/// trailers frame if trailers were set.
+///
+/// Requires the `http-body1` feature.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::{BufList, BufListBody};
+/// use http_1::{HeaderMap, HeaderValue};
+///
+/// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+///
+/// let mut trailers = HeaderMap::new();
+/// trailers.insert("grpc-status", HeaderValue::from_static("0"));
+/// let body = BufListBody::new(buf_list).with_trailers(trailers);
+///
+/// assert_eq!(body.get_ref().num_bytes(), 11);
+/// assert!(body.trailers().is_some());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BufListBody {
+    data: BufList,
+    trailers: Option<HeaderMap>,
+}
+
+impl BufListBody {
+    /// Creates a new body out of `data`, without any trailers.
+    #[inline]
+    pub fn new(data: BufList) -> Self {
+        Self {
+            data,
+            trailers: None,
+        }
+    }
+
+    /// Sets the trailers to send once all the data has been sent.
+    #[inline]
+    pub fn with_trailers(mut self, trailers: HeaderMap) -> Self {
+        self.trailers = Some(trailers);
+        self
+    }
+
+    /// Returns the trailers that haven't been sent yet, if any.
+    #[inline]
+    pub fn trailers(&self) -> Option<&HeaderMap> {
+        self.trailers.as_ref()
+    }
+
+    /// Gets a reference to the data that hasn't been sent yet.
+    #[inline]
+    pub fn get_ref(&self) -> &BufList {
+        &self.data
+    }
+
+    /// Consumes this body, returning the data that hasn't been sent yet.
+    #[inline]
+    pub fn into_inner(self) -> BufList {
+        self.data
+    }
+}
+
+impl From<BufList> for BufListBody {
+    #[inline]
+    fn from(data: BufList) -> Self {
+        Self::new(data)
+    }
+}
+
+impl Body for BufListBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = &mut *self;
+        let frame = match this.data.pop_front_chunk() {
+            Some(chunk) => Some(Frame::data(chunk)),
+            None => this.trailers.take().map(Frame::trailers),
+        };
+        Poll::Ready(frame.map(Ok))
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        !self.data.has_remaining() && self.trailers.is_none()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.data.total_len())
+    }
+}
+
+/// A future which collects an `http-body` 1.0 [`Body`] into a [`BufList`].
+///
+/// Returned by [`BufList::from_body`].
+///
+/// Requires the `http-body1` feature.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct FromBody<B> {
+    body: B,
+    buf_list: BufList,
+}
+
+impl<B> Future for FromBody<B>
+where
+    B: Body + Unpin,
+{
+    type Output = Result<BufList, B::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            match Pin::new(&mut this.body).poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => {
+                    if let Ok(data) = frame.into_data() {
+                        this.buf_list.push_chunk(data);
+                    }
+                }
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Err(err)),
+                Poll::Ready(None) => {
+                    return Poll::Ready(Ok(mem::replace(&mut this.buf_list, BufList::new())));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::Buf;
+use std::io;
+
+/// Converts a list into a `hyper` 0.14 body.
+///
+/// A list with a single chunk is converted without losing its length. A list with several chunks
+/// is turned into a streaming body over its chunks, without copying them. Since `hyper` doesn't
+/// know the length of a streaming body, it's sent without a `content-length` header: to keep it,
+/// use the list as the body type directly, since it implements
+/// [`http_body_04::Body`].
+impl From<BufList> for hyper_014::Body {
+    fn from(buf_list: BufList) -> Self {
+        if buf_list.num_chunks() <= 1 {
+            let mut buf_list = buf_list;
+            let len = buf_list.remaining();
+            hyper_014::Body::from(buf_list.copy_to_bytes(len))
+        } else {
+            hyper_014::Body::wrap_stream(buf_list.into_try_stream::<io::Error>())
+        }
+    }
+}
@@ -2,55 +2,114 @@
 // Copyright (c) The buf-list Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{
+    deque::{self, ChunkDeque},
+    errors::LimitExceededError,
+    writer::DEFAULT_CHUNK_SIZE,
+};
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 use once_cell::sync::OnceCell;
 use std::{
-    collections::VecDeque,
+    fmt,
     io::IoSlice,
     iter::{FromIterator, FusedIterator},
+    mem,
 };
 
 /// Data composed of a list of [`Bytes`] chunks.
 ///
+/// This is a [`SegmentedBuf`] of `Bytes`. Since `Bytes` can be cloned and sliced cheaply, most of
+/// the functionality in this crate, including [`Cursor`](crate::Cursor), is only available for
+/// `BufList`.
+///
 /// For more, see the [crate documentation](crate).
-#[derive(Clone, Debug, Default)]
-pub struct BufList {
+pub type BufList = SegmentedBuf<Bytes>;
+
+/// Data composed of a list of segments, each of which implements [`Buf`].
+///
+/// A `SegmentedBuf` can hold segments of any `Buf` type, such as `BytesMut` or `&[u8]`. The most
+/// common kind of segmented buffer is [`BufList`], which holds [`Bytes`] chunks and has many more
+/// operations available.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::SegmentedBuf;
+/// use bytes::{Buf, BytesMut};
+///
+/// let mut buf = SegmentedBuf::new();
+/// buf.push_segment(BytesMut::from(&b"hello"[..]));
+/// buf.push_segment(BytesMut::from(&b"world"[..]));
+/// assert_eq!(buf.num_chunks(), 2);
+/// assert_eq!(buf.total_len(), 10);
+///
+/// buf.advance(3);
+/// assert_eq!(buf.chunk(), &b"lo"[..]);
+/// ```
+#[derive(Clone)]
+pub struct SegmentedBuf<T> {
     // Invariant: none of the bufs in this queue are zero-length.
-    bufs: VecDeque<Bytes>,
+    //
+    // Lists with a few chunks are stored inline, so creating and cloning them doesn't allocate.
+    bufs: ChunkDeque<T>,
+
+    /// The total number of bytes across all chunks, kept up to date so that `remaining()` is O(1).
+    num_bytes: u64,
 
     /// An index of chunks and their start positions. There's an additional index at the end, which
-    /// is the length of the list (list.num_bytes()).
-    start_pos: OnceCell<Box<[u64]>>,
+    /// is the length of the list (list.total_len()).
+    ///
+    /// This is built lazily, and kept up to date as chunks are pushed. Other mutations invalidate
+    /// it.
+    start_pos: OnceCell<Vec<u64>>,
+
+    /// The address just past the end of the last chunk, if `extend_from_slice` created it. Only
+    /// that chunk may be extended in place.
+    ///
+    /// This is cleared when any other chunk is pushed. If the chunk is later truncated or removed,
+    /// the last chunk no longer ends at this address.
+    tail_end: Option<usize>,
 }
 
-impl BufList {
-    /// Creates a new, empty, `BufList`.
+impl<T> SegmentedBuf<T> {
+    /// Creates a new, empty list.
     #[inline]
     pub fn new() -> Self {
         Self::default()
     }
 
-    #[inline]
-    pub(crate) fn get_start_pos(&self) -> &[u64] {
-        self.start_pos.get_or_init(|| {
-            let mut start_pos = Vec::with_capacity(self.bufs.len() + 1);
-            let mut next = 0u64;
-            for chunk in self.bufs.iter() {
-                start_pos.push(next);
-                next += chunk.len() as u64;
-            }
-            // Add the length of the chunk at the end.
-            start_pos.push(next);
-            start_pos.into_boxed_slice()
-        })
-    }
-
-    /// Creates a new, empty, `BufList` with the given capacity.
+    /// Creates a new, empty list with the given capacity.
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            bufs: VecDeque::with_capacity(capacity),
+            bufs: ChunkDeque::with_capacity(capacity),
+            num_bytes: 0,
             start_pos: OnceCell::new(),
+            tail_end: None,
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more chunks to be added to this list.
+    ///
+    /// This is useful when the number of chunks is known ahead of time, for example when
+    /// collecting a large number of chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let mut buf_list = BufList::new();
+    /// buf_list.reserve(64);
+    /// for _ in 0..64 {
+    ///     buf_list.push_chunk(&b"chunk"[..]);
+    /// }
+    /// assert_eq!(buf_list.num_chunks(), 64);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.bufs.reserve(additional);
+        if let Some(start_pos) = self.start_pos.get_mut() {
+            start_pos.reserve(additional);
         }
     }
 
@@ -69,7 +128,12 @@ impl BufList {
         self.bufs.len()
     }
 
-    /// Returns the total number of bytes across all chunks.
+    /// Returns the total number of bytes across all chunks, as a `u64`.
+    ///
+    /// This is cached, so it takes O(1) time. Unlike [`num_bytes`](Self::num_bytes), this is exact
+    /// even for lists larger than 4 GiB on 32-bit targets. Positions within a `BufList`, such as
+    /// those returned by [`BufList::find`] or used by [`Cursor`](crate::Cursor), are `u64`s for
+    /// the same reason.
     ///
     /// # Examples
     ///
@@ -77,11 +141,11 @@ impl BufList {
     /// use buf_list::BufList;
     ///
     /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
-    /// assert_eq!(buf_list.num_bytes(), 10);
+    /// assert_eq!(buf_list.total_len(), 10);
     /// ```
     #[inline]
-    pub fn num_bytes(&self) -> usize {
-        self.remaining()
+    pub fn total_len(&self) -> u64 {
+        self.num_bytes
     }
 
     /// Provides a reference to the chunk at the given index.
@@ -96,22 +160,138 @@ impl BufList {
     /// assert_eq!(buf_list.get_chunk(1), Some(&Bytes::from(&b"world"[..])));
     /// ```
     #[inline]
-    pub fn get_chunk(&self, index: usize) -> Option<&Bytes> {
+    pub fn get_chunk(&self, index: usize) -> Option<&T> {
         self.bufs.get(index)
     }
 
     /// Iterates over the chunks in this list.
     #[inline]
-    pub fn iter(&self) -> Iter<'_> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             iter: self.bufs.iter(),
         }
     }
 
+    /// Iterates mutably over the segments in this list.
+    ///
+    /// Callers must not change the lengths of the segments, since the list caches them.
+    #[inline]
+    pub(crate) fn segments_mut(&mut self) -> deque::IterMut<'_, T> {
+        self.bufs.iter_mut()
+    }
+
+    /// Returns a mutable reference to the segment at the given index.
+    ///
+    /// Callers must not change the length of the segment, since the list caches it.
+    #[inline]
+    pub(crate) fn segment_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.bufs.get_mut(index)
+    }
+}
+
+impl<T: Buf> SegmentedBuf<T> {
+    #[inline]
+    pub(crate) fn get_start_pos(&self) -> &[u64] {
+        self.start_pos.get_or_init(|| {
+            let mut start_pos = Vec::with_capacity(self.bufs.len() + 1);
+            let mut next = 0u64;
+            for chunk in self.bufs.iter() {
+                start_pos.push(next);
+                next += chunk.remaining() as u64;
+            }
+            // Add the length of the chunk at the end.
+            start_pos.push(next);
+            start_pos
+        })
+    }
+
+    /// Returns the total number of bytes across all chunks.
+    ///
+    /// On 32-bit targets, a list can hold more than `usize::MAX` bytes, in which case this
+    /// saturates at `usize::MAX`. Use [`total_len`](Self::total_len) to get the exact length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(buf_list.num_bytes(), 10);
+    /// ```
+    #[inline]
+    pub fn num_bytes(&self) -> usize {
+        self.remaining()
+    }
+
+    /// Adds a new segment to the end of this list.
+    ///
+    /// Unlike [`BufList::push_chunk`], the segment is stored as is, without being converted to
+    /// [`Bytes`]. If the segment is zero-length, it will not be added to the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::SegmentedBuf;
+    ///
+    /// let mut buf = SegmentedBuf::new();
+    /// buf.push_segment(&b"hello"[..]);
+    /// buf.push_segment(&b""[..]);
+    /// assert_eq!(buf.num_chunks(), 1);
+    /// assert_eq!(buf.get_chunk(0), Some(&&b"hello"[..]));
+    /// ```
+    pub fn push_segment(&mut self, segment: T) {
+        let len = segment.remaining();
+        // Don't push zero-length bufs to uphold the invariant.
+        if len > 0 {
+            self.bufs.push_back(segment);
+            self.num_bytes += len as u64;
+            self.tail_end = None;
+
+            // If the index has been built, extend it rather than invalidating it.
+            if let Some(start_pos) = self.start_pos.get_mut() {
+                let end = *start_pos
+                    .last()
+                    .expect("start_pos always has at least one element");
+                start_pos.push(end + len as u64);
+            }
+        }
+    }
+}
+
+impl SegmentedBuf<BytesMut> {
+    /// Appends data to the last segment in this list, or adds a new segment if the list is empty.
+    pub(crate) fn extend_back(&mut self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        match self.bufs.back_mut() {
+            Some(back) => {
+                back.extend_from_slice(data);
+                self.num_bytes += data.len() as u64;
+                // Only the end of the last segment has moved.
+                if let Some(start_pos) = self.start_pos.get_mut() {
+                    *start_pos
+                        .last_mut()
+                        .expect("start_pos always has at least one element") += data.len() as u64;
+                }
+            }
+            None => self.push_segment(BytesMut::from(data)),
+        }
+    }
+}
+
+impl BufList {
     /// Adds a new chunk to this list.
     ///
     /// If the provided [`Buf`] is zero-length, it will not be added to the list.
     ///
+    /// Each call adds a new chunk, copying the data unless it's already [`Bytes`], no matter how
+    /// small the chunk is. To append lots of small borrowed slices, use
+    /// [`extend_from_slice`](Self::extend_from_slice) instead, which coalesces them into larger
+    /// chunks. To coalesce small chunks while passing large ones through without copying them,
+    /// use a [`Writer`](crate::Writer) with a
+    /// [`min_chunk_size`](crate::BufListBuilder::min_chunk_size).
+    ///
     /// # Examples
     ///
     /// ```
@@ -133,9 +313,6 @@ impl BufList {
     /// assert_eq!(buf_list.num_chunks(), 2);
     /// ```
     pub fn push_chunk<B: Buf>(&mut self, mut data: B) -> Bytes {
-        // mutable borrow acquired, invalidate oncecell
-        self.start_pos = OnceCell::new();
-
         let len = data.remaining();
         // `data` is (almost) certainly a `Bytes`, so `copy_to_bytes` should
         // internally be a cheap refcount bump almost all of the time.
@@ -143,22 +320,216 @@ impl BufList {
         // now clone.
         let bytes = data.copy_to_bytes(len);
 
-        // Buffer a clone. Don't push zero-length bufs to uphold the invariant.
-        if len > 0 {
-            self.bufs.push_back(bytes.clone());
-        }
+        // Buffer a clone.
+        self.push_segment(bytes.clone());
 
         // Return the bytes
         bytes
     }
-}
 
-impl<B: Buf> Extend<B> for BufList {
-    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+    /// Appends a copy of `data` to the end of this list, coalescing small appends into larger
+    /// chunks.
+    ///
+    /// Unlike [`push_chunk`](Self::push_chunk), this doesn't add a chunk per call. Instead, `data`
+    /// is copied into a tail buffer of up to 8 KiB, which is the last chunk of the list. Later
+    /// appends are copied into the same buffer in place, so each byte is copied only once. A new
+    /// tail buffer is started if the last chunk wasn't created by this method, if the buffer is
+    /// full, or if another handle to it exists, for example because the list was cloned. Slices of
+    /// 8 KiB or more are added as chunks of their own.
+    ///
+    /// Chunks added with `push_chunk` are never copied or extended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let mut buf_list = BufList::new();
+    /// for word in ["hello", " ", "world"].iter() {
+    ///     buf_list.extend_from_slice(word.as_bytes());
+    /// }
+    ///
+    /// assert_eq!(buf_list.num_chunks(), 1);
+    /// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"hello world"[..]);
+    /// ```
+    pub fn extend_from_slice(&mut self, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+
+        if let Some(back) = self.bufs.back_mut() {
+            if self.tail_end == Some(chunk_end(back))
+                && back.len() + data.len() <= DEFAULT_CHUNK_SIZE
+            {
+                // Reclaiming the buffer only succeeds if nothing else refers to it, so writing to
+                // it can't be observed through other handles.
+                match mem::replace(back, Bytes::new()).try_into_mut() {
+                    Ok(mut tail) if tail.capacity() - tail.len() >= data.len() => {
+                        tail.extend_from_slice(data);
+                        *back = tail.freeze();
+                        self.tail_end = Some(chunk_end(back));
+                        self.num_bytes += data.len() as u64;
+                        // Only the end of the last chunk has moved.
+                        if let Some(start_pos) = self.start_pos.get_mut() {
+                            *start_pos
+                                .last_mut()
+                                .expect("start_pos always has at least one element") +=
+                                data.len() as u64;
+                        }
+                        return;
+                    }
+                    Ok(tail) => *back = tail.freeze(),
+                    Err(chunk) => *back = chunk,
+                }
+            }
+        }
+
+        let mut tail = BytesMut::with_capacity(DEFAULT_CHUNK_SIZE.max(data.len()));
+        tail.extend_from_slice(data);
+        // Splitting moves the buffer into shared storage, which keeps its spare capacity across
+        // `freeze` and `try_into_mut`.
+        let chunk = tail.split().freeze();
+        let tail_end = chunk_end(&chunk);
+        self.push_segment(chunk);
+        self.tail_end = Some(tail_end);
+    }
+
+    /// Adds a new chunk to this list, unless doing so would make the list longer than `max_bytes`.
+    ///
+    /// This is useful when collecting data from an untrusted source: the total size of the list
+    /// can be bounded without having to check it separately.
+    ///
+    /// As with [`push_chunk`](Self::push_chunk), a zero-length [`Buf`] is not added to the list.
+    /// If the limit would be exceeded, the list is not modified and an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let mut buf_list = BufList::new();
+    /// buf_list.try_push_chunk(&b"hello"[..], 8).unwrap();
+    ///
+    /// let err = buf_list.try_push_chunk(&b"world"[..], 8).unwrap_err();
+    /// assert_eq!(err.limit(), 8);
+    /// assert_eq!(err.attempted(), 10);
+    /// assert_eq!(buf_list.num_bytes(), 5);
+    /// ```
+    pub fn try_push_chunk<B: Buf>(
+        &mut self,
+        data: B,
+        max_bytes: u64,
+    ) -> Result<Bytes, LimitExceededError> {
+        let attempted = self.total_len() + data.remaining() as u64;
+        if attempted > max_bytes {
+            return Err(LimitExceededError {
+                limit: max_bytes,
+                attempted,
+            });
+        }
+        Ok(self.push_chunk(data))
+    }
+
+    /// Removes the first chunk from this list and returns it, or returns `None` if the list is
+    /// empty.
+    #[cfg(any(
+        feature = "actix-web4",
+        feature = "futures03",
+        feature = "http-body04",
+        feature = "http-body1",
+        feature = "tokio1"
+    ))]
+    pub(crate) fn pop_front_chunk(&mut self) -> Option<Bytes> {
+        if !self.has_remaining() {
+            return None;
+        }
+        // Copying out the first chunk in its entirety is just a reference count bump.
+        let len = self.chunk().len();
+        Some(self.copy_to_bytes(len))
+    }
+
+    /// Shortens this list to `len` bytes, dropping the rest.
+    ///
+    /// If `len` is greater than the number of bytes in the list, this has no effect.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        // mutable borrow acquired, invalidate oncecell
+        self.start_pos = OnceCell::new();
+
+        let mut excess = self.num_bytes.saturating_sub(len as u64);
+        self.num_bytes -= excess;
+        while excess > 0 {
+            let back = self
+                .bufs
+                .back_mut()
+                .expect("excess > 0 means there's at least one chunk");
+            if back.len() as u64 <= excess {
+                excess -= back.len() as u64;
+                self.bufs.pop_back();
+            } else {
+                back.truncate(back.len() - excess as usize);
+                excess = 0;
+            }
+        }
+    }
+
+    /// Removes up to `max_bytes` from the front of this list, returning them as a list of chunks.
+    ///
+    /// Chunks are returned in order, without copying them. If `max_bytes` falls in the middle of a
+    /// chunk, that chunk is split: the part before `max_bytes` is returned, and the rest stays at
+    /// the front of this list. If the list has fewer than `max_bytes`, all of its chunks are
+    /// returned.
+    ///
+    /// This is useful for APIs that accept a batch of chunks at a time, such as
+    /// `quinn::SendStream::write_chunks`, when only the first window of a list needs to be sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let mut buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    ///
+    /// let chunks = buf_list.drain_chunks(8);
+    /// assert_eq!(chunks, vec![&b"hello "[..], &b"wo"[..]]);
+    /// assert_eq!(buf_list.num_bytes(), 3);
+    /// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"rld"[..]);
+    /// ```
+    pub fn drain_chunks(&mut self, max_bytes: usize) -> Vec<Bytes> {
         // mutable borrow acquired, invalidate oncecell
         self.start_pos = OnceCell::new();
 
-        for buf in iter.into_iter() {
+        let mut chunks = Vec::new();
+        let mut remaining = max_bytes;
+        while remaining > 0 {
+            let front_len = match self.bufs.front() {
+                Some(front) => front.len(),
+                None => break,
+            };
+            let chunk = if front_len <= remaining {
+                self.bufs
+                    .pop_front()
+                    .expect("front_len was computed from the front chunk")
+            } else {
+                self.bufs[0].split_to(remaining)
+            };
+            remaining -= chunk.len();
+            self.num_bytes -= chunk.len() as u64;
+            chunks.push(chunk);
+        }
+        chunks
+    }
+}
+
+/// Adds each chunk to the end of the list, as with [`BufList::push_chunk`].
+///
+/// Chunks are stored as is, no matter how small they are. To coalesce small chunks, extend a
+/// [`Writer`](crate::Writer) with a [`min_chunk_size`](crate::BufListBuilder::min_chunk_size)
+/// instead.
+impl<B: Buf> Extend<B> for BufList {
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for buf in iter {
             self.push_chunk(buf);
         }
     }
@@ -166,17 +537,39 @@ impl<B: Buf> Extend<B> for BufList {
 
 impl<B: Buf> FromIterator<B> for BufList {
     fn from_iter<T: IntoIterator<Item = B>>(iter: T) -> Self {
-        let mut buf_list = BufList::new();
-        for buf in iter.into_iter() {
+        let iter = iter.into_iter();
+        let mut buf_list = BufList::with_capacity(iter.size_hint().0);
+        for buf in iter {
             buf_list.push_chunk(buf);
         }
         buf_list
     }
 }
 
-impl IntoIterator for BufList {
-    type Item = Bytes;
-    type IntoIter = IntoIter;
+impl<T: fmt::Debug> fmt::Debug for SegmentedBuf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SegmentedBuf")
+            .field("bufs", &self.bufs)
+            .field("num_bytes", &self.num_bytes)
+            .field("start_pos", &self.start_pos)
+            .finish()
+    }
+}
+
+impl<T> Default for SegmentedBuf<T> {
+    fn default() -> Self {
+        Self {
+            bufs: ChunkDeque::new(),
+            num_bytes: 0,
+            start_pos: OnceCell::new(),
+            tail_end: None,
+        }
+    }
+}
+
+impl<T> IntoIterator for SegmentedBuf<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -186,9 +579,9 @@ impl IntoIterator for BufList {
     }
 }
 
-impl<'a> IntoIterator for &'a BufList {
-    type Item = &'a Bytes;
-    type IntoIter = Iter<'a>;
+impl<'a, T> IntoIterator for &'a SegmentedBuf<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -196,15 +589,21 @@ impl<'a> IntoIterator for &'a BufList {
     }
 }
 
-impl AsRef<BufList> for BufList {
-    fn as_ref(&self) -> &BufList {
+impl<T> AsRef<SegmentedBuf<T>> for SegmentedBuf<T> {
+    fn as_ref(&self) -> &SegmentedBuf<T> {
         self
     }
 }
 
-impl Buf for BufList {
+impl<T: Buf> Buf for SegmentedBuf<T> {
+    #[inline]
     fn remaining(&self) -> usize {
-        self.bufs.iter().map(Buf::remaining).sum()
+        // Saturate rather than overflow on 32-bit targets, like `bytes::buf::Chain` does.
+        if self.num_bytes > std::usize::MAX as u64 {
+            std::usize::MAX
+        } else {
+            self.num_bytes as usize
+        }
     }
 
     fn chunk(&self) -> &[u8] {
@@ -220,7 +619,7 @@ impl Buf for BufList {
         // Loop over the buffers in the replay buffer list, and try to fill as
         // many iovecs as we can from each buffer.
         let mut filled = 0;
-        for buf in &self.bufs {
+        for buf in self.bufs.iter() {
             filled += buf.chunks_vectored(&mut iovs[filled..]);
             if filled == iovs.len() {
                 return filled;
@@ -241,12 +640,14 @@ impl Buf for BufList {
             // and we're done.
             if rem > amt {
                 self.bufs[0].advance(amt);
+                self.num_bytes -= amt as u64;
                 return;
             }
 
             // Otherwise, advance the first buffer to its end, and
             // continue.
             self.bufs[0].advance(rem);
+            self.num_bytes -= rem as u64;
             amt -= rem;
 
             self.bufs.pop_front();
@@ -263,6 +664,7 @@ impl Buf for BufList {
         match self.bufs.front_mut() {
             Some(first) if len <= first.remaining() => {
                 let buf = first.copy_to_bytes(len);
+                self.num_bytes -= len as u64;
                 // If we consumed the first buffer, also advance our "cursor" by
                 // popping it.
                 if first.remaining() == 0 {
@@ -294,16 +696,16 @@ impl<T: Into<Bytes>> From<T> for BufList {
     }
 }
 
-/// An owned iterator over chunks in a [`BufList`].
+/// An owned iterator over chunks in a [`SegmentedBuf`], such as a [`BufList`].
 ///
-/// Returned by the [`IntoIterator`] implementation for [`BufList`].
+/// Returned by the [`IntoIterator`] implementation for [`SegmentedBuf`].
 #[derive(Clone, Debug)]
-pub struct IntoIter {
-    iter: std::collections::vec_deque::IntoIter<Bytes>,
+pub struct IntoIter<T = Bytes> {
+    iter: deque::IntoIter<T>,
 }
 
-impl Iterator for IntoIter {
-    type Item = Bytes;
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -316,32 +718,33 @@ impl Iterator for IntoIter {
     }
 }
 
-impl DoubleEndedIterator for IntoIter {
+impl<T> DoubleEndedIterator for IntoIter<T> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back()
     }
 }
 
-impl ExactSizeIterator for IntoIter {
+impl<T> ExactSizeIterator for IntoIter<T> {
     #[inline]
     fn len(&self) -> usize {
         self.iter.len()
     }
 }
 
-impl FusedIterator for IntoIter {}
+impl<T> FusedIterator for IntoIter<T> {}
 
-/// A borrowed iterator over chunks in a [`BufList`].
+/// A borrowed iterator over chunks in a [`SegmentedBuf`], such as a [`BufList`].
 ///
-/// Returned by [`BufList::iter`], and by the [`IntoIterator`] implementation for `&'a BufList`.
+/// Returned by [`SegmentedBuf::iter`], and by the [`IntoIterator`] implementation for
+/// `&'a SegmentedBuf<T>`.
 #[derive(Clone, Debug)]
-pub struct Iter<'a> {
-    iter: std::collections::vec_deque::Iter<'a, Bytes>,
+pub struct Iter<'a, T = Bytes> {
+    iter: deque::Iter<'a, T>,
 }
 
-impl<'a> Iterator for Iter<'a> {
-    type Item = &'a Bytes;
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -382,7 +785,7 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
-impl<'a> DoubleEndedIterator for Iter<'a> {
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back()
@@ -400,11 +803,17 @@ impl<'a> DoubleEndedIterator for Iter<'a> {
     // Can't implement try_rfold as it uses `std::ops::Try` which isn't stable yet, as of Rust 1.67.
 }
 
-impl<'a> ExactSizeIterator for Iter<'a> {
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
     #[inline]
     fn len(&self) -> usize {
         self.iter.len()
     }
 }
 
-impl<'a> FusedIterator for Iter<'a> {}
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+/// Returns the address just past the end of `chunk`.
+#[inline]
+fn chunk_end(chunk: &Bytes) -> usize {
+    chunk.as_ptr() as usize + chunk.len()
+}
@@ -2,10 +2,12 @@
 // Copyright (c) The buf-list Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-#![forbid(unsafe_code)]
+#![deny(unsafe_code)]
 #![warn(missing_docs)]
 #![cfg_attr(doc_cfg, feature(doc_cfg, doc_auto_cfg, doc_cfg_hide))]
-#![cfg_attr(doc_cfg, doc(cfg_hide(const_fn_trait_bounds)))]
+#![cfg_attr(doc_cfg, doc(cfg_hide(const_fn_trait_bounds, read_buf, seek_relative)))]
+#![cfg_attr(read_buf, feature(read_buf, core_io_borrowed_buf))]
+#![cfg_attr(can_vector, feature(can_vector))]
 
 //! A segmented list of [`bytes::Bytes`] chunks.
 //!
@@ -20,6 +22,10 @@
 //!
 //! If you've ever wanted a `Vec<Bytes>` or a `VecDeque<Bytes>`, this type is for you.
 //!
+//! [`BufList`] is an alias for [`SegmentedBuf<Bytes>`](SegmentedBuf). A [`SegmentedBuf`] can hold
+//! segments of any `Buf` type, but most of the functionality in this crate is only available for
+//! `Bytes` chunks.
+//!
 //! # Cursors
 //!
 //! This crate also provides [`Cursor`], which is a cursor type around a [`BufList`]. A [`Cursor`]
@@ -69,37 +75,58 @@
 //! # Ok(()) }
 //! ```
 //!
-//! ## Converting to `Stream`s
+//! If the stream comes from an untrusted peer, collecting it this way can use up an unbounded
+//! amount of memory. With the `futures03` feature enabled, use
+//! [`BufList::try_collect_limited`] instead to stop collecting once a limit is exceeded.
 //!
-//! A `BufList` can be converted into a `futures::Stream`, or a `TryStream`, of `Bytes` chunks. Use
-//! this recipe to do so:
+//! ## Converting to `Stream`s
 //!
-//! (This will be exposed as an API on `BufList` once `Stream` and/or `TryStream` become part of
-//! stable Rust.)
+//! With the `futures03` feature enabled, [`BufList::into_stream`](SegmentedBuf::into_stream)
+//! converts a `BufList` into a `futures::Stream` of `Bytes` chunks, and
+//! [`BufList::into_try_stream`](SegmentedBuf::into_try_stream) converts it into a `TryStream`.
+//! The returned [`IntoStream`] and [`IntoTryStream`] are named types, so they can be stored in
+//! structs.
 //!
 //! ```rust
-//! use buf_list::BufList;
-//! use bytes::Bytes;
-//! use futures::{Stream, TryStream};
-//!
-//! fn into_stream(buf_list: BufList) -> impl Stream<Item = Bytes> {
-//!     futures::stream::iter(buf_list)
-//! }
+//! # #[cfg(feature = "futures03")]
+//! # mod example {
+//! use buf_list::{BufList, IntoTryStream};
+//! use std::io;
 //!
-//! fn into_try_stream<E>(buf_list: BufList) -> impl TryStream<Ok = Bytes, Error = E> {
-//!     futures::stream::iter(buf_list.into_iter().map(Ok))
+//! // For example, a request body that can be replayed.
+//! fn body(buf_list: &BufList) -> IntoTryStream<bytes::Bytes, io::Error> {
+//!     buf_list.clone().into_try_stream()
 //! }
+//! # }
 //! ```
 //!
+//! # Encoding and compression
+//!
+//! The base64 and compression adapters enabled by the `base64022`, `flate21`, `lz4-flex011` and
+//! `zstd013` features work on a list chunk by chunk, so it is never flattened into one contiguous
+//! buffer first. Encoders are fed each chunk in turn, and decoders read the list through a
+//! [`Cursor`], carrying partial input across chunk boundaries. In both directions, the output is
+//! written into a new list in chunks of up to 8 KiB.
+//!
 //! # Optional features
 //!
 //! * `tokio1`: With this feature enabled, [`Cursor`] implements the `tokio` crate's
 //!   [`AsyncSeek`](tokio::io::AsyncSeek), [`AsyncRead`](tokio::io::AsyncRead) and
-//!   [`AsyncBufRead`](tokio::io::AsyncBufRead).
+//!   [`AsyncBufRead`](tokio::io::AsyncBufRead), and [`BufList`] implements
+//!   [`AsyncWrite`](tokio::io::AsyncWrite) and a consuming [`AsyncRead`](tokio::io::AsyncRead).
+//!   This feature also enables [`pipe`], an in-memory pipe that passes chunks through without
+//!   copying them, and [`BroadcastReader`] implements `AsyncRead` and `AsyncBufRead`.
 //!
 //! * `futures03`: With this feature enabled, [`Cursor`] implements the `futures` crate's
 //!   [`AsyncSeek`](futures_io_03::AsyncSeek), [`AsyncRead`](futures_io_03::AsyncRead) and
-//!   [`AsyncBufRead`](futures_io_03::AsyncBufRead).
+//!   [`AsyncBufRead`](futures_io_03::AsyncBufRead), and [`BufList`] implements
+//!   [`AsyncWrite`](futures_io_03::AsyncWrite) and a consuming
+//!   [`AsyncRead`](futures_io_03::AsyncRead). [`Cursor`] also implements
+//!   [`Stream`](futures_core_03::Stream), yielding the remaining chunks, and
+//!   [`BufList::into_stream`](SegmentedBuf::into_stream) and
+//!   [`BufList::into_try_stream`](SegmentedBuf::into_try_stream) convert a list into streams.
+//!   [`BroadcastReader`] implements `AsyncRead`, `AsyncBufRead` and `Stream` as well. This feature
+//!   also enables [`pipe`].
 //!
 //!   Note that supporting `futures03` means exporting 0.x types as a public interface. **This
 //!   violates the
@@ -108,6 +135,92 @@
 //!   is an optional feature and not critical to `buf-list`. As newer versions of the `futures`
 //!   crate are released, `buf-list` will support their versions of the async traits as well.
 //!
+//! * `http-body04`: With this feature enabled, [`BufList`] implements the `http-body` 0.4 crate's
+//!   [`Body`](http_body_04::Body) trait, yielding each chunk as a separate data frame without
+//!   copying it. This makes it possible to return a list directly as a `hyper` 0.14 request or
+//!   response body.
+//!
+//! * `http-body1`: With this feature enabled, [`BufList`] implements the `http-body` 1.0 crate's
+//!   [`Body`](http_body_1::Body) trait in the same way, for use with `hyper` 1.x and `tower-http`.
+//!   [`BufListBody`] wraps a list along with optional trailers, which are sent after the data.
+//!   [`BufList::from_body`] collects a `Body` into a list, preserving chunk boundaries.
+//!
+//! * `hyper014`: With this feature enabled, a [`BufList`] can be converted into a `hyper` 0.14
+//!   [`Body`](hyper_014::Body). This feature also enables `futures03` and `http-body04`.
+//!
+//! * `axum08`: With this feature enabled, [`BufList`] and [`BufListBody`] implement `axum` 0.8's
+//!   [`IntoResponse`](axum_core_05::response::IntoResponse) trait, so that handlers can return
+//!   them directly. Their chunks are streamed out without copying them, along with a
+//!   `content-length` header. This feature also enables `http-body1`.
+//!
+//! * `actix-web4`: With this feature enabled, [`BufList`] implements `actix-web` 4's
+//!   [`MessageBody`](actix_http_3::body::MessageBody) trait, so that services can respond with a
+//!   list without coalescing its chunks.
+//!
+//! * `reqwest012`: With this feature enabled, a [`BufList`] can be converted into a `reqwest` 0.12
+//!   [`Body`](reqwest_012::Body), which streams out the list's chunks without flattening them into
+//!   a single allocation. This feature also enables `http-body1`.
+//!
+//! * `prost014`: With this feature enabled, [`BufList::encode_prost`] encodes a `prost` 0.14
+//!   message into a list, so that large messages are stored in segmented memory rather than one
+//!   big buffer.
+//!
+//! * `positioned-io02`: With this feature enabled, [`BufList`] implements the `positioned-io`
+//!   crate's [`ReadAt`](positioned_io_02::ReadAt) and [`Size`](positioned_io_02::Size) traits,
+//!   so that many readers can share one list without needing separate cursors.
+//!
+//! * `tokio-util07`: With this feature enabled, this crate provides [`LengthDelimitedDecoder`], a
+//!   `tokio-util` [`Decoder`](tokio_util_07::codec::Decoder) which decodes length-delimited frames
+//!   into `BufList`s without copying them, and [`BufList::into_stream_reader`] converts a list
+//!   into a [`StreamReader`](tokio_util_07::io::StreamReader). This feature also enables
+//!   `futures03`.
+//!
+//! * `rkyv08`: With this feature enabled, [`BufList`] can be archived with `rkyv` 0.8. The archived
+//!   form, [`ArchivedBufList`], stores the list's bytes contiguously along with its chunk
+//!   boundaries, so that both can be accessed without deserializing the archive.
+//!
+//! * `proptest1`: With this feature enabled, the [`proptest_strategies`] module provides `proptest`
+//!   strategies which generate lists with varying chunk boundaries, for use in property tests.
+//!
+//! * `quickcheck1`: With this feature enabled, [`BufList`] implements `quickcheck`'s
+//!   [`Arbitrary`](quickcheck_1::Arbitrary) trait, generating lists with varying chunk boundaries.
+//!
+//! * `serde1`: With this feature enabled, [`BufList`] implements `serde`'s `Serialize` and
+//!   `Deserialize` traits. A list is serialized as a contiguous sequence of bytes, and deserialized
+//!   into a list with a single chunk. [`BufListSeed`] deserializes a list without copying data that
+//!   formats like `bincode` borrow from a `Bytes` input buffer.
+//!
+//! * `base64022`: With this feature enabled, [`BufList::encode_base64`] and
+//!   [`BufList::decode_base64`] encode and decode lists as base64 using the `base64` 0.22
+//!   crate, without flattening them.
+//!
+//! * `digest010`: With this feature enabled, [`BufList::update_digest`] feeds a list into a hasher
+//!   from the `digest` 0.10 crate, such as SHA-256, one chunk at a time. [`BufList::digest`]
+//!   computes a hash in one call.
+//!
+//! * `crc32fast1` and `crc32c06`: With these features enabled, [`BufList::crc32`] and
+//!   [`BufList::crc32c`] compute CRC-32 and CRC-32C checksums over a list using the `crc32fast` and
+//!   `crc32c` crates respectively, without flattening it.
+//!
+//! * `xxhash08`: With this feature enabled, [`BufList::fast_hash`] computes a fast,
+//!   non-cryptographic XXH3 hash over a list using the `xxhash-rust` crate, one chunk at a time.
+//!
+//! * `flate21`: With this feature enabled, [`BufList::compress_gzip`] and
+//!   [`BufList::decompress_gzip`] compress and decompress a list with gzip using the `flate2`
+//!   crate, one chunk at a time.
+//!
+//! * `zstd013`: With this feature enabled, [`BufList::compress_zstd`] and
+//!   [`BufList::decompress_zstd`] compress and decompress a list with zstd using the `zstd` crate,
+//!   one chunk at a time. Dictionaries are supported as well.
+//!
+//! * `lz4-flex011`: With this feature enabled, [`BufList::compress_lz4`] and
+//!   [`BufList::decompress_lz4`] compress and decompress a list in the LZ4 frame format using the
+//!   `lz4_flex` crate, one chunk at a time.
+//!
+//! * `zeroize1`: With this feature enabled, this crate provides [`ZeroizingBufList`], a list for
+//!   sensitive data which zeroes out its contents using the `zeroize` crate once they're consumed
+//!   or dropped.
+//!
 //! # Minimum supported Rust version
 //!
 //! The minimum supported Rust version (MSRV) is **1.39**, same as the `bytes` crate. Optional
@@ -116,9 +229,113 @@
 //! The MSRV is not expected to change in the future. If the MSRV changes, it will be accompanied by
 //! a major version bump to `buf-list`.
 
+#[cfg(feature = "actix-web4")]
+mod actix_imp;
+#[cfg(feature = "axum08")]
+mod axum_imp;
+#[cfg(feature = "base64022")]
+mod base64_imp;
+mod broadcast;
+mod builder;
+mod cmp;
+#[cfg(any(
+    feature = "base64022",
+    feature = "flate21",
+    feature = "lz4-flex011",
+    feature = "zstd013"
+))]
+mod compression;
+#[cfg(any(feature = "crc32fast1", feature = "crc32c06"))]
+mod crc32_imp;
 mod cursor;
-pub(crate) mod errors;
+mod deque;
+#[cfg(feature = "digest010")]
+mod digest_imp;
+mod display;
+mod errors;
+#[cfg(feature = "flate21")]
+mod flate2_imp;
+mod framed;
+#[cfg(feature = "futures03")]
+mod futures_imp;
+#[cfg(feature = "http-body04")]
+mod http_body04_imp;
+#[cfg(feature = "http-body1")]
+mod http_body1_imp;
+#[cfg(feature = "hyper014")]
+mod hyper014_imp;
 mod imp;
+#[cfg(feature = "lz4-flex011")]
+mod lz4_imp;
+mod mask;
+mod mutable;
+#[cfg(any(feature = "futures03", feature = "tokio1"))]
+mod pipe;
+mod pool;
+mod positional;
+#[cfg(feature = "positioned-io02")]
+mod positioned_io_imp;
+#[cfg(feature = "proptest1")]
+pub mod proptest_strategies;
+#[cfg(feature = "prost014")]
+mod prost_imp;
+#[cfg(feature = "quickcheck1")]
+mod quickcheck_imp;
+mod reader;
+#[cfg(feature = "reqwest012")]
+mod reqwest_imp;
+#[cfg(feature = "rkyv08")]
+mod rkyv_imp;
+mod search;
+#[cfg(feature = "serde1")]
+mod serde_imp;
+mod split;
+mod stats;
+mod sync;
+mod tail;
+#[cfg(feature = "tokio1")]
+mod tokio_imp;
+#[cfg(feature = "tokio-util07")]
+mod tokio_util_imp;
+mod utf8;
+mod view;
+mod watermark;
+mod writer;
+#[cfg(feature = "xxhash08")]
+mod xxhash_imp;
+#[cfg(feature = "zeroize1")]
+mod zeroize_imp;
+#[cfg(feature = "zstd013")]
+mod zstd_imp;
 
+pub use broadcast::*;
+pub use builder::*;
 pub use cursor::*;
+pub use display::*;
+pub use errors::*;
+#[cfg(feature = "futures03")]
+pub use futures_imp::*;
+#[cfg(feature = "http-body1")]
+pub use http_body1_imp::*;
 pub use imp::*;
+pub use mutable::*;
+#[cfg(any(feature = "futures03", feature = "tokio1"))]
+pub use pipe::*;
+pub use pool::*;
+pub use reader::*;
+#[cfg(feature = "rkyv08")]
+pub use rkyv_imp::*;
+pub use search::*;
+#[cfg(feature = "serde1")]
+pub use serde_imp::*;
+pub use split::*;
+pub use stats::*;
+pub use tail::*;
+#[cfg(feature = "tokio-util07")]
+pub use tokio_util_imp::*;
+pub use utf8::*;
+pub use view::*;
+pub use watermark::*;
+pub use writer::*;
+#[cfg(feature = "zeroize1")]
+pub use zeroize_imp::*;
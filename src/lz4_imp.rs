@@ -0,0 +1,67 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compression::{decode_to_end, encode_chunks},
+    BufList, Writer,
+};
+use lz4_flex_011::frame::{FrameDecoder, FrameEncoder, FrameInfo};
+use std::io;
+
+impl BufList {
+    /// Compresses the bytes in this list as an LZ4 frame.
+    ///
+    /// The list is compressed [chunk by chunk](crate#encoding-and-compression). The frame header
+    /// records the length of the list, so decompressors can size their output up front.
+    ///
+    /// Requires the `lz4-flex011` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let compressed = buf_list.compress_lz4();
+    ///
+    /// let decompressed = compressed.decompress_lz4().unwrap();
+    /// assert_eq!(decompressed.to_str_lossy(), "hello world");
+    /// ```
+    pub fn compress_lz4(&self) -> BufList {
+        self.compress_lz4_with(FrameInfo::new().content_size(Some(self.total_len())))
+    }
+
+    /// Compresses the bytes in this list as an LZ4 frame, using the provided frame options.
+    ///
+    /// This behaves like [`compress_lz4`](Self::compress_lz4), except that the block size,
+    /// checksums and other frame options are taken from `frame_info`. The content size isn't
+    /// recorded unless `frame_info` sets it.
+    ///
+    /// Requires the `lz4-flex011` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame_info` sets a content size that doesn't match the length of the list.
+    pub fn compress_lz4_with(&self, frame_info: FrameInfo) -> BufList {
+        let encoder = FrameEncoder::with_frame_info(frame_info, Writer::new());
+        encode_chunks(self, encoder)
+            .expect("writing to a Writer without a limit is infallible")
+            .finish()
+            .expect("content size matches the length of the list")
+            .into_inner()
+    }
+
+    /// Decompresses the bytes in this list as a sequence of LZ4 frames.
+    ///
+    /// The list is decompressed [chunk by chunk](crate#encoding-and-compression).
+    ///
+    /// Requires the `lz4-flex011` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list isn't valid LZ4 frame data, or if it ends in the middle of a
+    /// frame.
+    pub fn decompress_lz4(&self) -> io::Result<BufList> {
+        decode_to_end(self, |reader| Ok(FrameDecoder::new(reader)))
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! XOR masking across chunk boundaries, as used by WebSocket frames.
+
+use crate::{BufList, BufListMut};
+use bytes::BytesMut;
+
+impl BufList {
+    /// Returns a copy of this list with a 4-byte XOR mask applied to its bytes.
+    ///
+    /// The mask is applied over the bytes in the list as a whole, starting with `key[0]`: the
+    /// mask's phase carries across chunk boundaries. This is the masking that
+    /// [RFC 6455](https://www.rfc-editor.org/rfc/rfc6455#section-5.3) requires for WebSocket
+    /// frames sent by clients. Since masking is its own inverse, the same method also unmasks data.
+    ///
+    /// The returned list has the same chunk boundaries as this one. To mask data in place without
+    /// copying it, use [`BufListMut::apply_mask`](crate::SegmentedBuf::apply_mask).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let key = [0x37, 0xfa, 0x21, 0x3d];
+    /// let buf_list = vec![&b"Hel"[..], &b"lo"[..]].into_iter().collect::<BufList>();
+    ///
+    /// // This example comes from section 5.7 of RFC 6455.
+    /// let masked = buf_list.masked(key);
+    /// assert_eq!(masked.get_chunk(0).unwrap(), &b"\x7f\x9f\x4d"[..]);
+    /// assert_eq!(masked.get_chunk(1).unwrap(), &b"\x51\x58"[..]);
+    ///
+    /// assert_eq!(masked.masked(key).to_str_lossy(), "Hello");
+    /// ```
+    pub fn masked(&self, key: [u8; 4]) -> BufList {
+        let mut out = BufList::with_capacity(self.num_chunks());
+        let mut phase = 0;
+        for chunk in self.iter() {
+            let mut chunk = BytesMut::from(&chunk[..]);
+            phase = mask_slice(&mut chunk, key, phase);
+            out.push_chunk(chunk.freeze());
+        }
+        out
+    }
+}
+
+impl BufListMut {
+    /// Applies a 4-byte XOR mask to the bytes in this list, in place.
+    ///
+    /// As with [`BufList::masked`](crate::SegmentedBuf::masked), the mask is applied over the bytes
+    /// in the list as a whole, starting with `key[0]`. Applying the same mask twice restores the
+    /// original data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufListMut;
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf_list = BufListMut::new();
+    /// buf_list.push_segment(BytesMut::from(&b"Hel"[..]));
+    /// buf_list.push_segment(BytesMut::from(&b"lo"[..]));
+    ///
+    /// buf_list.apply_mask([0x37, 0xfa, 0x21, 0x3d]);
+    /// assert_eq!(buf_list.get_chunk(1).unwrap(), &b"\x51\x58"[..]);
+    ///
+    /// buf_list.apply_mask([0x37, 0xfa, 0x21, 0x3d]);
+    /// assert_eq!(buf_list.freeze().to_str_lossy(), "Hello");
+    /// ```
+    pub fn apply_mask(&mut self, key: [u8; 4]) {
+        let mut phase = 0;
+        for chunk in self.iter_mut() {
+            phase = mask_slice(chunk, key, phase);
+        }
+    }
+}
+
+/// Masks `data` with `key`, starting at `key[phase]`, and returns the phase to continue from.
+fn mask_slice(data: &mut [u8], key: [u8; 4], phase: usize) -> usize {
+    for (byte, k) in data.iter_mut().zip(key.iter().cycle().skip(phase)) {
+        *byte ^= k;
+    }
+    (phase + data.len()) % 4
+}
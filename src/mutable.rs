@@ -0,0 +1,136 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{deque, BufList, SegmentedBuf};
+use bytes::BytesMut;
+use std::iter::FusedIterator;
+
+/// Data composed of a list of [`BytesMut`] chunks, which can be modified in place.
+///
+/// This is the mutable counterpart of [`BufList`]: a payload can be built up piecewise, patched
+/// in place, and then turned into a `BufList` with [`freeze`](SegmentedBuf::freeze) without
+/// copying any data.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::BufListMut;
+/// use bytes::BytesMut;
+///
+/// let mut buf_list = BufListMut::new();
+/// buf_list.push_segment(BytesMut::from(&b"\0\0hello"[..]));
+/// buf_list.push_segment(BytesMut::from(&b"world"[..]));
+///
+/// // Fill in a length prefix after the fact.
+/// let len = buf_list.total_len() as u16 - 2;
+/// buf_list.get_chunk_mut(0).unwrap()[..2].copy_from_slice(&len.to_be_bytes());
+///
+/// let buf_list = buf_list.freeze();
+/// assert_eq!(buf_list.to_str_lossy(), "\0\x0ahelloworld");
+/// ```
+pub type BufListMut = SegmentedBuf<BytesMut>;
+
+impl SegmentedBuf<BytesMut> {
+    /// Provides a mutable reference to the contents of the chunk at the given index.
+    ///
+    /// The chunk is returned as a slice, so its contents can be changed but its length can't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufListMut;
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf_list = BufListMut::new();
+    /// buf_list.push_segment(BytesMut::from(&b"hello"[..]));
+    /// buf_list.get_chunk_mut(0).unwrap().make_ascii_uppercase();
+    /// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"HELLO"[..]);
+    /// assert!(buf_list.get_chunk_mut(1).is_none());
+    /// ```
+    #[inline]
+    pub fn get_chunk_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        self.segment_mut(index).map(|chunk| &mut chunk[..])
+    }
+
+    /// Iterates mutably over the contents of the chunks in this list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufListMut;
+    /// use bytes::BytesMut;
+    ///
+    /// let mut buf_list = BufListMut::new();
+    /// buf_list.push_segment(BytesMut::from(&b"hello"[..]));
+    /// buf_list.push_segment(BytesMut::from(&b"world"[..]));
+    /// for chunk in buf_list.iter_mut() {
+    ///     chunk[0] = b'_';
+    /// }
+    /// assert_eq!(buf_list.freeze().to_str_lossy(), "_ello_orld");
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        IterMut {
+            iter: self.segments_mut(),
+        }
+    }
+
+    /// Converts this list into an immutable [`BufList`], without copying any data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufListMut;
+    /// use bytes::{Buf, BytesMut};
+    ///
+    /// let mut buf_list = BufListMut::new();
+    /// buf_list.push_segment(BytesMut::from(&b"hello"[..]));
+    /// let buf_list = buf_list.freeze();
+    /// assert_eq!(buf_list.chunk(), &b"hello"[..]);
+    /// ```
+    pub fn freeze(self) -> BufList {
+        let mut buf_list = BufList::with_capacity(self.num_chunks());
+        for chunk in self {
+            buf_list.push_chunk(chunk.freeze());
+        }
+        buf_list
+    }
+}
+
+/// A mutable iterator over the contents of the chunks in a [`BufListMut`].
+///
+/// Returned by [`BufListMut::iter_mut`](SegmentedBuf::iter_mut).
+#[derive(Debug)]
+pub struct IterMut<'a> {
+    iter: deque::IterMut<'a, BytesMut>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = &'a mut [u8];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|chunk| &mut chunk[..])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for IterMut<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|chunk| &mut chunk[..])
+    }
+}
+
+impl<'a> ExactSizeIterator for IterMut<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a> FusedIterator for IterMut<'a> {}
@@ -0,0 +1,302 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{sync::lock, BufList};
+use bytes::{Buf, Bytes};
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// Creates an in-memory pipe which buffers up to `capacity` bytes in a [`BufList`].
+///
+/// Data written to the [`PipeWriter`] can be read from the [`PipeReader`]. Unlike
+/// `tokio::io::duplex`, chunks are passed through as they are: chunks added with
+/// [`PipeWriter::push_chunk`] are never copied, and the reader can receive them as a `Stream` of
+/// [`Bytes`] without copying them either.
+///
+/// Once `capacity` bytes are buffered, writes wait until the reader has made room for them. A
+/// chunk larger than `capacity` is added once the pipe is empty.
+///
+/// * With the `tokio1` feature, the writer implements `tokio`'s `AsyncWrite`, and the reader
+///   implements `AsyncRead` and `AsyncBufRead`.
+/// * With the `futures03` feature, the writer implements `futures`' `AsyncWrite`, and the reader
+///   implements `AsyncRead`, `AsyncBufRead` and `Stream<Item = Bytes>`.
+///
+/// Dropping or shutting down the writer signals end-of-file to the reader, once the buffered data
+/// has been read. Dropping the reader causes writes to fail with
+/// [`io::ErrorKind::BrokenPipe`].
+///
+/// Requires the `tokio1` or `futures03` feature.
+///
+/// # Panics
+///
+/// Panics if `capacity` is 0.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "tokio1")]
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use bytes::Bytes;
+/// use tokio::io::AsyncReadExt;
+///
+/// let (mut writer, mut reader) = buf_list::pipe(64);
+///
+/// let producer = tokio::spawn(async move {
+///     for _ in 0..4 {
+///         writer.push_chunk(Bytes::from_static(&[b'x'; 32])).await.unwrap();
+///     }
+///     // Dropping the writer closes the pipe.
+/// });
+///
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).await.unwrap();
+/// assert_eq!(data.len(), 128);
+/// producer.await.unwrap();
+/// # }
+/// # #[cfg(not(feature = "tokio1"))]
+/// # fn main() {}
+/// ```
+pub fn pipe(capacity: usize) -> (PipeWriter, PipeReader) {
+    assert!(capacity > 0, "capacity must be greater than 0");
+    let shared = Arc::new(Mutex::new(Shared {
+        list: BufList::new(),
+        capacity,
+        writer_closed: false,
+        reader_closed: false,
+        reader_waker: None,
+        writer_waker: None,
+    }));
+    let writer = PipeWriter {
+        shared: shared.clone(),
+    };
+    let reader = PipeReader {
+        shared,
+        current: Bytes::new(),
+    };
+    (writer, reader)
+}
+
+/// The state shared between the two halves of a pipe.
+#[derive(Debug)]
+struct Shared {
+    list: BufList,
+    capacity: usize,
+    writer_closed: bool,
+    reader_closed: bool,
+    // Only one task can wait on each half at a time, since each half requires &mut access.
+    reader_waker: Option<Waker>,
+    writer_waker: Option<Waker>,
+}
+
+impl Shared {
+    fn wake_reader(&mut self) {
+        if let Some(waker) = self.reader_waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn wake_writer(&mut self) {
+        if let Some(waker) = self.writer_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The writing half of a [`pipe`].
+#[derive(Debug)]
+pub struct PipeWriter {
+    shared: Arc<Mutex<Shared>>,
+}
+
+impl PipeWriter {
+    /// Returns the maximum number of bytes buffered in the pipe before writes wait for the reader.
+    pub fn capacity(&self) -> usize {
+        lock(&self.shared).capacity
+    }
+
+    /// Adds a chunk of data to the pipe without copying it (if it's [`Bytes`]), waiting for the
+    /// reader to make room for it if necessary.
+    ///
+    /// The returned future resolves to an error if the reader has been dropped, or if this writer
+    /// has been shut down.
+    pub fn push_chunk<B: Buf>(&mut self, mut data: B) -> PushChunk<'_> {
+        let len = data.remaining();
+        PushChunk {
+            writer: self,
+            chunk: Some(data.copy_to_bytes(len)),
+        }
+    }
+
+    pub(crate) fn poll_push_chunk_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+        chunk: &mut Option<Bytes>,
+    ) -> Poll<io::Result<()>> {
+        let mut shared = lock(&self.shared);
+        check_writable(&shared)?;
+        let len = chunk.as_ref().map_or(0, |chunk| chunk.len());
+        let buffered = shared.list.num_bytes();
+        if buffered > 0 && buffered + len > shared.capacity {
+            shared.writer_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        if let Some(chunk) = chunk.take() {
+            shared.list.push_chunk(chunk);
+        }
+        shared.wake_reader();
+        Poll::Ready(Ok(()))
+    }
+
+    pub(crate) fn poll_write_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut shared = lock(&self.shared);
+        check_writable(&shared)?;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let available = shared.capacity.saturating_sub(shared.list.num_bytes());
+        if available == 0 {
+            shared.writer_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let n = available.min(buf.len());
+        shared.list.push_chunk(Bytes::copy_from_slice(&buf[..n]));
+        shared.wake_reader();
+        Poll::Ready(Ok(n))
+    }
+
+    pub(crate) fn close_impl(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.writer_closed = true;
+        shared.wake_reader();
+    }
+}
+
+fn check_writable(shared: &Shared) -> io::Result<()> {
+    if shared.reader_closed {
+        Err(io::Error::new(
+            io::ErrorKind::BrokenPipe,
+            "pipe reader was dropped",
+        ))
+    } else if shared.writer_closed {
+        Err(io::Error::new(
+            io::ErrorKind::BrokenPipe,
+            "pipe writer was shut down",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        self.close_impl();
+    }
+}
+
+/// A future which adds a chunk to a [`pipe`].
+///
+/// Returned by [`PipeWriter::push_chunk`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct PushChunk<'a> {
+    writer: &'a mut PipeWriter,
+    // This is None once the chunk has been added.
+    chunk: Option<Bytes>,
+}
+
+impl<'a> Future for PushChunk<'a> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        this.writer.poll_push_chunk_impl(cx, &mut this.chunk)
+    }
+}
+
+/// The reading half of a [`pipe`].
+#[derive(Debug)]
+pub struct PipeReader {
+    shared: Arc<Mutex<Shared>>,
+    // The chunk currently being read, which has already been removed from the pipe.
+    current: Bytes,
+}
+
+impl PipeReader {
+    /// Removes the next chunk from the pipe, waiting for one to be written if necessary.
+    ///
+    /// Returns `None` once the writer has been closed and all the data has been read.
+    fn poll_next_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        let mut shared = lock(&self.shared);
+        if !shared.list.has_remaining() {
+            if shared.writer_closed {
+                return Poll::Ready(None);
+            }
+            shared.reader_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let chunk = shared.list.pop_front_chunk();
+        shared.wake_writer();
+        Poll::Ready(chunk)
+    }
+
+    pub(crate) fn poll_fill_buf_impl(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        if self.current.is_empty() {
+            match self.poll_next_chunk(cx) {
+                Poll::Ready(Some(chunk)) => self.current = chunk,
+                Poll::Ready(None) => {}
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(&self.current))
+    }
+
+    pub(crate) fn consume_impl(&mut self, amt: usize) {
+        let amt = amt.min(self.current.len());
+        self.current.advance(amt);
+    }
+
+    #[cfg(feature = "futures03")]
+    pub(crate) fn poll_read_impl(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let chunk = match self.poll_fill_buf_impl(cx) {
+            Poll::Ready(Ok(chunk)) => chunk,
+            Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+            Poll::Pending => return Poll::Pending,
+        };
+        let n = chunk.len().min(buf.len());
+        buf[..n].copy_from_slice(&chunk[..n]);
+        self.consume_impl(n);
+        Poll::Ready(Ok(n))
+    }
+
+    #[cfg(feature = "futures03")]
+    pub(crate) fn poll_next_impl(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        if !self.current.is_empty() {
+            return Poll::Ready(Some(std::mem::replace(&mut self.current, Bytes::new())));
+        }
+        self.poll_next_chunk(cx)
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.reader_closed = true;
+        // Nothing will read the buffered data, so free it right away.
+        shared.list = BufList::new();
+        shared.wake_writer();
+    }
+}
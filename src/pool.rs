@@ -0,0 +1,128 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use bytes::BytesMut;
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// A pool of fixed-size [`BytesMut`] blocks, which reuses the memory of blocks once they're no
+/// longer in use.
+///
+/// Servers that build lots of short-lived [`BufList`](crate::BufList)s allocate and free lots of
+/// blocks. A `ChunkPool` keeps track of the most recently handed out blocks, and once every
+/// `BytesMut` and [`Bytes`](bytes::Bytes) that was split off or frozen from a block has been
+/// dropped, its memory is reused for a new block rather than allocating again.
+///
+/// Reclamation is lazy: a block's memory is reclaimed when [`get`](Self::get) is called, not when
+/// the last reference to it is dropped. If the oldest block being tracked is still in use, a new
+/// block is allocated in its place, and the old block is freed as usual once it's dropped.
+///
+/// `ChunkPool` is cheap to clone, and clones share the same blocks. A [`Writer`](crate::Writer)
+/// can draw its blocks from a pool -- see [`Writer::with_pool`](crate::Writer::with_pool).
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::ChunkPool;
+/// use bytes::BufMut;
+///
+/// let pool = ChunkPool::new(4096, 1);
+///
+/// let mut block = pool.get();
+/// assert_eq!(block.capacity(), 4096);
+/// block.put_slice(b"hello");
+/// let bytes = block.freeze();
+/// let ptr = bytes.as_ptr();
+/// drop(bytes);
+///
+/// // The memory is reused once the previous block has been dropped.
+/// let block = pool.get();
+/// assert_eq!(block.as_ptr(), ptr);
+/// ```
+#[derive(Clone)]
+pub struct ChunkPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    block_size: usize,
+    max_blocks: usize,
+    /// Handles to the blocks that have been handed out, oldest first.
+    ///
+    /// Each handle is an empty `BytesMut` that shares its allocation with a block. Once all the
+    /// other references to the allocation are dropped, reserving space on the handle reclaims
+    /// the allocation without copying.
+    blocks: Mutex<VecDeque<BytesMut>>,
+}
+
+impl ChunkPool {
+    /// Creates a new pool which hands out blocks of `block_size` bytes, and tracks up to
+    /// `max_blocks` blocks for reuse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is 0.
+    pub fn new(block_size: usize, max_blocks: usize) -> Self {
+        assert!(block_size > 0, "block_size must be greater than 0");
+        Self {
+            inner: Arc::new(PoolInner {
+                block_size,
+                max_blocks,
+                blocks: Mutex::new(VecDeque::with_capacity(max_blocks)),
+            }),
+        }
+    }
+
+    /// Returns the capacity of the blocks handed out by this pool.
+    #[inline]
+    pub fn block_size(&self) -> usize {
+        self.inner.block_size
+    }
+
+    /// Returns the maximum number of blocks this pool tracks for reuse.
+    #[inline]
+    pub fn max_blocks(&self) -> usize {
+        self.inner.max_blocks
+    }
+
+    /// Returns an empty block with a capacity of [`block_size`](Self::block_size) bytes.
+    ///
+    /// If the oldest block being tracked is no longer in use, its memory is reused. Otherwise, a
+    /// new block is allocated.
+    pub fn get(&self) -> BytesMut {
+        let inner = &*self.inner;
+        if inner.max_blocks == 0 {
+            return BytesMut::with_capacity(inner.block_size);
+        }
+
+        // A panic while holding the lock can't leave the queue in an inconsistent state.
+        let mut blocks = inner
+            .blocks
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let mut handle = if blocks.len() < inner.max_blocks {
+            BytesMut::with_capacity(inner.block_size)
+        } else {
+            let mut handle = blocks.pop_front().expect("max_blocks is non-zero");
+            // If this is the only remaining reference to the allocation, this reclaims it.
+            // Otherwise, it allocates a new one.
+            handle.reserve(inner.block_size);
+            handle
+        };
+        let block = handle.split_off(0);
+        blocks.push_back(handle);
+        block
+    }
+}
+
+impl fmt::Debug for ChunkPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkPool")
+            .field("block_size", &self.inner.block_size)
+            .field("max_blocks", &self.inner.max_blocks)
+            .finish()
+    }
+}
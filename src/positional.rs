@@ -0,0 +1,151 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Random access to the bytes in a `BufList`, by position.
+
+use crate::{errors::ReadExactError, split::ChunkPos, BufList};
+use std::{
+    io,
+    ops::{Bound, RangeBounds},
+};
+
+impl BufList {
+    /// Returns the byte at position `pos`, or `None` if `pos` is past the end of the list.
+    ///
+    /// The chunk containing `pos` is found with a binary search over the list's index of chunk
+    /// start positions, so this takes O(log n) time in the number of chunks. The index is built on
+    /// first use, and kept up to date as chunks are pushed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(buf_list.byte_at(0), Some(b'h'));
+    /// assert_eq!(buf_list.byte_at(5), Some(b'w'));
+    /// assert_eq!(buf_list.byte_at(10), None);
+    /// ```
+    pub fn byte_at(&self, pos: u64) -> Option<u8> {
+        let mut byte = [0u8];
+        if ChunkPos::at(self, pos).copy_to_slice(self, &mut byte) == 1 {
+            Some(byte[0])
+        } else {
+            None
+        }
+    }
+
+    /// Copies bytes starting at position `pos` into `buf`, returning the number of bytes copied.
+    ///
+    /// Fewer than `buf.len()` bytes are copied only if the end of the list is reached. If `pos` is
+    /// past the end of the list, nothing is copied and 0 is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    ///
+    /// let mut buf = [0u8; 4];
+    /// assert_eq!(buf_list.read_at(3, &mut buf), 4);
+    /// assert_eq!(&buf, b"lowo");
+    ///
+    /// assert_eq!(buf_list.read_at(8, &mut buf), 2);
+    /// assert_eq!(&buf[..2], b"ld");
+    /// ```
+    pub fn read_at(&self, pos: u64, buf: &mut [u8]) -> usize {
+        ChunkPos::at(self, pos).copy_to_slice(self, buf)
+    }
+
+    /// Fills `buf` with bytes starting at position `pos`.
+    ///
+    /// Like [`read_at`](Self::read_at), this takes `&self`, so many readers can read from the
+    /// same list concurrently without any locking.
+    ///
+    /// # Errors
+    ///
+    /// If fewer than `buf.len()` bytes are available starting at `pos`, this returns an error of
+    /// kind [`io::ErrorKind::UnexpectedEof`], and the contents of `buf` are unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    ///
+    /// let mut buf = [0u8; 4];
+    /// buf_list.read_exact_at(3, &mut buf).unwrap();
+    /// assert_eq!(&buf, b"lowo");
+    ///
+    /// assert!(buf_list.read_exact_at(8, &mut buf).is_err());
+    /// ```
+    pub fn read_exact_at(&self, pos: u64, buf: &mut [u8]) -> io::Result<()> {
+        let remaining = self.total_len().saturating_sub(pos);
+        if remaining < buf.len() as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                ReadExactError {
+                    remaining,
+                    buf_len: buf.len(),
+                },
+            ));
+        }
+        self.read_at(pos, buf);
+        Ok(())
+    }
+
+    /// Returns a new `BufList` with the bytes in `range`.
+    ///
+    /// This doesn't copy any data: the chunks in the new list are slices of the chunks in this
+    /// one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end, or if the end of the range is
+    /// past the end of the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Buf;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    /// let slice = buf_list.slice(3..7);
+    /// assert_eq!(slice.num_chunks(), 2);
+    /// assert_eq!(slice.chunk(), &b"lo"[..]);
+    /// assert_eq!(slice.to_str_lossy(), "lowo");
+    ///
+    /// assert_eq!(buf_list.slice(5..).to_str_lossy(), "world");
+    /// ```
+    pub fn slice(&self, range: impl RangeBounds<u64>) -> BufList {
+        let total_len = self.total_len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("out of range"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("out of range"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => total_len,
+        };
+
+        assert!(
+            start <= end,
+            "range start must not be greater than end: {:?} <= {:?}",
+            start,
+            end,
+        );
+        assert!(
+            end <= total_len,
+            "range end out of bounds: {:?} <= {:?}",
+            end,
+            total_len,
+        );
+
+        ChunkPos::at(self, start).take(self, end - start)
+    }
+}
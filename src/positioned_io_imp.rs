@@ -0,0 +1,25 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use positioned_io_02::{ReadAt, Size};
+use std::io;
+
+impl ReadAt for BufList {
+    #[inline]
+    fn read_at(&self, pos: u64, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(BufList::read_at(self, pos, buf))
+    }
+
+    #[inline]
+    fn read_exact_at(&self, pos: u64, buf: &mut [u8]) -> io::Result<()> {
+        BufList::read_exact_at(self, pos, buf)
+    }
+}
+
+impl Size for BufList {
+    #[inline]
+    fn size(&self) -> io::Result<Option<u64>> {
+        Ok(Some(self.total_len()))
+    }
+}
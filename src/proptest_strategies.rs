@@ -0,0 +1,65 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! `proptest` strategies for generating [`BufList`]s.
+//!
+//! Code that handles segmented buffers often has bugs at chunk boundaries. The strategies here
+//! generate lists with varying numbers of chunks and chunk sizes, so that property tests exercise
+//! those boundaries.
+//!
+//! Requires the `proptest1` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use buf_list::proptest_strategies;
+//! use bytes::Buf;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(buf_list in proptest_strategies::buf_list())| {
+//!     let mut buf_list = buf_list;
+//!     let len = buf_list.remaining();
+//!     prop_assert_eq!(buf_list.copy_to_bytes(len).len(), len);
+//! });
+//! ```
+
+use crate::BufList;
+use bytes::Bytes;
+use proptest::{collection::SizeRange, prelude::*};
+
+/// Returns a strategy which generates lists of 0 to 31 chunks, each 1 to 127 bytes long.
+///
+/// Generated lists shrink towards fewer and shorter chunks.
+pub fn buf_list() -> impl Strategy<Value = BufList> {
+    buf_list_of(prop::collection::vec(any::<u8>(), 1..128), 0..32)
+}
+
+/// Returns a strategy which generates lists with a number of chunks in the range `chunks`, where
+/// the contents of each chunk are generated by `bytes_strategy`.
+///
+/// Empty chunks generated by `bytes_strategy` aren't added to the list, as with
+/// [`BufList::push_chunk`], so the list may have fewer chunks than were generated.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::proptest_strategies;
+/// use proptest::prelude::*;
+///
+/// // Lists of 4 to 8 chunks, each consisting of 1 or 2 ASCII digits.
+/// let strategy = proptest_strategies::buf_list_of(
+///     prop::collection::vec(b'0'..=b'9', 1..=2),
+///     4..=8,
+/// );
+/// ```
+pub fn buf_list_of<S>(
+    bytes_strategy: S,
+    chunks: impl Into<SizeRange>,
+) -> impl Strategy<Value = BufList>
+where
+    S: Strategy,
+    S::Value: Into<Bytes>,
+{
+    prop::collection::vec(bytes_strategy, chunks)
+        .prop_map(|chunks| chunks.into_iter().map(Into::into).collect())
+}
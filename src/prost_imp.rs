@@ -0,0 +1,58 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{writer::DEFAULT_CHUNK_SIZE, BufList, Writer};
+use prost_014::{length_delimiter_len, Message};
+
+impl BufList {
+    /// Encodes a `prost` message into a new list.
+    ///
+    /// Messages larger than 8 KiB are split across several chunks rather than being encoded into
+    /// one large allocation. Smaller messages are encoded into a single chunk of exactly the right
+    /// size. To encode several messages into the same list, or to control the chunk size, encode
+    /// them into a [`Writer`] instead, since it implements [`BufMut`](bytes::BufMut).
+    ///
+    /// Messages can be decoded directly from a list with [`Message::decode`], since `BufList`
+    /// implements [`Buf`](bytes::Buf). Decoding a `bytes::Bytes` field that lies within a single
+    /// chunk doesn't copy it.
+    ///
+    /// Requires the `prost014` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use prost_014::Message;
+    ///
+    /// let msg = "hello world".to_owned();
+    /// let buf_list = BufList::encode_prost(&msg);
+    /// assert_eq!(buf_list.num_bytes(), msg.encoded_len());
+    ///
+    /// let decoded = String::decode(buf_list).unwrap();
+    /// assert_eq!(decoded, msg);
+    /// ```
+    pub fn encode_prost<M: Message>(msg: &M) -> Self {
+        let mut writer = writer_for_len(msg.encoded_len());
+        msg.encode(&mut writer)
+            .expect("writers without a limit have enough capacity");
+        writer.into_inner()
+    }
+
+    /// Encodes a `prost` message into a new list, prefixed with its length as a varint.
+    ///
+    /// This is the framing used by [`Message::decode_length_delimited`], which can decode messages
+    /// from the returned list.
+    ///
+    /// Requires the `prost014` feature.
+    pub fn encode_prost_length_delimited<M: Message>(msg: &M) -> Self {
+        let len = msg.encoded_len();
+        let mut writer = writer_for_len(length_delimiter_len(len) + len);
+        msg.encode_length_delimited(&mut writer)
+            .expect("writers without a limit have enough capacity");
+        writer.into_inner()
+    }
+}
+
+fn writer_for_len(len: usize) -> Writer {
+    Writer::with_chunk_size(len.max(1).min(DEFAULT_CHUNK_SIZE))
+}
@@ -0,0 +1,29 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::Bytes;
+use quickcheck_1::{Arbitrary, Gen};
+
+/// Generates lists with arbitrary numbers of chunks, each with arbitrary contents.
+///
+/// Lists shrink by removing chunks as well as by shrinking the contents of each chunk, so that
+/// failures are reported with minimal chunk structures. Empty chunks aren't added to the list, as
+/// with [`BufList::push_chunk`].
+impl Arbitrary for BufList {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Vec::<Vec<u8>>::arbitrary(g)
+            .into_iter()
+            .map(Bytes::from)
+            .collect()
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let chunks: Vec<Vec<u8>> = self.iter().map(|chunk| chunk.to_vec()).collect();
+        Box::new(
+            chunks
+                .shrink()
+                .map(|chunks| chunks.into_iter().map(Bytes::from).collect()),
+        )
+    }
+}
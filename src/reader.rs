@@ -0,0 +1,105 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::Buf;
+use std::io;
+
+impl BufList {
+    /// Converts this list into a reader which consumes chunks as they're read.
+    ///
+    /// Unlike a [`Cursor`](crate::Cursor), the returned [`IntoReader`] can't seek, so it never
+    /// builds the index of chunk positions a `Cursor` uses, and chunks are freed as soon as they've
+    /// been read. Use it when a list only needs to be read through once.
+    ///
+    /// `IntoReader` implements [`io::Read`] and [`io::BufRead`], and with the `tokio1` and
+    /// `futures03` features, the `AsyncRead` and `AsyncBufRead` traits from those crates.
+    /// Since the same reader serves both synchronous and asynchronous code, it's named
+    /// `into_reader` rather than `into_async_read`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use std::io::Read;
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let mut reader = buf_list.into_reader();
+    ///
+    /// let mut s = String::new();
+    /// reader.read_to_string(&mut s).unwrap();
+    /// assert_eq!(s, "hello world");
+    /// ```
+    #[inline]
+    pub fn into_reader(self) -> IntoReader {
+        IntoReader { inner: self }
+    }
+
+    /// Reads from the front of this list into `buf`, consuming the bytes that were read.
+    pub(crate) fn read_consuming(&mut self, buf: &mut [u8]) -> usize {
+        let mut nread = 0;
+        while nread < buf.len() && self.has_remaining() {
+            let chunk = self.chunk();
+            let n = chunk.len().min(buf.len() - nread);
+            buf[nread..nread + n].copy_from_slice(&chunk[..n]);
+            self.advance(n);
+            nread += n;
+        }
+        nread
+    }
+}
+
+/// A sequential reader over a [`BufList`], which consumes chunks as they're read.
+///
+/// Returned by [`BufList::into_reader`].
+#[derive(Clone, Debug)]
+pub struct IntoReader {
+    inner: BufList,
+}
+
+impl IntoReader {
+    /// Returns the number of bytes left to read.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    /// Gets a reference to the data left to read.
+    #[inline]
+    pub fn get_ref(&self) -> &BufList {
+        &self.inner
+    }
+
+    /// Consumes this reader, returning the data left to read.
+    #[inline]
+    pub fn into_inner(self) -> BufList {
+        self.inner
+    }
+
+    /// Gets a mutable reference to the data left to read.
+    #[cfg(feature = "tokio1")]
+    #[inline]
+    pub(crate) fn inner_mut(&mut self) -> &mut BufList {
+        &mut self.inner
+    }
+}
+
+impl io::Read for IntoReader {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.inner.read_consuming(buf))
+    }
+}
+
+impl io::BufRead for IntoReader {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.inner.chunk())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.inner.remaining());
+        self.inner.advance(amt);
+    }
+}
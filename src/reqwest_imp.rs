@@ -0,0 +1,15 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+
+/// Converts a list into a streaming `reqwest` body over its chunks, without copying them.
+///
+/// Since the length of the list is known up front, requests with the body are sent with a
+/// `content-length` header.
+impl From<BufList> for reqwest_012::Body {
+    #[inline]
+    fn from(buf_list: BufList) -> Self {
+        reqwest_012::Body::wrap(buf_list)
+    }
+}
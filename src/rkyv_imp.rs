@@ -0,0 +1,164 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::Bytes;
+use rkyv_08::{
+    munge::munge,
+    rancor::Fallible,
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Archived, Deserialize, Place, Portable, Serialize,
+};
+use std::{fmt, iter::FusedIterator};
+
+/// The archived form of a [`BufList`], created with the `rkyv` crate.
+///
+/// The bytes in the list are stored contiguously, along with the offsets at which each chunk
+/// ends. That makes it possible to read both the contents of the list and its chunks straight out
+/// of an archive, without copying or deserializing them.
+///
+/// Deserializing an `ArchivedBufList` produces a `BufList` with the same chunk boundaries as the
+/// list that was archived.
+///
+/// Requires the `rkyv08` feature.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::{ArchivedBufList, BufList};
+/// use rkyv_08::rancor::Error;
+///
+/// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+/// let bytes = rkyv_08::to_bytes::<Error>(&buf_list).unwrap();
+///
+/// let archived = rkyv_08::access::<ArchivedBufList, Error>(&bytes).unwrap();
+/// assert_eq!(archived.as_bytes(), b"hello world");
+/// assert_eq!(archived.num_chunks(), 2);
+/// assert_eq!(archived.get_chunk(1), Some(&b"world"[..]));
+///
+/// let deserialized = rkyv_08::deserialize::<BufList, Error>(archived).unwrap();
+/// assert_eq!(deserialized.num_chunks(), 2);
+/// ```
+#[derive(Portable, rkyv_08::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv_08::bytecheck)]
+#[rkyv(crate = rkyv_08)]
+#[repr(C)]
+pub struct ArchivedBufList {
+    data: ArchivedVec<u8>,
+    // The offset at which each chunk ends.
+    ends: ArchivedVec<Archived<u64>>,
+}
+
+impl ArchivedBufList {
+    /// Returns the bytes in the list, as a contiguous slice.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_slice()
+    }
+
+    /// Returns the total number of bytes in the list.
+    #[inline]
+    pub fn num_bytes(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the number of chunks in the list.
+    #[inline]
+    pub fn num_chunks(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns the chunk at the provided index, or `None` if the index is out of bounds.
+    ///
+    /// This also returns `None` if the archive's chunk offsets are inconsistent with its data,
+    /// which can only happen if the archive was tampered with.
+    pub fn get_chunk(&self, index: usize) -> Option<&[u8]> {
+        let ends = self.ends.as_slice();
+        let start = match index {
+            0 => 0,
+            _ => ends.get(index - 1)?.to_native() as usize,
+        };
+        let end = ends.get(index)?.to_native() as usize;
+        self.data.as_slice().get(start..end)
+    }
+
+    /// Iterates over the chunks in the list.
+    #[inline]
+    pub fn iter(&self) -> ArchivedIter<'_> {
+        ArchivedIter {
+            list: self,
+            index: 0,
+        }
+    }
+}
+
+impl fmt::Debug for ArchivedBufList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the chunks in an [`ArchivedBufList`].
+///
+/// Returned by [`ArchivedBufList::iter`].
+#[derive(Clone, Debug)]
+pub struct ArchivedIter<'a> {
+    list: &'a ArchivedBufList,
+    index: usize,
+}
+
+impl<'a> Iterator for ArchivedIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.list.get_chunk(self.index)?;
+        self.index += 1;
+        Some(chunk)
+    }
+}
+
+impl<'a> FusedIterator for ArchivedIter<'a> {}
+
+/// The resolver for an [`ArchivedBufList`].
+pub struct BufListResolver {
+    data: VecResolver,
+    ends: VecResolver,
+}
+
+impl Archive for BufList {
+    type Archived = ArchivedBufList;
+    type Resolver = BufListResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedBufList { data, ends } = out);
+        ArchivedVec::resolve_from_len(self.num_bytes(), resolver.data, data);
+        ArchivedVec::resolve_from_len(self.num_chunks(), resolver.ends, ends);
+    }
+}
+
+impl<S: Fallible + Allocator + Writer + ?Sized> Serialize<S> for BufList {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        // Bytes don't need to be aligned, so the chunks can be written out one after another.
+        let data = VecResolver::from_pos(serializer.pos());
+        let mut ends = Vec::with_capacity(self.num_chunks());
+        let mut end = 0_u64;
+        for chunk in self.iter() {
+            serializer.write(chunk)?;
+            end += chunk.len() as u64;
+            ends.push(end);
+        }
+        let ends = ArchivedVec::<Archived<u64>>::serialize_from_slice(&ends, serializer)?;
+        Ok(BufListResolver { data, ends })
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<BufList, D> for ArchivedBufList {
+    fn deserialize(&self, _deserializer: &mut D) -> Result<BufList, D::Error> {
+        let mut buf_list = BufList::with_capacity(self.num_chunks());
+        for chunk in self.iter() {
+            buf_list.push_chunk(Bytes::copy_from_slice(chunk));
+        }
+        Ok(buf_list)
+    }
+}
@@ -0,0 +1,346 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Searching for byte patterns within a `BufList`.
+
+use crate::{BufList, Iter};
+use std::iter::FusedIterator;
+
+impl BufList {
+    /// Returns the position of the first occurrence of `byte` in this list, or `None` if it doesn't
+    /// occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world\n"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(buf_list.find_byte(b'o'), Some(4));
+    /// assert_eq!(buf_list.find_byte(b'\n'), Some(10));
+    /// assert_eq!(buf_list.find_byte(b'x'), None);
+    /// ```
+    #[inline]
+    pub fn find_byte(&self, byte: u8) -> Option<u64> {
+        self.positions_of(byte).next()
+    }
+
+    /// Returns the position of the last occurrence of `byte` in this list, or `None` if it doesn't
+    /// occur.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world\n"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(buf_list.rfind_byte(b'o'), Some(6));
+    /// assert_eq!(buf_list.rfind_byte(b'x'), None);
+    /// ```
+    pub fn rfind_byte(&self, byte: u8) -> Option<u64> {
+        let mut chunk_end = self.total_len();
+        for chunk in self.iter().rev() {
+            let chunk_start = chunk_end - chunk.len() as u64;
+            if let Some(index) = memchr::memrchr(byte, chunk) {
+                return Some(chunk_start + index as u64);
+            }
+            chunk_end = chunk_start;
+        }
+        None
+    }
+
+    /// Returns an iterator over the positions of all occurrences of `byte` in this list, in
+    /// ascending order.
+    ///
+    /// Each chunk is scanned with `memchr`, so this is much faster than looking at one byte at a
+    /// time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"a\nb"[..], &b"\n\nc"[..]].into_iter().collect::<BufList>();
+    /// let newlines: Vec<u64> = buf_list.positions_of(b'\n').collect();
+    /// assert_eq!(newlines, vec![1, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn positions_of(&self, byte: u8) -> PositionsOf<'_> {
+        PositionsOf {
+            byte,
+            chunks: self.iter(),
+            current: &[],
+            current_pos: 0,
+            next_chunk_start: 0,
+        }
+    }
+
+    /// Returns true if this list starts with `prefix`, even if `prefix` spans several chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"\x1f"[..], &b"\x8b\x08"[..]].into_iter().collect::<BufList>();
+    /// assert!(buf_list.starts_with(b"\x1f\x8b"));
+    /// assert!(!buf_list.starts_with(b"PK"));
+    /// ```
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        let mut prefix = prefix;
+        for chunk in self.iter() {
+            if prefix.is_empty() {
+                break;
+            }
+            let n = chunk.len().min(prefix.len());
+            if chunk[..n] != prefix[..n] {
+                return false;
+            }
+            prefix = &prefix[n..];
+        }
+        prefix.is_empty()
+    }
+
+    /// Returns true if this list ends with `suffix`, even if `suffix` spans several chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"hello\r"[..], &b"\n"[..]].into_iter().collect::<BufList>();
+    /// assert!(buf_list.ends_with(b"\r\n"));
+    /// assert!(!buf_list.ends_with(b"\n\n"));
+    /// ```
+    pub fn ends_with(&self, suffix: &[u8]) -> bool {
+        let mut suffix = suffix;
+        for chunk in self.iter().rev() {
+            if suffix.is_empty() {
+                break;
+            }
+            let n = chunk.len().min(suffix.len());
+            if chunk[chunk.len() - n..] != suffix[suffix.len() - n..] {
+                return false;
+            }
+            suffix = &suffix[..suffix.len() - n];
+        }
+        suffix.is_empty()
+    }
+
+    /// Returns the position of the first occurrence of `needle` in this list, or `None` if it
+    /// doesn't occur.
+    ///
+    /// Matches that straddle chunk boundaries are found as well. An empty `needle` matches at
+    /// position 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"--boun"[..], &b"dary--bou"[..], &b"ndary"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>();
+    /// assert_eq!(buf_list.find(b"boundary"), Some(2));
+    /// assert_eq!(buf_list.find(b"xyz"), None);
+    /// ```
+    pub fn find(&self, needle: &[u8]) -> Option<u64> {
+        find_in_chunks(self.iter().map(|chunk| chunk.as_ref()), needle)
+    }
+
+    /// Returns the position of the last occurrence of `needle` in this list, or `None` if it
+    /// doesn't occur.
+    ///
+    /// Matches that straddle chunk boundaries are found as well. An empty `needle` matches at the
+    /// end of the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"--boun"[..], &b"dary--bou"[..], &b"ndary"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>();
+    /// assert_eq!(buf_list.rfind(b"boundary"), Some(12));
+    /// assert_eq!(buf_list.rfind(b"xyz"), None);
+    /// ```
+    pub fn rfind(&self, needle: &[u8]) -> Option<u64> {
+        let mut chunk_end = self.total_len();
+        if needle.is_empty() {
+            return Some(chunk_end);
+        }
+
+        // This mirrors `find`, except that chunks are visited back to front, and matches are
+        // found in the order of the chunk they start in.
+        //
+        // `carry` holds the first `needle.len() - 1` bytes after the current chunk, which is the
+        // longest suffix of a match that can start in an earlier chunk.
+        let overlap = needle.len() - 1;
+        let mut carry: Vec<u8> = Vec::with_capacity(overlap * 2);
+
+        for chunk in self.iter().rev() {
+            let chunk = chunk.as_ref();
+            let chunk_start = chunk_end - chunk.len() as u64;
+
+            // First, look for matches that start in this chunk and end in `carry`.
+            if !carry.is_empty() {
+                let head_len = overlap.min(chunk.len());
+                let mut window = Vec::with_capacity(head_len + carry.len());
+                window.extend_from_slice(&chunk[chunk.len() - head_len..]);
+                window.extend_from_slice(&carry);
+                if let Some(index) = rfind_in_slice(&window, needle) {
+                    if index < head_len {
+                        return Some(chunk_end - (head_len - index) as u64);
+                    }
+                }
+            }
+
+            // Then, look for matches entirely within this chunk.
+            if let Some(index) = rfind_in_slice(chunk, needle) {
+                return Some(chunk_start + index as u64);
+            }
+
+            // Finally, update `carry` for the previous chunk.
+            if chunk.len() >= overlap {
+                carry.clear();
+                carry.extend_from_slice(&chunk[..overlap]);
+            } else {
+                let keep = carry.len().min(overlap - chunk.len());
+                carry.truncate(keep);
+                carry.splice(..0, chunk.iter().copied());
+            }
+
+            chunk_end = chunk_start;
+        }
+
+        None
+    }
+}
+
+/// An iterator over the positions of a byte in a [`BufList`].
+///
+/// Returned by [`BufList::positions_of`].
+#[derive(Clone, Debug)]
+pub struct PositionsOf<'a> {
+    byte: u8,
+    chunks: Iter<'a>,
+    /// The unsearched part of the current chunk.
+    current: &'a [u8],
+    /// The position of the start of `current` within the list.
+    current_pos: u64,
+    /// The position of the start of the next chunk within the list.
+    next_chunk_start: u64,
+}
+
+impl<'a> Iterator for PositionsOf<'a> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(index) = memchr::memchr(self.byte, self.current) {
+                let pos = self.current_pos + index as u64;
+                self.current = &self.current[index + 1..];
+                self.current_pos = pos + 1;
+                return Some(pos);
+            }
+
+            let chunk = self.chunks.next()?;
+            self.current = chunk.as_ref();
+            self.current_pos = self.next_chunk_start;
+            self.next_chunk_start += chunk.len() as u64;
+        }
+    }
+}
+
+impl<'a> FusedIterator for PositionsOf<'a> {}
+
+/// Returns the position of the first occurrence of `needle` in the logical concatenation of
+/// `chunks`.
+pub(crate) fn find_in_chunks<'a>(
+    chunks: impl Iterator<Item = &'a [u8]>,
+    needle: &[u8],
+) -> Option<u64> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+
+    // Matches are found in the order of the chunk they end in. Since all matches have the same
+    // length, this is also the order of their start positions.
+    //
+    // `carry` holds the last `needle.len() - 1` bytes seen so far, which is the longest prefix of a
+    // match that can end in a later chunk.
+    let overlap = needle.len() - 1;
+    let mut carry: Vec<u8> = Vec::with_capacity(overlap * 2);
+    let mut chunk_start = 0u64;
+
+    for chunk in chunks {
+        // First, look for matches that start in `carry` and end in this chunk.
+        if !carry.is_empty() {
+            let carry_len = carry.len();
+            carry.extend_from_slice(&chunk[..overlap.min(chunk.len())]);
+            if let Some(index) = find_in_slice(&carry, needle) {
+                if index < carry_len {
+                    return Some(chunk_start - (carry_len - index) as u64);
+                }
+            }
+            carry.truncate(carry_len);
+        }
+
+        // Then, look for matches entirely within this chunk.
+        if let Some(index) = find_in_slice(chunk, needle) {
+            return Some(chunk_start + index as u64);
+        }
+
+        // Finally, update `carry` for the next chunk.
+        if chunk.len() >= overlap {
+            carry.clear();
+            carry.extend_from_slice(&chunk[chunk.len() - overlap..]);
+        } else {
+            carry.extend_from_slice(chunk);
+            let excess = carry.len().saturating_sub(overlap);
+            carry.drain(..excess);
+        }
+
+        chunk_start += chunk.len() as u64;
+    }
+
+    None
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+///
+/// `needle` must be non-empty.
+pub(crate) fn find_in_slice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let (first, rest) = needle.split_first().expect("needle is non-empty");
+    let mut start = 0;
+    while haystack.len() - start >= needle.len() {
+        let last_start = haystack.len() - needle.len();
+        let index = start + memchr::memchr(*first, &haystack[start..=last_start])?;
+        if &haystack[index + 1..index + needle.len()] == rest {
+            return Some(index);
+        }
+        start = index + 1;
+    }
+    None
+}
+
+/// Returns the index of the last occurrence of `needle` in `haystack`.
+///
+/// `needle` must be non-empty.
+pub(crate) fn rfind_in_slice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let (first, rest) = needle.split_first().expect("needle is non-empty");
+    if haystack.len() < needle.len() {
+        return None;
+    }
+    // One past the last index at which a match can start.
+    let mut end = haystack.len() - needle.len() + 1;
+    while end > 0 {
+        let index = memchr::memrchr(*first, &haystack[..end])?;
+        if &haystack[index + 1..index + needle.len()] == rest {
+            return Some(index);
+        }
+        end = index;
+    }
+    None
+}
@@ -0,0 +1,157 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::Bytes;
+use serde::{
+    de::{self, DeserializeSeed, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::fmt;
+
+/// Serializes the list as a contiguous sequence of bytes, using `serialize_bytes`.
+///
+/// Lists with more than one chunk are copied into a single buffer first. Human-readable formats
+/// like JSON represent the bytes as an array of numbers.
+impl Serialize for BufList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.num_chunks() {
+            0 => serializer.serialize_bytes(&[]),
+            1 => serializer.serialize_bytes(self.get_chunk(0).expect("1 chunk")),
+            _ => {
+                let mut bytes = Vec::with_capacity(self.num_bytes());
+                for chunk in self.iter() {
+                    bytes.extend_from_slice(chunk);
+                }
+                serializer.serialize_bytes(&bytes)
+            }
+        }
+    }
+}
+
+/// Deserializes a sequence of bytes into a list with a single chunk.
+///
+/// Besides bytes, this accepts strings and arrays of numbers, so data serialized by
+/// human-readable formats can be read back.
+impl<'de> Deserialize<'de> for BufList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(BufListVisitor { source: None })
+    }
+}
+
+/// Deserializes a [`BufList`] from a [`Bytes`] buffer without copying its data.
+///
+/// The [`Deserialize`] implementation for `BufList` has to copy data that's borrowed from the
+/// input. That copy can be avoided for formats like `bincode` and `postcard`, which borrow bytes
+/// directly from an input buffer. If that buffer is a `Bytes`, pass it to [`new`](Self::new): the
+/// deserialized list then shares its memory with the input buffer, like
+/// [`Bytes::slice_ref`] does. (Data that the format hands over as an owned `Vec<u8>` is never
+/// copied, whether or not this seed is used.)
+///
+/// Bytes that aren't borrowed from `source` are copied as usual.
+///
+/// Requires the `serde1` feature.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::BufListSeed;
+/// use bytes::Bytes;
+/// use serde::de::{value::BorrowedBytesDeserializer, value::Error, DeserializeSeed};
+///
+/// let input = Bytes::from_static(b"hello world");
+/// // A stand-in for a format like bincode, which borrows bytes from its input.
+/// let deserializer = BorrowedBytesDeserializer::<Error>::new(&input[6..]);
+///
+/// let buf_list = BufListSeed::new(input.clone()).deserialize(deserializer).unwrap();
+/// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"world"[..]);
+/// assert_eq!(buf_list.get_chunk(0).unwrap().as_ptr(), input[6..].as_ptr());
+/// ```
+#[derive(Clone, Debug)]
+pub struct BufListSeed {
+    source: Bytes,
+}
+
+impl BufListSeed {
+    /// Creates a new seed which avoids copying bytes borrowed from `source`.
+    #[inline]
+    pub fn new(source: Bytes) -> Self {
+        Self { source }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for BufListSeed {
+    type Value = BufList;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<BufList, D::Error> {
+        // Unlike deserialize_byte_buf, this asks formats for borrowed bytes where possible.
+        deserializer.deserialize_bytes(BufListVisitor {
+            source: Some(&self.source),
+        })
+    }
+}
+
+struct BufListVisitor<'a> {
+    // The buffer that borrowed bytes might point into.
+    source: Option<&'a Bytes>,
+}
+
+impl<'a> BufListVisitor<'a> {
+    fn single_chunk(chunk: Bytes) -> BufList {
+        let mut buf_list = BufList::new();
+        buf_list.push_chunk(chunk);
+        buf_list
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BufListVisitor<'a> {
+    type Value = BufList;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Self::single_chunk(Bytes::copy_from_slice(v)))
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        match self.source {
+            Some(source) if is_subslice(source, v) => Ok(Self::single_chunk(source.slice_ref(v))),
+            _ => self.visit_bytes(v),
+        }
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        // This doesn't copy the data.
+        Ok(Self::single_chunk(Bytes::from(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        self.visit_bytes(v.as_bytes())
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        self.visit_borrowed_bytes(v.as_bytes())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        self.visit_byte_buf(v.into_bytes())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        // Cap the preallocation, since the size hint can't be trusted.
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        self.visit_byte_buf(bytes)
+    }
+}
+
+/// Returns true if `subset` points into `source`, as required by `Bytes::slice_ref`.
+fn is_subslice(source: &Bytes, subset: &[u8]) -> bool {
+    let start = source.as_ptr() as usize;
+    let subset_start = subset.as_ptr() as usize;
+    subset_start >= start && subset_start + subset.len() <= start + source.len()
+}
@@ -0,0 +1,396 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Splitting a `BufList` into zero-copy segments.
+
+use crate::{errors::TruncatedFrameError, search::find_in_chunks, BufList};
+use bytes::Buf;
+use std::iter::FusedIterator;
+
+impl BufList {
+    /// Returns an iterator over the segments of this list separated by `delimiter`.
+    ///
+    /// Each segment is returned as a `BufList` whose chunks are slices of the chunks in this list,
+    /// so no payload bytes are copied. Delimiters that straddle chunk boundaries are recognized.
+    ///
+    /// This behaves like [`str::split`]: if the list starts or ends with `delimiter`, an empty
+    /// segment is returned first or last, and an empty list produces a single empty segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delimiter` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Buf;
+    ///
+    /// let buf_list = vec![&b"key=val"[..], &b"ue&k"[..], &b"ey2=value2"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>();
+    ///
+    /// let mut segments = buf_list.split(b"&");
+    /// let mut first = segments.next().unwrap();
+    /// // The first segment borrows from both of the first two chunks.
+    /// assert_eq!(first.num_chunks(), 2);
+    /// assert_eq!(first.copy_to_bytes(first.remaining()), &b"key=value"[..]);
+    ///
+    /// let second = segments.next().unwrap();
+    /// assert_eq!(second.num_bytes(), 11);
+    /// assert!(segments.next().is_none());
+    /// ```
+    pub fn split<'a>(&'a self, delimiter: &'a [u8]) -> Split<'a> {
+        assert!(!delimiter.is_empty(), "delimiter must not be empty");
+        Split {
+            list: self,
+            delimiter,
+            pos: ChunkPos::default(),
+            finished: false,
+        }
+    }
+
+    /// Returns an iterator over the lines in this list.
+    ///
+    /// This behaves like [`str::lines`]: lines are split at `\n`, and a `\r` right before the `\n`
+    /// is removed as well. The final line doesn't need to be terminated, and an empty final line is
+    /// not returned.
+    ///
+    /// As with [`split`](Self::split), each line is returned as a `BufList` of zero-copy slices of
+    /// this list's chunks. No UTF-8 validation is performed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Buf;
+    ///
+    /// let buf_list = vec![&b"GET / HTTP/1.1\r"[..], &b"\nHost: exa"[..], &b"mple.com\r\n"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>();
+    ///
+    /// let lines: Vec<_> = buf_list
+    ///     .lines()
+    ///     .map(|mut line| line.copy_to_bytes(line.remaining()))
+    ///     .collect();
+    /// assert_eq!(lines, vec!["GET / HTTP/1.1", "Host: example.com"]);
+    /// ```
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            split: self.split(b"\n"),
+        }
+    }
+
+    /// Returns an iterator over the length-prefixed frames in this list.
+    ///
+    /// Each frame consists of a length encoded as described by `prefix`, followed by that many
+    /// bytes of body. The body of each frame is returned as a `BufList` of zero-copy slices of this
+    /// list's chunks. Both the prefix and the body may straddle chunk boundaries.
+    ///
+    /// If the list ends partway through a frame, a [`TruncatedFrameError`] is returned, after which
+    /// the iterator is exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{BufList, LengthPrefix};
+    /// use bytes::Buf;
+    ///
+    /// let buf_list = vec![&b"\x00\x03a"[..], &b"bc\x00"[..], &b"\x02de\x00\x05f"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>();
+    ///
+    /// let mut frames = buf_list.frames(LengthPrefix::U16Be);
+    /// let mut frame = frames.next().unwrap().unwrap();
+    /// assert_eq!(frame.copy_to_bytes(frame.remaining()), &b"abc"[..]);
+    /// let mut frame = frames.next().unwrap().unwrap();
+    /// assert_eq!(frame.copy_to_bytes(frame.remaining()), &b"de"[..]);
+    ///
+    /// // The last frame claims to be 5 bytes long, but only 1 byte is available.
+    /// let err = frames.next().unwrap().unwrap_err();
+    /// assert_eq!(err.position(), 9);
+    /// assert_eq!(err.needed(), 7);
+    /// assert_eq!(err.available(), 3);
+    ///
+    /// assert!(frames.next().is_none());
+    /// ```
+    pub fn frames(&self, prefix: LengthPrefix) -> Frames<'_> {
+        Frames {
+            list: self,
+            prefix,
+            pos: ChunkPos::default(),
+            position: 0,
+            remaining: self.total_len(),
+            finished: false,
+        }
+    }
+}
+
+/// An iterator over the segments of a [`BufList`] separated by a delimiter.
+///
+/// Returned by [`BufList::split`].
+#[derive(Clone, Debug)]
+pub struct Split<'a> {
+    list: &'a BufList,
+    delimiter: &'a [u8],
+    pos: ChunkPos,
+    finished: bool,
+}
+
+impl<'a> Iterator for Split<'a> {
+    type Item = BufList;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match find_in_chunks(self.pos.remaining_slices(self.list), self.delimiter) {
+            Some(len) => {
+                let segment = self.pos.take(self.list, len);
+                self.pos.skip(self.list, self.delimiter.len() as u64);
+                Some(segment)
+            }
+            None => {
+                self.finished = true;
+                Some(self.pos.take(self.list, std::u64::MAX))
+            }
+        }
+    }
+}
+
+impl<'a> FusedIterator for Split<'a> {}
+
+/// An iterator over the lines in a [`BufList`].
+///
+/// Returned by [`BufList::lines`].
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+    split: Split<'a>,
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = BufList;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = self.split.next()?;
+        if self.split.finished {
+            // This is the final line, which isn't terminated by a newline. If it's empty, the list
+            // was either empty or ended with a newline.
+            if line.num_bytes() == 0 {
+                return None;
+            }
+        } else if line.ends_with(b"\r") {
+            line.truncate(line.num_bytes() - 1);
+        }
+        Some(line)
+    }
+}
+
+impl<'a> FusedIterator for Lines<'a> {}
+
+/// The encoding of the length prefix of frames returned by [`BufList::frames`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LengthPrefix {
+    /// A single byte.
+    U8,
+    /// A big-endian `u16`.
+    U16Be,
+    /// A little-endian `u16`.
+    U16Le,
+    /// A big-endian `u32`.
+    U32Be,
+    /// A little-endian `u32`.
+    U32Le,
+    /// A big-endian `u64`.
+    U64Be,
+    /// A little-endian `u64`.
+    U64Le,
+}
+
+impl LengthPrefix {
+    /// Returns the size of the length prefix in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            LengthPrefix::U8 => 1,
+            LengthPrefix::U16Be | LengthPrefix::U16Le => 2,
+            LengthPrefix::U32Be | LengthPrefix::U32Le => 4,
+            LengthPrefix::U64Be | LengthPrefix::U64Le => 8,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> u64 {
+        let mut buf = bytes;
+        match self {
+            LengthPrefix::U8 => buf.get_u8() as u64,
+            LengthPrefix::U16Be => buf.get_u16() as u64,
+            LengthPrefix::U16Le => buf.get_u16_le() as u64,
+            LengthPrefix::U32Be => buf.get_u32() as u64,
+            LengthPrefix::U32Le => buf.get_u32_le() as u64,
+            LengthPrefix::U64Be => buf.get_u64(),
+            LengthPrefix::U64Le => buf.get_u64_le(),
+        }
+    }
+}
+
+/// An iterator over the length-prefixed frames in a [`BufList`].
+///
+/// Returned by [`BufList::frames`].
+#[derive(Clone, Debug)]
+pub struct Frames<'a> {
+    list: &'a BufList,
+    prefix: LengthPrefix,
+    pos: ChunkPos,
+    /// The position corresponding to `pos`.
+    position: u64,
+    /// The number of bytes after `pos`.
+    remaining: u64,
+    finished: bool,
+}
+
+impl<'a> Frames<'a> {
+    fn truncated(&mut self, needed: u64) -> TruncatedFrameError {
+        self.finished = true;
+        TruncatedFrameError {
+            position: self.position,
+            needed,
+            available: self.remaining,
+        }
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Result<BufList, TruncatedFrameError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.remaining == 0 {
+            return None;
+        }
+
+        let prefix_size = self.prefix.size();
+        if self.remaining < prefix_size as u64 {
+            return Some(Err(self.truncated(prefix_size as u64)));
+        }
+        let mut prefix_buf = [0u8; 8];
+        self.pos
+            .copy_to_slice(self.list, &mut prefix_buf[..prefix_size]);
+        let len = self.prefix.decode(&prefix_buf[..prefix_size]);
+
+        let needed = (prefix_size as u64).saturating_add(len);
+        if self.remaining < needed {
+            return Some(Err(self.truncated(needed)));
+        }
+
+        self.pos.skip(self.list, prefix_size as u64);
+        let frame = self.pos.take(self.list, len);
+        self.position += needed;
+        self.remaining -= needed;
+        Some(Ok(frame))
+    }
+}
+
+impl<'a> FusedIterator for Frames<'a> {}
+
+/// A position within a `BufList`, expressed as a chunk index and an offset within that chunk.
+///
+/// Unlike a `Cursor`, this does not require the list's start position index, which makes it
+/// suitable for iterators that only ever move forward.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ChunkPos {
+    chunk: usize,
+    /// Invariant: if `chunk` is a valid index, this is strictly less than that chunk's length.
+    offset: usize,
+}
+
+impl ChunkPos {
+    /// Returns the position corresponding to `pos` bytes from the start of the list, looked up
+    /// through the list's start position index.
+    ///
+    /// If `pos` is at or past the end of the list, the position returned is the end of the list.
+    pub(crate) fn at(list: &BufList, pos: u64) -> Self {
+        let start_pos = list.get_start_pos();
+        let num_chunks = start_pos.len() - 1;
+        match start_pos.binary_search(&pos) {
+            // Chunks are never empty, so an exact match on the last entry (the length of the list)
+            // is the only way to get num_chunks here.
+            Ok(chunk) => Self { chunk, offset: 0 },
+            Err(chunk_plus_1) if chunk_plus_1 <= num_chunks => Self {
+                chunk: chunk_plus_1 - 1,
+                offset: (pos - start_pos[chunk_plus_1 - 1]) as usize,
+            },
+            Err(_) => Self {
+                chunk: num_chunks,
+                offset: 0,
+            },
+        }
+    }
+
+    /// Returns the index of the chunk this position is in, or the number of chunks if this position
+    /// is at the end of the list.
+    #[inline]
+    pub(crate) fn chunk(&self) -> usize {
+        self.chunk
+    }
+
+    /// Returns the data from this position to the end of the list, as a sequence of slices.
+    pub(crate) fn remaining_slices<'a>(
+        self,
+        list: &'a BufList,
+    ) -> impl Iterator<Item = &'a [u8]> + 'a {
+        let first = list
+            .get_chunk(self.chunk)
+            .map(move |chunk| &chunk.as_ref()[self.offset..]);
+        let rest = (self.chunk + 1..list.num_chunks())
+            .filter_map(move |index| list.get_chunk(index).map(|chunk| chunk.as_ref()));
+        first.into_iter().chain(rest)
+    }
+
+    /// Copies bytes starting at this position into `dest`, without moving forward.
+    ///
+    /// Returns the number of bytes copied, which is less than `dest.len()` only if the end of the
+    /// list was reached.
+    pub(crate) fn copy_to_slice(self, list: &BufList, dest: &mut [u8]) -> usize {
+        let mut copied = 0;
+        for slice in self.remaining_slices(list) {
+            if copied == dest.len() {
+                break;
+            }
+            let n = slice.len().min(dest.len() - copied);
+            dest[copied..copied + n].copy_from_slice(&slice[..n]);
+            copied += n;
+        }
+        copied
+    }
+
+    /// Moves forward by up to `len` bytes, returning the bytes moved over as zero-copy slices.
+    pub(crate) fn take(&mut self, list: &BufList, len: u64) -> BufList {
+        let mut out = BufList::new();
+        self.advance(list, len, Some(&mut out));
+        out
+    }
+
+    /// Moves forward by up to `len` bytes.
+    pub(crate) fn skip(&mut self, list: &BufList, len: u64) {
+        self.advance(list, len, None);
+    }
+
+    fn advance(&mut self, list: &BufList, mut len: u64, mut out: Option<&mut BufList>) {
+        while len > 0 {
+            let chunk = match list.get_chunk(self.chunk) {
+                Some(chunk) => chunk,
+                None => break,
+            };
+            let n = ((chunk.len() - self.offset) as u64).min(len) as usize;
+            if let Some(out) = out.as_mut() {
+                out.push_chunk(chunk.slice(self.offset..self.offset + n));
+            }
+
+            len -= n as u64;
+            self.offset += n;
+            if self.offset == chunk.len() {
+                self.chunk += 1;
+                self.offset = 0;
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Statistics about the chunks in a `BufList`.
+
+use crate::BufList;
+
+impl BufList {
+    /// Returns statistics about the sizes of the chunks in this list.
+    ///
+    /// This is useful for diagnosing fragmentation: for example, a list that was collected from a
+    /// stream of many tiny chunks may be worth copying into a smaller number of larger chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"a"[..], &b"bcd"[..], &b"efghijklm"[..]]
+    ///     .into_iter()
+    ///     .collect::<BufList>();
+    /// let stats = buf_list.chunk_stats();
+    /// assert_eq!(stats.num_chunks(), 3);
+    /// assert_eq!(stats.num_bytes(), 13);
+    /// assert_eq!(stats.min_chunk_size(), Some(1));
+    /// assert_eq!(stats.max_chunk_size(), Some(9));
+    /// assert_eq!(stats.mean_chunk_size(), Some(13.0 / 3.0));
+    ///
+    /// // Sizes 1, 2-3, 4-7 and 8-15 respectively.
+    /// assert_eq!(stats.histogram(), &[1, 1, 0, 1]);
+    /// ```
+    pub fn chunk_stats(&self) -> ChunkStats {
+        let mut stats = ChunkStats {
+            num_chunks: 0,
+            num_bytes: 0,
+            min_chunk_size: None,
+            max_chunk_size: None,
+            histogram: Vec::new(),
+        };
+
+        for chunk in self.iter() {
+            let len = chunk.len();
+            stats.num_chunks += 1;
+            stats.num_bytes += len as u64;
+            stats.min_chunk_size = Some(stats.min_chunk_size.map_or(len, |min| min.min(len)));
+            stats.max_chunk_size = Some(stats.max_chunk_size.map_or(len, |max| max.max(len)));
+
+            // Chunks are never empty, so this is always in the range [0, usize::BITS).
+            let bucket = (0usize.count_zeros() - 1 - len.leading_zeros()) as usize;
+            if stats.histogram.len() <= bucket {
+                stats.histogram.resize(bucket + 1, 0);
+            }
+            stats.histogram[bucket] += 1;
+        }
+
+        stats
+    }
+
+    /// Returns an estimate of the memory referenced by the chunks in this list.
+    ///
+    /// The chunks in a `BufList` are usually slices of larger buffers. The logical length of the
+    /// list can therefore differ from the amount of memory it keeps alive, in both directions:
+    ///
+    /// * Several chunks may be slices of the same buffer, or even clones of the same chunk.
+    /// * A small chunk may be a slice of a much larger buffer, keeping all of it alive.
+    ///
+    /// The memory regions referenced by the chunks are observable, so this method reports the
+    /// number and total size of distinct regions, merging chunks whose memory overlaps or is
+    /// adjacent. The capacity of the buffers backing each chunk is *not* observable through the
+    /// public API of [`Bytes`](bytes::Bytes), so the second case can't be detected directly.
+    /// However, a list with a large number of small, non-adjacent regions is a sign that it may be
+    /// holding on to more memory than it needs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Bytes;
+    ///
+    /// let data = Bytes::from(vec![0u8; 1024]);
+    /// let mut buf_list = BufList::new();
+    /// // Two adjacent slices of the same buffer...
+    /// buf_list.push_chunk(data.slice(0..256));
+    /// buf_list.push_chunk(data.slice(256..512));
+    /// // ...and the same slice twice.
+    /// buf_list.push_chunk(data.slice(768..1024));
+    /// buf_list.push_chunk(data.slice(768..1024));
+    ///
+    /// let usage = buf_list.memory_usage();
+    /// assert_eq!(usage.num_bytes(), 1024);
+    /// assert_eq!(usage.num_regions(), 2);
+    /// assert_eq!(usage.region_bytes(), 768);
+    /// ```
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let mut ranges: Vec<(usize, usize)> = self
+            .iter()
+            .map(|chunk| {
+                let start = chunk.as_ptr() as usize;
+                (start, start + chunk.len())
+            })
+            .collect();
+        ranges.sort_unstable();
+
+        let mut usage = MemoryUsage {
+            num_bytes: 0,
+            num_regions: 0,
+            region_bytes: 0,
+        };
+        let mut current: Option<(usize, usize)> = None;
+        for (start, end) in ranges {
+            usage.num_bytes += (end - start) as u64;
+            current = match current {
+                Some((cur_start, cur_end)) if start <= cur_end => {
+                    Some((cur_start, cur_end.max(end)))
+                }
+                Some((cur_start, cur_end)) => {
+                    usage.num_regions += 1;
+                    usage.region_bytes += (cur_end - cur_start) as u64;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((cur_start, cur_end)) = current {
+            usage.num_regions += 1;
+            usage.region_bytes += (cur_end - cur_start) as u64;
+        }
+
+        usage
+    }
+}
+
+/// Statistics about the sizes of the chunks in a [`BufList`].
+///
+/// Returned by [`BufList::chunk_stats`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChunkStats {
+    num_chunks: usize,
+    num_bytes: u64,
+    min_chunk_size: Option<usize>,
+    max_chunk_size: Option<usize>,
+    histogram: Vec<usize>,
+}
+
+impl ChunkStats {
+    /// Returns the number of chunks.
+    #[inline]
+    pub fn num_chunks(&self) -> usize {
+        self.num_chunks
+    }
+
+    /// Returns the total number of bytes across all chunks.
+    #[inline]
+    pub fn num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+
+    /// Returns the size of the smallest chunk, or `None` if there are no chunks.
+    #[inline]
+    pub fn min_chunk_size(&self) -> Option<usize> {
+        self.min_chunk_size
+    }
+
+    /// Returns the size of the largest chunk, or `None` if there are no chunks.
+    #[inline]
+    pub fn max_chunk_size(&self) -> Option<usize> {
+        self.max_chunk_size
+    }
+
+    /// Returns the mean chunk size, or `None` if there are no chunks.
+    #[inline]
+    pub fn mean_chunk_size(&self) -> Option<f64> {
+        if self.num_chunks == 0 {
+            None
+        } else {
+            Some(self.num_bytes as f64 / self.num_chunks as f64)
+        }
+    }
+
+    /// Returns a histogram of chunk sizes, bucketed by powers of two.
+    ///
+    /// The element at index `i` is the number of chunks whose size is in the range
+    /// `2^i..2^(i + 1)`. The histogram ends at the bucket for the largest chunk, so it is empty if
+    /// there are no chunks.
+    #[inline]
+    pub fn histogram(&self) -> &[usize] {
+        &self.histogram
+    }
+}
+
+/// An estimate of the memory referenced by the chunks in a [`BufList`].
+///
+/// Returned by [`BufList::memory_usage`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryUsage {
+    num_bytes: u64,
+    num_regions: usize,
+    region_bytes: u64,
+}
+
+impl MemoryUsage {
+    /// Returns the logical length of the list, i.e. the total number of bytes across all chunks.
+    #[inline]
+    pub fn num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+
+    /// Returns the number of distinct memory regions referenced by the list.
+    ///
+    /// Chunks whose memory overlaps or is adjacent are counted as a single region.
+    #[inline]
+    pub fn num_regions(&self) -> usize {
+        self.num_regions
+    }
+
+    /// Returns the total size of the distinct memory regions referenced by the list.
+    ///
+    /// This is smaller than [`num_bytes`](Self::num_bytes) if some chunks refer to the same memory.
+    #[inline]
+    pub fn region_bytes(&self) -> u64 {
+        self.region_bytes
+    }
+}
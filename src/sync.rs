@@ -0,0 +1,14 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Helpers for the state shared between the halves of pipes and broadcast lists.
+
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks `shared`, ignoring poisoning.
+///
+/// Callers complete every update to the shared state before doing anything that could panic, so
+/// it's never left inconsistent and a panic in another thread doesn't need to be propagated.
+pub(crate) fn lock<T>(shared: &Mutex<T>) -> MutexGuard<'_, T> {
+    shared.lock().unwrap_or_else(|error| error.into_inner())
+}
@@ -0,0 +1,100 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A `BufList` wrapper that keeps only the most recent bytes pushed into it.
+
+use crate::BufList;
+use bytes::Buf;
+
+/// A [`BufList`] that only keeps the last `max_bytes` bytes pushed into it.
+///
+/// Once more than `max_bytes` bytes have been pushed, the oldest data is dropped, slicing the
+/// boundary chunk as needed. This is useful for keeping the tail of a long-running stream in
+/// memory, such as the last few megabytes of a process's output.
+///
+/// Slicing is zero-copy, so a chunk that has been partly dropped still keeps its backing memory
+/// alive until the rest of it is dropped as well.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::TailBufList;
+/// use bytes::Buf;
+///
+/// let mut tail = TailBufList::new(8);
+/// tail.push_chunk(&b"hello "[..]);
+/// tail.push_chunk(&b"world"[..]);
+///
+/// assert_eq!(tail.dropped_bytes(), 3);
+/// let mut buf_list = tail.into_inner();
+/// assert_eq!(buf_list.copy_to_bytes(buf_list.remaining()), &b"lo world"[..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TailBufList {
+    inner: BufList,
+    max_bytes: usize,
+    dropped_bytes: u64,
+}
+
+impl TailBufList {
+    /// Creates a new, empty `TailBufList` that keeps at most `max_bytes` bytes.
+    #[inline]
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            inner: BufList::new(),
+            max_bytes,
+            dropped_bytes: 0,
+        }
+    }
+
+    /// Returns the maximum number of bytes kept by this list.
+    #[inline]
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the total number of bytes that have been dropped so far.
+    #[inline]
+    pub fn dropped_bytes(&self) -> u64 {
+        self.dropped_bytes
+    }
+
+    /// Adds a new chunk to the end of this list, dropping the oldest data if the list would
+    /// otherwise hold more than `max_bytes` bytes.
+    ///
+    /// If the provided [`Buf`] is zero-length, it will not be added to the list.
+    pub fn push_chunk<B: Buf>(&mut self, data: B) {
+        self.inner.push_chunk(data);
+        let excess = self.inner.num_bytes().saturating_sub(self.max_bytes);
+        if excess > 0 {
+            self.inner.advance(excess);
+            self.dropped_bytes += excess as u64;
+        }
+    }
+
+    /// Returns a reference to the underlying `BufList`.
+    #[inline]
+    pub fn get_ref(&self) -> &BufList {
+        &self.inner
+    }
+
+    /// Consumes this `TailBufList`, returning the underlying `BufList`.
+    #[inline]
+    pub fn into_inner(self) -> BufList {
+        self.inner
+    }
+}
+
+impl AsRef<BufList> for TailBufList {
+    fn as_ref(&self) -> &BufList {
+        &self.inner
+    }
+}
+
+impl<B: Buf> Extend<B> for TailBufList {
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        for buf in iter.into_iter() {
+            self.push_chunk(buf);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{errors::LimitExceededError, BufList, IntoTryStream};
+use bytes::{Buf, Bytes, BytesMut};
+use std::io;
+use tokio_util_07::{codec::Decoder, io::StreamReader};
+
+impl BufList {
+    /// Converts this list into a `tokio-util` [`StreamReader`] over its chunks.
+    ///
+    /// This is useful to pass a list into APIs that are generic over `StreamReader`, or that
+    /// otherwise take one. To read a list through `tokio`'s `AsyncRead` without depending on
+    /// `tokio-util`, use [`into_reader`](Self::into_reader) instead.
+    ///
+    /// Requires the `tokio-util07` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use tokio::io::AsyncBufReadExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let buf_list: BufList = vec![&b"hello\nwor"[..], &b"ld\n"[..]].into_iter().collect();
+    /// let mut lines = buf_list.into_stream_reader().lines();
+    ///
+    /// assert_eq!(lines.next_line().await.unwrap().unwrap(), "hello");
+    /// assert_eq!(lines.next_line().await.unwrap().unwrap(), "world");
+    /// assert_eq!(lines.next_line().await.unwrap(), None);
+    /// # }
+    /// ```
+    pub fn into_stream_reader(self) -> StreamReader<IntoTryStream<Bytes, io::Error>, Bytes> {
+        StreamReader::new(self.into_try_stream())
+    }
+}
+
+/// The size of the length prefix read by [`LengthDelimitedDecoder`].
+const HEADER_LEN: usize = 4;
+
+/// The default maximum frame length, which is the same as `tokio_util`'s.
+const DEFAULT_MAX_FRAME_LENGTH: usize = 8 * 1024 * 1024;
+
+/// A [`Decoder`] for frames prefixed by their length, which produces each frame as a [`BufList`].
+///
+/// Each frame is a 4-byte big-endian length, followed by that many bytes of data. This is the
+/// same format as `tokio_util`'s `LengthDelimitedCodec` with its default settings, and the length
+/// prefix isn't included in the decoded frames.
+///
+/// With `LengthDelimitedCodec`, a frame is only decoded once it's entirely in the read buffer, so
+/// the read buffer has to grow to the size of the largest frame, and may copy the data it holds as
+/// it does so. This decoder instead splits data off the read buffer as it arrives, and adds it to a
+/// [`BufList`] without copying it. Large frames are assembled in place, and the read buffer stays
+/// small.
+///
+/// Requires the `tokio-util07` feature.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::LengthDelimitedDecoder;
+/// use futures::TryStreamExt;
+/// use tokio_util_07::codec::FramedRead;
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let data = &b"\x00\x00\x00\x05hello\x00\x00\x00\x05world"[..];
+/// let frames: Vec<_> = FramedRead::new(data, LengthDelimitedDecoder::new())
+///     .try_collect()
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(frames.len(), 2);
+/// assert_eq!(frames[0].to_str_lossy(), "hello");
+/// assert_eq!(frames[1].to_str_lossy(), "world");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LengthDelimitedDecoder {
+    max_frame_length: usize,
+    // The frame currently being decoded, once its length prefix has been read.
+    partial: Option<PartialFrame>,
+}
+
+#[derive(Debug)]
+struct PartialFrame {
+    remaining: usize,
+    frame: BufList,
+}
+
+impl LengthDelimitedDecoder {
+    /// Creates a new decoder with the default maximum frame length of 8 MiB.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            max_frame_length: DEFAULT_MAX_FRAME_LENGTH,
+            partial: None,
+        }
+    }
+
+    /// Sets the maximum length of a frame, not including its length prefix.
+    ///
+    /// Decoding a frame that's longer than this fails with an [`io::ErrorKind::InvalidData`] error,
+    /// which wraps a [`LimitExceededError`].
+    #[inline]
+    pub fn with_max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length;
+        self
+    }
+
+    /// Returns the maximum length of a frame, not including its length prefix.
+    #[inline]
+    pub fn max_frame_length(&self) -> usize {
+        self.max_frame_length
+    }
+}
+
+impl Default for LengthDelimitedDecoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for LengthDelimitedDecoder {
+    type Item = BufList;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BufList>> {
+        if self.partial.is_none() {
+            if src.len() < HEADER_LEN {
+                return Ok(None);
+            }
+            let len = src.get_u32() as usize;
+            if len > self.max_frame_length {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    LimitExceededError {
+                        limit: self.max_frame_length as u64,
+                        attempted: len as u64,
+                    },
+                ));
+            }
+            self.partial = Some(PartialFrame {
+                remaining: len,
+                frame: BufList::new(),
+            });
+        }
+
+        let partial = self.partial.as_mut().expect("set above");
+        // Move whatever part of the frame has arrived into the list. This doesn't copy anything,
+        // since the split-off part shares the read buffer's allocation.
+        let n = partial.remaining.min(src.len());
+        if n > 0 {
+            partial.frame.push_chunk(src.split_to(n).freeze());
+            partial.remaining -= n;
+        }
+        if partial.remaining > 0 {
+            return Ok(None);
+        }
+        Ok(self.partial.take().map(|partial| partial.frame))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<BufList>> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if self.partial.is_none() && src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "stream ended partway through a frame",
+            )),
+        }
+    }
+}
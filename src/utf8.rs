@@ -0,0 +1,324 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! UTF-8 validation and decoding across chunk boundaries.
+
+use crate::{errors::Utf8Error, BufList, Iter};
+use bytes::Bytes;
+use std::{borrow::Cow, iter::FusedIterator, iter::Map, str};
+
+impl BufList {
+    /// Returns true if the bytes in this list are valid UTF-8.
+    ///
+    /// Code points that straddle chunk boundaries are validated correctly, without flattening the
+    /// list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// // "é" is encoded as [0xc3, 0xa9], and is split across two chunks here.
+    /// let buf_list = vec![&b"caf\xc3"[..], &b"\xa9"[..]].into_iter().collect::<BufList>();
+    /// assert!(buf_list.is_utf8());
+    ///
+    /// let buf_list = vec![&b"caf\xc3"[..], &b"!"[..]].into_iter().collect::<BufList>();
+    /// assert!(!buf_list.is_utf8());
+    /// ```
+    pub fn is_utf8(&self) -> bool {
+        Utf8Pieces::new(self.iter().map(|chunk| chunk.as_ref())).all(|piece| match piece {
+            Utf8Piece::Invalid { .. } => false,
+            Utf8Piece::Str(_) | Utf8Piece::Char(_) => true,
+        })
+    }
+
+    /// Converts the bytes in this list to a string, replacing invalid UTF-8 sequences with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// This produces the same result as [`String::from_utf8_lossy`] on the flattened bytes. If the
+    /// list has at most one chunk and it is valid UTF-8, the result borrows from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"caf\xc3"[..], &b"\xa9 \xff"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(buf_list.to_str_lossy(), "café \u{fffd}");
+    /// ```
+    pub fn to_str_lossy(&self) -> Cow<'_, str> {
+        match self.num_chunks() {
+            0 => Cow::Borrowed(""),
+            1 => String::from_utf8_lossy(self.get_chunk(0).expect("1 chunk")),
+            _ => {
+                let mut out = String::with_capacity(self.num_bytes());
+                for piece in Utf8Pieces::new(self.iter().map(|chunk| chunk.as_ref())) {
+                    match piece {
+                        Utf8Piece::Str(s) => out.push_str(s),
+                        Utf8Piece::Char(c) => out.push(c),
+                        Utf8Piece::Invalid { .. } => out.push('\u{fffd}'),
+                    }
+                }
+                Cow::Owned(out)
+            }
+        }
+    }
+
+    /// Copies the bytes in this list into a `String`, returning an error if they aren't valid
+    /// UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"caf\xc3"[..], &b"\xa9"[..]].into_iter().collect::<BufList>();
+    /// assert_eq!(buf_list.try_to_string().unwrap(), "café");
+    ///
+    /// let buf_list = vec![&b"caf\xc3"[..], &b"!"[..]].into_iter().collect::<BufList>();
+    /// let err = buf_list.try_to_string().unwrap_err();
+    /// assert_eq!(err.valid_up_to(), 3);
+    /// assert_eq!(err.error_len(), Some(1));
+    /// ```
+    pub fn try_to_string(&self) -> Result<String, Utf8Error> {
+        let mut out = String::with_capacity(self.num_bytes());
+        for piece in Utf8Pieces::new(self.iter().map(|chunk| chunk.as_ref())) {
+            match piece {
+                Utf8Piece::Str(s) => out.push_str(s),
+                Utf8Piece::Char(c) => out.push(c),
+                Utf8Piece::Invalid { len, truncated } => {
+                    return Err(Utf8Error {
+                        valid_up_to: out.len() as u64,
+                        error_len: if truncated { None } else { Some(len) },
+                    });
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Returns an iterator over the characters in this list, decoded as UTF-8.
+    ///
+    /// Code points that straddle chunk boundaries are stitched together. Each invalid sequence is
+    /// reported as an error, after which decoding continues with the next byte. The invalid
+    /// sequences are the same ones that [`String::from_utf8_lossy`] would replace with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list = vec![&b"a\xc3"[..], &b"\xa9\xffb"[..]].into_iter().collect::<BufList>();
+    /// let mut chars = buf_list.chars();
+    /// assert_eq!(chars.next(), Some(Ok('a')));
+    /// assert_eq!(chars.next(), Some(Ok('é')));
+    ///
+    /// let err = chars.next().unwrap().unwrap_err();
+    /// assert_eq!(err.valid_up_to(), 3);
+    /// assert_eq!(err.error_len(), Some(1));
+    ///
+    /// assert_eq!(chars.next(), Some(Ok('b')));
+    /// assert_eq!(chars.next(), None);
+    /// ```
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            pieces: Utf8Pieces::new(self.iter().map(bytes_as_slice as fn(&Bytes) -> &[u8])),
+            current: "".chars(),
+            position: 0,
+        }
+    }
+}
+
+/// The chunks of a `BufList` as slices, in a form that can be named in a struct.
+type ChunkSlices<'a> = Map<Iter<'a>, fn(&Bytes) -> &[u8]>;
+
+fn bytes_as_slice(bytes: &Bytes) -> &[u8] {
+    bytes.as_ref()
+}
+
+/// An iterator over the characters in a [`BufList`].
+///
+/// Returned by [`BufList::chars`].
+#[derive(Clone, Debug)]
+pub struct Chars<'a> {
+    pieces: Utf8Pieces<'a, ChunkSlices<'a>>,
+    current: str::Chars<'a>,
+    position: u64,
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = Result<char, Utf8Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = match self.current.next() {
+            Some(c) => c,
+            None => match self.pieces.next()? {
+                Utf8Piece::Str(s) => {
+                    self.current = s.chars();
+                    self.current.next().expect("pieces are non-empty")
+                }
+                Utf8Piece::Char(c) => c,
+                Utf8Piece::Invalid { len, truncated } => {
+                    let err = Utf8Error {
+                        valid_up_to: self.position,
+                        error_len: if truncated { None } else { Some(len) },
+                    };
+                    self.position += len as u64;
+                    return Some(Err(err));
+                }
+            },
+        };
+        self.position += c.len_utf8() as u64;
+        Some(Ok(c))
+    }
+}
+
+impl<'a> FusedIterator for Chars<'a> {}
+
+/// A piece of decoded UTF-8, produced by [`Utf8Pieces`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Utf8Piece<'a> {
+    /// A valid string borrowed from a single chunk.
+    Str(&'a str),
+    /// A code point that straddled a chunk boundary.
+    Char(char),
+    /// An invalid sequence of `len` bytes. This is the same unit that [`String::from_utf8_lossy`]
+    /// replaces with a single `U+FFFD`.
+    ///
+    /// `truncated` is true if the sequence is the incomplete start of a code point at the end of
+    /// the input.
+    Invalid { len: usize, truncated: bool },
+}
+
+/// Decodes a sequence of chunks as UTF-8, stitching together code points that straddle chunk
+/// boundaries.
+#[derive(Clone, Debug)]
+pub(crate) struct Utf8Pieces<'a, I> {
+    chunks: I,
+    /// The undecoded part of the current chunk.
+    rest: &'a [u8],
+    /// The incomplete start of a code point from a previous chunk.
+    carry: [u8; 4],
+    carry_len: usize,
+}
+
+impl<'a, I> Utf8Pieces<'a, I>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    pub(crate) fn new(chunks: I) -> Self {
+        Self {
+            chunks,
+            rest: &[],
+            carry: [0; 4],
+            carry_len: 0,
+        }
+    }
+
+    /// Tries to complete the code point in `carry` with bytes from `rest`. Returns `None` if more
+    /// bytes are needed.
+    fn complete_carry(&mut self) -> Option<Utf8Piece<'a>> {
+        // carry[0] is always a valid leading byte, so it determines the width of the code point.
+        let width = match self.carry[0] {
+            0x00..=0xdf => 2,
+            0xe0..=0xef => 3,
+            _ => 4,
+        };
+        let n = (width - self.carry_len).min(self.rest.len());
+        self.carry[self.carry_len..self.carry_len + n].copy_from_slice(&self.rest[..n]);
+
+        match str::from_utf8(&self.carry[..self.carry_len + n]) {
+            Ok(s) => {
+                let c = s.chars().next().expect("s is non-empty");
+                self.rest = &self.rest[n..];
+                self.carry_len = 0;
+                Some(Utf8Piece::Char(c))
+            }
+            Err(error) => match error.error_len() {
+                Some(len) => {
+                    // The carried bytes are a valid prefix of a code point, so the invalid
+                    // sequence includes all of them.
+                    self.rest = &self.rest[len - self.carry_len..];
+                    self.carry_len = 0;
+                    Some(Utf8Piece::Invalid {
+                        len,
+                        truncated: false,
+                    })
+                }
+                None => {
+                    // Still incomplete: `rest` has been used up.
+                    self.rest = &self.rest[n..];
+                    self.carry_len += n;
+                    None
+                }
+            },
+        }
+    }
+}
+
+impl<'a, I> Iterator for Utf8Pieces<'a, I>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    type Item = Utf8Piece<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if !self.rest.is_empty() {
+                if self.carry_len > 0 {
+                    match self.complete_carry() {
+                        Some(piece) => return Some(piece),
+                        None => continue,
+                    }
+                }
+
+                match str::from_utf8(self.rest) {
+                    Ok(s) => {
+                        self.rest = &[];
+                        return Some(Utf8Piece::Str(s));
+                    }
+                    Err(error) => {
+                        let valid_up_to = error.valid_up_to();
+                        if valid_up_to > 0 {
+                            let (valid, rest) = self.rest.split_at(valid_up_to);
+                            self.rest = rest;
+                            let s = str::from_utf8(valid).expect("prefix was validated");
+                            return Some(Utf8Piece::Str(s));
+                        }
+                        match error.error_len() {
+                            Some(len) => {
+                                self.rest = &self.rest[len..];
+                                return Some(Utf8Piece::Invalid {
+                                    len,
+                                    truncated: false,
+                                });
+                            }
+                            None => {
+                                // The chunk ends partway through a code point.
+                                self.carry[..self.rest.len()].copy_from_slice(self.rest);
+                                self.carry_len = self.rest.len();
+                                self.rest = &[];
+                            }
+                        }
+                    }
+                }
+            }
+
+            match self.chunks.next() {
+                Some(chunk) => self.rest = chunk,
+                None => {
+                    if self.carry_len > 0 {
+                        let len = self.carry_len;
+                        self.carry_len = 0;
+                        return Some(Utf8Piece::Invalid {
+                            len,
+                            truncated: true,
+                        });
+                    }
+                    return None;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::io::IoSlice;
+
+impl BufList {
+    /// Returns a [`Buf`] over the data in this list, which doesn't consume the list.
+    ///
+    /// `BufList` implements `Buf` itself, but advancing it drops the chunks that are read. The
+    /// view returned by this method tracks its own position instead, so the same list can be handed
+    /// to several `Buf` consumers in turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Buf;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    ///
+    /// let mut view = buf_list.as_buf();
+    /// assert_eq!(view.get_u32(), u32::from_be_bytes(*b"hell"));
+    /// assert_eq!(view.remaining(), 6);
+    ///
+    /// // The list itself is unchanged, and can be viewed again.
+    /// assert_eq!(buf_list.num_bytes(), 10);
+    /// assert_eq!(buf_list.as_buf().chunk(), &b"hello"[..]);
+    /// ```
+    #[inline]
+    pub fn as_buf(&self) -> BufListView<'_> {
+        BufListView {
+            list: self,
+            chunk: 0,
+            offset: 0,
+            remaining: self.total_len(),
+        }
+    }
+}
+
+/// A non-consuming [`Buf`] over a [`BufList`].
+///
+/// Returned by [`BufList::as_buf`].
+#[derive(Clone, Debug)]
+pub struct BufListView<'a> {
+    list: &'a BufList,
+    /// The index of the current chunk.
+    chunk: usize,
+    /// Invariant: if `chunk` is a valid index, this is strictly less than that chunk's length.
+    offset: usize,
+    remaining: u64,
+}
+
+impl<'a> BufListView<'a> {
+    /// Returns the list this view is over.
+    #[inline]
+    pub fn get_ref(&self) -> &'a BufList {
+        self.list
+    }
+
+    /// Returns the current position of this view within the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    /// use bytes::Buf;
+    ///
+    /// let buf_list = vec![&b"hello"[..], &b"world"[..]].into_iter().collect::<BufList>();
+    /// let mut view = buf_list.as_buf();
+    /// view.advance(7);
+    /// assert_eq!(view.position(), 7);
+    /// ```
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.list.total_len() - self.remaining
+    }
+
+    #[inline]
+    fn current(&self) -> Option<&'a Bytes> {
+        self.list.get_chunk(self.chunk)
+    }
+}
+
+impl<'a> Buf for BufListView<'a> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        // Saturate rather than overflow on 32-bit targets, like `bytes::buf::Chain` does.
+        if self.remaining > std::usize::MAX as u64 {
+            std::usize::MAX
+        } else {
+            self.remaining as usize
+        }
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        match self.current() {
+            Some(chunk) => &chunk[self.offset..],
+            None => &[],
+        }
+    }
+
+    fn chunks_vectored<'iovs>(&'iovs self, iovs: &mut [IoSlice<'iovs>]) -> usize {
+        if iovs.is_empty() {
+            return 0;
+        }
+
+        let mut filled = 0;
+        for (index, chunk) in self.list.iter().enumerate().skip(self.chunk) {
+            let offset = if index == self.chunk { self.offset } else { 0 };
+            iovs[filled] = IoSlice::new(&chunk[offset..]);
+            filled += 1;
+            if filled == iovs.len() {
+                break;
+            }
+        }
+        filled
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        assert!(
+            cnt as u64 <= self.remaining,
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.remaining,
+        );
+        self.remaining -= cnt as u64;
+
+        while cnt > 0 {
+            let chunk_len = self.current().expect("cnt <= remaining").len();
+            let n = (chunk_len - self.offset).min(cnt);
+            cnt -= n;
+            self.offset += n;
+            if self.offset == chunk_len {
+                self.chunk += 1;
+                self.offset = 0;
+            }
+        }
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        // If the requested bytes are all within the current chunk, this is just a reference count
+        // bump.
+        if let Some(chunk) = self.current() {
+            if len <= chunk.len() - self.offset {
+                let bytes = chunk.slice(self.offset..self.offset + len);
+                self.advance(len);
+                return bytes;
+            }
+        }
+
+        assert!(
+            len as u64 <= self.remaining,
+            "`len` ({}) greater than remaining ({})",
+            len,
+            self.remaining
+        );
+        let mut buf = BytesMut::with_capacity(len);
+        buf.put(self.take(len));
+        buf.freeze()
+    }
+}
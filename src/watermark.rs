@@ -0,0 +1,198 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A `BufList` wrapper that applies backpressure based on its size.
+
+use crate::BufList;
+use bytes::{Buf, Bytes};
+use std::{fmt, io::IoSlice};
+
+/// A watermark crossed by a [`WatermarkBufList`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Watermark {
+    /// The number of bytes in the list rose to the high watermark or above.
+    ///
+    /// Producers should typically stop adding data to the list at this point.
+    High,
+
+    /// After crossing the high watermark, the number of bytes in the list fell to the low
+    /// watermark or below.
+    ///
+    /// Producers can typically resume adding data to the list at this point.
+    Low,
+}
+
+/// A [`BufList`] that tracks low and high watermarks on its size, for applying backpressure to
+/// producers.
+///
+/// Data is added to the list with [`push_chunk`](Self::push_chunk) and consumed through the
+/// [`Buf`] implementation. Once the number of bytes in the list reaches the high watermark, the
+/// list is considered [full](Self::is_full) until consumers drain it down to the low watermark.
+/// Each transition invokes an optional callback with the [`Watermark`] that was crossed.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::{Watermark, WatermarkBufList};
+/// use bytes::Buf;
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let events = Rc::new(RefCell::new(Vec::new()));
+/// let events2 = events.clone();
+/// let mut buf_list = WatermarkBufList::with_callback(4, 8, move |watermark| {
+///     events2.borrow_mut().push(watermark);
+/// });
+///
+/// buf_list.push_chunk(&b"hello"[..]);
+/// assert!(!buf_list.is_full());
+/// buf_list.push_chunk(&b"world"[..]);
+/// assert!(buf_list.is_full());
+///
+/// // Draining to 5 bytes isn't enough to cross the low watermark...
+/// buf_list.advance(5);
+/// assert!(buf_list.is_full());
+/// // ...but draining to 4 bytes is.
+/// buf_list.advance(1);
+/// assert!(!buf_list.is_full());
+///
+/// assert_eq!(*events.borrow(), vec![Watermark::High, Watermark::Low]);
+/// ```
+pub struct WatermarkBufList<F = fn(Watermark)> {
+    inner: BufList,
+    low: usize,
+    high: usize,
+    full: bool,
+    callback: F,
+}
+
+impl WatermarkBufList {
+    /// Creates a new, empty `WatermarkBufList` with the given watermarks and no callback.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` isn't less than `high`.
+    pub fn new(low: usize, high: usize) -> Self {
+        fn noop(_: Watermark) {}
+        Self::with_callback(low, high, noop)
+    }
+}
+
+impl<F: FnMut(Watermark)> WatermarkBufList<F> {
+    /// Creates a new, empty `WatermarkBufList` with the given watermarks, calling `callback` each
+    /// time a watermark is crossed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low` isn't less than `high`.
+    pub fn with_callback(low: usize, high: usize, callback: F) -> Self {
+        assert!(
+            low < high,
+            "low watermark ({}) must be less than high watermark ({})",
+            low,
+            high
+        );
+        Self {
+            inner: BufList::new(),
+            low,
+            high,
+            full: false,
+            callback,
+        }
+    }
+
+    /// Returns the low watermark.
+    #[inline]
+    pub fn low_watermark(&self) -> usize {
+        self.low
+    }
+
+    /// Returns the high watermark.
+    #[inline]
+    pub fn high_watermark(&self) -> usize {
+        self.high
+    }
+
+    /// Returns true if the list has reached the high watermark, and hasn't been drained to the low
+    /// watermark since.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.full
+    }
+
+    /// Adds a new chunk to this list, invoking the callback if the high watermark is reached.
+    ///
+    /// The chunk is always added, even if the list is already full.
+    pub fn push_chunk<B: Buf>(&mut self, data: B) -> Bytes {
+        let bytes = self.inner.push_chunk(data);
+        self.update();
+        bytes
+    }
+
+    /// Returns a reference to the underlying `BufList`.
+    #[inline]
+    pub fn get_ref(&self) -> &BufList {
+        &self.inner
+    }
+
+    /// Consumes this `WatermarkBufList`, returning the underlying `BufList`.
+    #[inline]
+    pub fn into_inner(self) -> BufList {
+        self.inner
+    }
+
+    fn update(&mut self) {
+        let num_bytes = self.inner.num_bytes();
+        if !self.full && num_bytes >= self.high {
+            self.full = true;
+            (self.callback)(Watermark::High);
+        } else if self.full && num_bytes <= self.low {
+            self.full = false;
+            (self.callback)(Watermark::Low);
+        }
+    }
+}
+
+impl<F> AsRef<BufList> for WatermarkBufList<F> {
+    fn as_ref(&self) -> &BufList {
+        &self.inner
+    }
+}
+
+impl<F> fmt::Debug for WatermarkBufList<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatermarkBufList")
+            .field("inner", &self.inner)
+            .field("low", &self.low)
+            .field("high", &self.high)
+            .field("full", &self.full)
+            .finish()
+    }
+}
+
+impl<F: FnMut(Watermark)> Buf for WatermarkBufList<F> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.inner.chunk()
+    }
+
+    #[inline]
+    fn chunks_vectored<'iovs>(&'iovs self, iovs: &mut [IoSlice<'iovs>]) -> usize {
+        self.inner.chunks_vectored(iovs)
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        self.inner.advance(cnt);
+        self.update();
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        let bytes = self.inner.copy_to_bytes(len);
+        self.update();
+        bytes
+    }
+}
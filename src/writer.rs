@@ -0,0 +1,503 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{errors::LimitExceededError, BufList, ChunkPool};
+use bytes::{buf::UninitSlice, Buf, BufMut, BytesMut};
+use std::io::{self, IoSlice};
+
+/// The default size of the blocks that a [`Writer`] allocates.
+pub(crate) const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A writer that builds up a [`BufList`], coalescing writes into chunks of a target size.
+///
+/// `Writer` implements [`BufMut`] and [`io::Write`], so encoders, serializers and formatters can
+/// write directly into a segmented buffer. Data is written into blocks of a target chunk size (8
+/// KiB by default). Once a block is full, it's frozen and added to the list without copying, and a
+/// new block is allocated -- earlier data is never moved. This means that lots of tiny writes
+/// don't turn into lots of tiny chunks.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::Writer;
+/// use bytes::{Buf, BufMut};
+///
+/// let mut writer = Writer::new();
+/// writer.put_u32(0xdead_beef);
+/// writer.put_slice(b"hello");
+/// assert_eq!(writer.total_len(), 9);
+///
+/// let mut buf_list = writer.into_inner();
+/// assert_eq!(buf_list.get_u32(), 0xdead_beef);
+/// assert_eq!(buf_list.chunk(), &b"hello"[..]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Writer {
+    /// The blocks that have been filled up or flushed.
+    list: BufList,
+    /// The block currently being written to, which isn't part of `list` yet.
+    block: BytesMut,
+    min_chunk_size: usize,
+    chunk_size: usize,
+    max_bytes: Option<u64>,
+    pool: Option<ChunkPool>,
+}
+
+impl Writer {
+    /// Creates a new, empty `Writer` with the default target chunk size of 8 KiB.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Creates a new, empty `Writer` which allocates blocks of `chunk_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::Writer;
+    /// use std::io::Write;
+    ///
+    /// let mut writer = Writer::with_chunk_size(4);
+    /// for _ in 0..5 {
+    ///     writer.write_all(b"ab").unwrap();
+    /// }
+    ///
+    /// let buf_list = writer.into_inner();
+    /// assert_eq!(buf_list.num_chunks(), 3);
+    /// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"abab"[..]);
+    /// ```
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self::with_chunk_sizes(0, chunk_size)
+    }
+
+    /// Creates a new, empty `Writer` which allocates blocks of `chunk_size` bytes, and which
+    /// coalesces chunks smaller than `min_chunk_size` passed into
+    /// [`push_chunk`](Self::push_chunk).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0, or if `min_chunk_size` is greater than `chunk_size`.
+    pub fn with_chunk_sizes(min_chunk_size: usize, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk_size must be greater than 0");
+        assert!(
+            min_chunk_size <= chunk_size,
+            "min_chunk_size ({}) must not be greater than chunk_size ({})",
+            min_chunk_size,
+            chunk_size
+        );
+        Self {
+            list: BufList::new(),
+            block: BytesMut::new(),
+            min_chunk_size,
+            chunk_size,
+            max_bytes: None,
+            pool: None,
+        }
+    }
+
+    /// Creates a new, empty `Writer` which draws its blocks from `pool`.
+    ///
+    /// The target chunk size is the pool's [`block_size`](ChunkPool::block_size).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::{ChunkPool, Writer};
+    /// use std::io::Write;
+    ///
+    /// let pool = ChunkPool::new(1024, 16);
+    /// let mut writer = Writer::with_pool(pool.clone());
+    /// writer.write_all(b"hello").unwrap();
+    /// let buf_list = writer.into_inner();
+    ///
+    /// // Once buf_list is dropped, its memory can be reused by other writers using the pool.
+    /// drop(buf_list);
+    /// ```
+    pub fn with_pool(pool: ChunkPool) -> Self {
+        let mut writer = Self::with_chunk_size(pool.block_size());
+        writer.pool = Some(pool);
+        writer
+    }
+
+    /// Applies the settings from a [`BufListBuilder`](crate::BufListBuilder).
+    pub(crate) fn configure(
+        &mut self,
+        list: BufList,
+        max_bytes: Option<u64>,
+        pool: Option<ChunkPool>,
+    ) {
+        self.list = list;
+        self.max_bytes = max_bytes;
+        self.pool = pool;
+    }
+
+    /// Returns the minimum size of chunks passed into [`push_chunk`](Self::push_chunk) that are
+    /// added to the list as is. Smaller chunks are copied into the current block.
+    #[inline]
+    pub fn min_chunk_size(&self) -> usize {
+        self.min_chunk_size
+    }
+
+    /// Returns the target chunk size for this `Writer`.
+    #[inline]
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the maximum total number of bytes that can be written, if a limit was set through
+    /// [`BufListBuilder::max_bytes`](crate::BufListBuilder::max_bytes).
+    ///
+    /// The limit is enforced by every method that adds data:
+    ///
+    /// * [`try_push_chunk`](Self::try_push_chunk) returns a [`LimitExceededError`].
+    /// * [`io::Write::write`] writes as many bytes as fit, and returns an error if none of them
+    ///   fit.
+    /// * [`push_chunk`](Self::push_chunk) and the [`BufMut`] methods panic, and
+    ///   [`BufMut::remaining_mut`] reports the number of bytes left before the limit.
+    #[inline]
+    pub fn max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    /// Adds a chunk of data to this writer.
+    ///
+    /// Chunks at least [`min_chunk_size`](Self::min_chunk_size) bytes long are added to the list
+    /// without copying them (if they're [`Bytes`](bytes::Bytes)). Smaller chunks are copied into
+    /// the current block, so that a stream of tiny chunks doesn't turn into a list of tiny chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::Writer;
+    /// use bytes::Bytes;
+    ///
+    /// let mut writer = Writer::with_chunk_sizes(4, 16);
+    /// writer.push_chunk(&b"ab"[..]);
+    /// writer.push_chunk(&b"cd"[..]);
+    /// // This chunk is large enough to be added to the list as is.
+    /// let large = Bytes::from_static(b"efghijkl");
+    /// writer.push_chunk(large.clone());
+    ///
+    /// let buf_list = writer.into_inner();
+    /// assert_eq!(buf_list.num_chunks(), 2);
+    /// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"abcd"[..]);
+    /// assert_eq!(buf_list.get_chunk(1).unwrap().as_ptr(), large.as_ptr());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if adding the chunk would exceed [`max_bytes`](Self::max_bytes).
+    pub fn push_chunk<B: Buf>(&mut self, data: B) {
+        if let Err(error) = self.try_push_chunk(data) {
+            panic!("{}", error);
+        }
+    }
+
+    /// Adds a chunk of data to this writer, unless doing so would exceed
+    /// [`max_bytes`](Self::max_bytes).
+    ///
+    /// If the limit would be exceeded, nothing is written and an error is returned.
+    pub fn try_push_chunk<B: Buf>(&mut self, mut data: B) -> Result<(), LimitExceededError> {
+        let len = data.remaining();
+        self.check_limit(len)?;
+        if len == 0 {
+            return Ok(());
+        }
+        if len >= self.min_chunk_size {
+            // Preserve ordering by moving the current block's data into the list first.
+            self.freeze_block();
+            self.list.push_chunk(data);
+        } else {
+            while data.has_remaining() {
+                let chunk = data.chunk();
+                let n = chunk.len();
+                self.put_slice(chunk);
+                data.advance(n);
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends a borrowed byte slice to this writer.
+    ///
+    /// The data is copied into the current block, which is only frozen and added to the list once
+    /// it fills up (or the writer is flushed or consumed). Unlike
+    /// [`BufList::push_chunk`](crate::BufList::push_chunk), repeatedly appending small slices
+    /// doesn't allocate once per call.
+    ///
+    /// This is the same as [`BufMut::put_slice`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if appending the data would exceed [`max_bytes`](Self::max_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::Writer;
+    ///
+    /// let mut writer = Writer::new();
+    /// for word in ["hello", " ", "world"].iter() {
+    ///     writer.extend_from_slice(word.as_bytes());
+    /// }
+    ///
+    /// let buf_list = writer.into_inner();
+    /// assert_eq!(buf_list.num_chunks(), 1);
+    /// assert_eq!(buf_list.get_chunk(0).unwrap(), &b"hello world"[..]);
+    /// ```
+    #[inline]
+    pub fn extend_from_slice(&mut self, data: &[u8]) {
+        self.put_slice(data);
+    }
+
+    /// Reserves space for at least `additional` more bytes to be written without allocating.
+    ///
+    /// If the current block doesn't have enough spare capacity, it's moved into the list and a new
+    /// block of at least `additional` bytes is allocated. This means that the data written next
+    /// may end up in a chunk larger than [`chunk_size`](Self::chunk_size).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::Writer;
+    /// use std::io::Write;
+    ///
+    /// let mut writer = Writer::with_chunk_size(4);
+    /// writer.reserve(12);
+    /// writer.write_all(b"hello world!").unwrap();
+    ///
+    /// let buf_list = writer.into_inner();
+    /// assert_eq!(buf_list.num_chunks(), 1);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        if self.block.capacity() - self.block.len() >= additional {
+            return;
+        }
+        self.freeze_block();
+        self.block = if additional <= self.chunk_size {
+            self.new_block()
+        } else {
+            BytesMut::with_capacity(additional)
+        };
+    }
+
+    /// Returns a reference to the list of chunks that have been filled up or flushed so far.
+    ///
+    /// Data in the block currently being written to is not part of this list until the block is
+    /// full, or until [`flush`](io::Write::flush) is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::Writer;
+    /// use std::io::Write;
+    ///
+    /// let mut writer = Writer::new();
+    /// writer.write_all(b"hello").unwrap();
+    /// assert_eq!(writer.get_ref().num_bytes(), 0);
+    ///
+    /// writer.flush().unwrap();
+    /// assert_eq!(writer.get_ref().num_bytes(), 5);
+    /// ```
+    #[inline]
+    pub fn get_ref(&self) -> &BufList {
+        &self.list
+    }
+
+    /// Returns the total number of bytes written so far.
+    #[inline]
+    pub fn total_len(&self) -> u64 {
+        self.list.total_len() + self.block.len() as u64
+    }
+
+    /// Consumes this `Writer`, returning the `BufList` containing everything written to it.
+    pub fn into_inner(mut self) -> BufList {
+        self.freeze_block();
+        self.list
+    }
+
+    /// Returns the number of bytes that can be written before reaching `max_bytes`.
+    fn remaining_limit(&self) -> u64 {
+        match self.max_bytes {
+            Some(max_bytes) => max_bytes.saturating_sub(self.total_len()),
+            None => std::u64::MAX,
+        }
+    }
+
+    /// Returns an error if writing `len` more bytes would exceed `max_bytes`.
+    fn check_limit(&self, len: usize) -> Result<(), LimitExceededError> {
+        match self.max_bytes {
+            Some(max_bytes) if len as u64 > self.remaining_limit() => Err(LimitExceededError {
+                limit: max_bytes,
+                attempted: self.total_len() + len as u64,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Moves the data written to the current block into the list.
+    fn freeze_block(&mut self) {
+        self.list.push_chunk(self.block.split().freeze());
+    }
+
+    /// Ensures that the current block has spare capacity, allocating a new block if necessary.
+    fn ensure_capacity(&mut self) {
+        if self.block.capacity() == self.block.len() {
+            self.freeze_block();
+            self.block = self.new_block();
+        }
+    }
+
+    /// Allocates a new block of `chunk_size` bytes, drawing it from the pool if there is one.
+    fn new_block(&self) -> BytesMut {
+        match &self.pool {
+            Some(pool) => pool.get(),
+            None => BytesMut::with_capacity(self.chunk_size),
+        }
+    }
+}
+
+// BufMut is an unsafe trait, and advance_mut is an unsafe method, so implementing it requires
+// unsafe code. All the unsafety is delegated to the current block.
+#[allow(unsafe_code)]
+unsafe impl BufMut for Writer {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        let remaining = std::usize::MAX - self.list.num_bytes().saturating_add(self.block.len());
+        self.remaining_limit().min(remaining as u64) as usize
+    }
+
+    #[inline]
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        // SAFETY: the caller guarantees that the first `cnt` bytes of the slice returned by
+        // `chunk_mut` have been initialized, and that slice came from the current block.
+        self.block.advance_mut(cnt);
+    }
+
+    #[inline]
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let remaining = self.remaining_mut();
+        if remaining > 0 {
+            self.ensure_capacity();
+        }
+        // The block has spare capacity, so this doesn't reallocate.
+        let chunk = self.block.chunk_mut();
+        let len = chunk.len().min(remaining);
+        &mut chunk[..len]
+    }
+
+    fn put_slice(&mut self, mut src: &[u8]) {
+        if let Err(error) = self.check_limit(src.len()) {
+            panic!("{}", error);
+        }
+        while !src.is_empty() {
+            self.ensure_capacity();
+            let n = (self.block.capacity() - self.block.len()).min(src.len());
+            self.block.extend_from_slice(&src[..n]);
+            src = &src[n..];
+        }
+    }
+}
+
+impl Default for Writer {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Buf> Extend<B> for Writer {
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        for buf in iter.into_iter() {
+            self.push_chunk(buf);
+        }
+    }
+}
+
+/// Writes data into the current block, allocating new blocks as needed.
+///
+/// Flushing moves the data in the current block into the list, where it becomes visible through
+/// [`get_ref`](Writer::get_ref). Later writes continue to use the rest of the block's capacity.
+impl io::Write for Writer {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Err(error) = self.check_limit(buf.len()) {
+            if self.remaining_limit() == 0 {
+                return Err(io::Error::new(io::ErrorKind::Other, error));
+            }
+        }
+        let n = (buf.len() as u64).min(self.remaining_limit()) as usize;
+        self.put_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let mut len = 0;
+        for buf in bufs {
+            if buf.is_empty() {
+                continue;
+            }
+            match self.write(buf) {
+                Ok(n) => {
+                    len += n;
+                    if n < buf.len() {
+                        break;
+                    }
+                }
+                Err(error) if len == 0 => return Err(error),
+                Err(_) => break,
+            }
+        }
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.freeze_block();
+        Ok(())
+    }
+}
+
+/// Appends written data to the end of the list.
+///
+/// Writes go through [`BufList::extend_from_slice`], so lots of small writes, such as the pieces
+/// produced by `write!`, are copied into a tail buffer rather than each becoming a chunk of its
+/// own.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::BufList;
+/// use std::io::Write;
+///
+/// let mut buf_list = BufList::new();
+/// write!(buf_list, "hello {}", 42).unwrap();
+/// assert_eq!(buf_list.to_str_lossy(), "hello 42");
+/// assert_eq!(buf_list.num_chunks(), 1);
+/// ```
+impl io::Write for BufList {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let mut len = 0;
+        for buf in bufs {
+            self.extend_from_slice(buf);
+            len += buf.len();
+        }
+        Ok(len)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
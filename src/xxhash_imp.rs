@@ -0,0 +1,46 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufList;
+use xxhash_rust_08::xxh3::Xxh3;
+
+impl BufList {
+    /// Computes a fast, non-cryptographic 64-bit hash of the bytes in this list, using the XXH3
+    /// algorithm from the `xxhash-rust` crate.
+    ///
+    /// The hash is computed one chunk at a time, and is the same regardless of how the list is
+    /// split into chunks. It's equal to the result of `xxh3_64` over the flattened bytes.
+    ///
+    /// This hash is suitable for deduplication and cache keys, but not for any purpose where an
+    /// adversary might try to produce collisions. For that, use a cryptographic hash (for example,
+    /// through the `digest010` feature) instead.
+    ///
+    /// Requires the `xxhash08` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let a: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let b: BufList = vec![&b"hello world"[..]].into_iter().collect();
+    /// assert_eq!(a.fast_hash(), b.fast_hash());
+    /// ```
+    pub fn fast_hash(&self) -> u64 {
+        self.fast_hash_with_seed(0)
+    }
+
+    /// Computes a fast, non-cryptographic 64-bit hash of the bytes in this list with the given
+    /// seed, using the XXH3 algorithm from the `xxhash-rust` crate.
+    ///
+    /// `fast_hash_with_seed(0)` is the same as [`Self::fast_hash`].
+    ///
+    /// Requires the `xxhash08` feature.
+    pub fn fast_hash_with_seed(&self, seed: u64) -> u64 {
+        let mut hasher = Xxh3::with_seed(seed);
+        for chunk in self.iter() {
+            hasher.update(chunk);
+        }
+        hasher.digest()
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::BufListMut;
+use bytes::{Buf, BytesMut};
+use std::{fmt, io::IoSlice};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A list of chunks holding sensitive data, which is zeroed out once it's no longer needed.
+///
+/// Unlike [`Bytes`](bytes::Bytes), which may share its memory with other handles, every chunk in a
+/// `ZeroizingBufList` is solely owned by the list. That makes it possible to reliably zero out:
+///
+/// * data consumed through the [`Buf`] implementation, as it's consumed.
+/// * all the data remaining in the list, when it's dropped or [zeroized](Zeroize::zeroize).
+///
+/// Data added through [`push_chunk`](Self::push_chunk) is copied into the list, so callers
+/// remain responsible for zeroing out their own copies.
+///
+/// Requires the `zeroize1` feature.
+///
+/// # Examples
+///
+/// ```
+/// use buf_list::ZeroizingBufList;
+/// use bytes::Buf;
+///
+/// let mut buf_list = ZeroizingBufList::new();
+/// buf_list.push_chunk(&b"secret "[..]);
+/// buf_list.push_chunk(&b"token"[..]);
+///
+/// // The consumed bytes are zeroed out as they're copied.
+/// let mut prefix = [0; 7];
+/// buf_list.copy_to_slice(&mut prefix);
+/// assert_eq!(&prefix, b"secret ");
+///
+/// // The rest are zeroed out when the list is dropped.
+/// assert_eq!(buf_list.chunk(), b"token");
+/// ```
+#[derive(Default)]
+pub struct ZeroizingBufList {
+    inner: BufListMut,
+}
+
+impl ZeroizingBufList {
+    /// Creates a new, empty list.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies a chunk of data into a new chunk at the end of this list.
+    ///
+    /// If the provided [`Buf`] is zero-length, it will not be added to the list.
+    pub fn push_chunk<B: Buf>(&mut self, mut data: B) {
+        let mut chunk = BytesMut::with_capacity(data.remaining());
+        while data.has_remaining() {
+            let n = data.chunk().len();
+            chunk.extend_from_slice(data.chunk());
+            data.advance(n);
+        }
+        self.inner.push_segment(chunk);
+    }
+
+    /// Returns a reference to the underlying list.
+    #[inline]
+    pub fn get_ref(&self) -> &BufListMut {
+        &self.inner
+    }
+}
+
+impl Zeroize for ZeroizingBufList {
+    /// Zeroes out all the data in this list, and then clears it.
+    fn zeroize(&mut self) {
+        for chunk in self.inner.iter_mut() {
+            chunk.zeroize();
+        }
+        self.inner = BufListMut::new();
+    }
+}
+
+impl Drop for ZeroizingBufList {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ZeroizingBufList {}
+
+impl fmt::Debug for ZeroizingBufList {
+    /// Prints the size of the list, but not its contents.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZeroizingBufList")
+            .field("num_chunks", &self.inner.num_chunks())
+            .field("total_len", &self.inner.total_len())
+            .finish()
+    }
+}
+
+impl Buf for ZeroizingBufList {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self.inner.chunk()
+    }
+
+    #[inline]
+    fn chunks_vectored<'iovs>(&'iovs self, iovs: &mut [IoSlice<'iovs>]) -> usize {
+        self.inner.chunks_vectored(iovs)
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining(),
+            "cannot advance past `remaining`: {:?} <= {:?}",
+            cnt,
+            self.remaining(),
+        );
+
+        // Zero out the consumed data before the chunks holding it are dropped.
+        let mut left = cnt;
+        for chunk in self.inner.iter_mut() {
+            if left == 0 {
+                break;
+            }
+            let n = left.min(chunk.len());
+            chunk[..n].zeroize();
+            left -= n;
+        }
+        self.inner.advance(cnt);
+    }
+}
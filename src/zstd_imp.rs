@@ -0,0 +1,96 @@
+// Copyright (c) The buf-list Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compression::{decode_to_end, encode_chunks},
+    BufList, Writer,
+};
+use std::io;
+use zstd_013::stream::{read::Decoder, write::Encoder};
+
+impl BufList {
+    /// Compresses the bytes in this list as a zstd frame, using the provided compression level.
+    ///
+    /// The list is compressed [chunk by chunk](crate#encoding-and-compression). The frame header
+    /// records the length of the list, so decompressors can size their output up front.
+    ///
+    /// A level of 0 selects zstd's default level, currently 3.
+    ///
+    /// Requires the `zstd013` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if zstd fails to set up a compression context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use buf_list::BufList;
+    ///
+    /// let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    /// let compressed = buf_list.compress_zstd(0).unwrap();
+    ///
+    /// let decompressed = compressed.decompress_zstd().unwrap();
+    /// assert_eq!(decompressed.to_str_lossy(), "hello world");
+    /// ```
+    pub fn compress_zstd(&self, level: i32) -> io::Result<BufList> {
+        compress_zstd_with(self, Encoder::new(Writer::new(), level)?)
+    }
+
+    /// Compresses the bytes in this list as a zstd frame, using the provided compression level
+    /// and dictionary.
+    ///
+    /// This behaves like [`compress_zstd`](Self::compress_zstd), except that the output can only
+    /// be decompressed with the same dictionary, for example through
+    /// [`decompress_zstd_with_dictionary`](Self::decompress_zstd_with_dictionary).
+    ///
+    /// Requires the `zstd013` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if zstd fails to set up a compression context or to load the dictionary.
+    pub fn compress_zstd_with_dictionary(
+        &self,
+        level: i32,
+        dictionary: &[u8],
+    ) -> io::Result<BufList> {
+        compress_zstd_with(
+            self,
+            Encoder::with_dictionary(Writer::new(), level, dictionary)?,
+        )
+    }
+
+    /// Decompresses the bytes in this list as a sequence of zstd frames.
+    ///
+    /// The list is decompressed [chunk by chunk](crate#encoding-and-compression).
+    ///
+    /// Requires the `zstd013` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the list isn't valid zstd data, or if it ends in the middle of a frame.
+    pub fn decompress_zstd(&self) -> io::Result<BufList> {
+        decode_to_end(self, Decoder::with_buffer)
+    }
+
+    /// Decompresses the bytes in this list as a sequence of zstd frames, using the provided
+    /// dictionary.
+    ///
+    /// This behaves like [`decompress_zstd`](Self::decompress_zstd), except that the frames
+    /// must have been compressed with the same dictionary.
+    ///
+    /// Requires the `zstd013` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if zstd fails to load the dictionary, if the list isn't valid zstd data
+    /// or if it ends in the middle of a frame.
+    pub fn decompress_zstd_with_dictionary(&self, dictionary: &[u8]) -> io::Result<BufList> {
+        decode_to_end(self, |reader| Decoder::with_dictionary(reader, dictionary))
+    }
+}
+
+fn compress_zstd_with(buf_list: &BufList, mut encoder: Encoder<'_, Writer>) -> io::Result<BufList> {
+    encoder.set_pledged_src_size(Some(buf_list.total_len()))?;
+    Ok(encode_chunks(buf_list, encoder)?.finish()?.into_inner())
+}
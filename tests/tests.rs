@@ -1,8 +1,11 @@
 // Copyright (c) The buf-list Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use buf_list::BufList;
-use bytes::{Buf, Bytes};
+use buf_list::{
+    BufList, BufListMut, LengthPrefix, SegmentedBuf, TailBufList, Watermark, WatermarkBufList,
+    Writer,
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use std::{io::IoSlice, ops::Deref};
 
 #[test]
@@ -11,6 +14,7 @@ fn test_basic() {
         .into_iter()
         .collect::<BufList>();
     println!("{:?}", buf_list);
+    assert!(format!("{:?}", buf_list).starts_with("SegmentedBuf { bufs: "));
     assert_eq!(buf_list.num_bytes(), 10);
     assert_eq!(buf_list.num_chunks(), 2);
 
@@ -186,3 +190,3141 @@ fn test_copy_to_bytes_panic() {
         .collect::<BufList>();
     buf_list.copy_to_bytes(12);
 }
+
+#[test]
+fn test_find() {
+    let buf_list = vec![&b"abc"[..], &b"d"[..], &b"ea"[..], &b"bcdef"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    let flattened = b"abcdeabcdef";
+
+    for needle in &[
+        &b"a"[..],
+        &b"cde"[..],
+        &b"bcdea"[..],
+        &b"abcdeabcdef"[..],
+        &b"def"[..],
+        &b"abx"[..],
+        &b"abcdeabcdefg"[..],
+    ] {
+        let expected_find = flattened
+            .windows(needle.len())
+            .position(|window| window == *needle)
+            .map(|index| index as u64);
+        let expected_rfind = flattened
+            .windows(needle.len())
+            .rposition(|window| window == *needle)
+            .map(|index| index as u64);
+        assert_eq!(buf_list.find(needle), expected_find, "find {:?}", needle);
+        assert_eq!(buf_list.rfind(needle), expected_rfind, "rfind {:?}", needle);
+    }
+
+    assert_eq!(buf_list.find(b""), Some(0));
+    assert_eq!(buf_list.rfind(b""), Some(11));
+    assert_eq!(BufList::new().find(b"a"), None);
+    assert_eq!(BufList::new().rfind(b"a"), None);
+}
+
+#[test]
+fn test_positions_of() {
+    let buf_list = vec![&b"a\n"[..], &b"bc"[..], &b"\n\n"[..], &b"d"[..]]
+        .into_iter()
+        .collect::<BufList>();
+
+    let positions: Vec<_> = buf_list.positions_of(b'\n').collect();
+    assert_eq!(positions, vec![1, 4, 5]);
+    assert_eq!(buf_list.find_byte(b'\n'), Some(1));
+    assert_eq!(buf_list.rfind_byte(b'\n'), Some(5));
+    assert_eq!(buf_list.find_byte(b'd'), Some(6));
+    assert_eq!(buf_list.rfind_byte(b'a'), Some(0));
+    assert_eq!(buf_list.find_byte(b'x'), None);
+    assert_eq!(buf_list.rfind_byte(b'x'), None);
+    assert_eq!(buf_list.positions_of(b'x').count(), 0);
+}
+
+#[test]
+fn test_starts_ends_with() {
+    let buf_list = vec![&b"ab"[..], &b"c"[..], &b"def"[..]]
+        .into_iter()
+        .collect::<BufList>();
+
+    for prefix in &[
+        &b""[..],
+        &b"a"[..],
+        &b"abc"[..],
+        &b"abcd"[..],
+        &b"abcdef"[..],
+    ] {
+        assert!(buf_list.starts_with(prefix), "starts with {:?}", prefix);
+    }
+    for prefix in &[&b"b"[..], &b"abd"[..], &b"abcdefg"[..]] {
+        assert!(
+            !buf_list.starts_with(prefix),
+            "doesn't start with {:?}",
+            prefix
+        );
+    }
+
+    for suffix in &[
+        &b""[..],
+        &b"f"[..],
+        &b"cdef"[..],
+        &b"bcdef"[..],
+        &b"abcdef"[..],
+    ] {
+        assert!(buf_list.ends_with(suffix), "ends with {:?}", suffix);
+    }
+    for suffix in &[&b"e"[..], &b"bdef"[..], &b"0abcdef"[..]] {
+        assert!(!buf_list.ends_with(suffix), "doesn't end with {:?}", suffix);
+    }
+
+    assert!(BufList::new().starts_with(b""));
+    assert!(!BufList::new().ends_with(b"a"));
+}
+
+#[test]
+fn test_common_prefix_len() {
+    let a = vec![&b"abc"[..], &b"de"[..], &b"f"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    let b = vec![&b"a"[..], &b"bcdx"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    let c = vec![&b"abcd"[..], &b"ef"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    let d = BufList::from(&b"abcdefgh"[..]);
+
+    assert_eq!(a.common_prefix_len(&b), 4);
+    assert_eq!(a.first_mismatch(&b), Some(4));
+    assert_eq!(a.common_prefix_len(&c), 6);
+    assert_eq!(a.first_mismatch(&c), None);
+    assert_eq!(a.common_prefix_len(&d), 6);
+    assert_eq!(a.first_mismatch(&d), Some(6));
+    assert_eq!(d.first_mismatch(&a), Some(6));
+    assert_eq!(a.first_mismatch(&BufList::new()), Some(0));
+    assert_eq!(BufList::new().first_mismatch(&BufList::new()), None);
+}
+
+#[test]
+fn test_split() {
+    let buf_list = vec![&b"a,"[..], &b"bc"[..], &b","[..], &b",d"[..], &b"e,"[..]]
+        .into_iter()
+        .collect::<BufList>();
+
+    let segments: Vec<_> = buf_list
+        .split(b",")
+        .map(|mut segment| segment.copy_to_bytes(segment.remaining()))
+        .collect();
+    assert_eq!(segments, vec!["a", "bc", "", "de", ""]);
+
+    // Multi-byte delimiters can span chunks.
+    let buf_list = vec![&b"one-"[..], &b"-two-"[..], &b"-"[..], &b"-three"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    let segments: Vec<_> = buf_list
+        .split(b"--")
+        .map(|mut segment| segment.copy_to_bytes(segment.remaining()))
+        .collect();
+    assert_eq!(segments, vec!["one", "two", "-three"]);
+
+    // Segments are zero-copy slices of the original chunks.
+    let first = buf_list.split(b"--").next().unwrap();
+    assert_eq!(
+        first.get_chunk(0).unwrap().as_ptr(),
+        buf_list.get_chunk(0).unwrap().as_ptr()
+    );
+
+    let segments: Vec<_> = BufList::new().split(b",").collect();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].num_bytes(), 0);
+}
+
+#[test]
+fn test_lines() {
+    let buf_list = vec![&b"a\r"[..], &b"\nb\n"[..], &b"\r\n\n"[..], &b"c\r"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    let lines: Vec<_> = buf_list
+        .lines()
+        .map(|mut line| line.copy_to_bytes(line.remaining()))
+        .collect();
+    assert_eq!(lines, vec!["a", "b", "", "", "c\r"]);
+
+    let buf_list = BufList::from(&b"a\n\n"[..]);
+    assert_eq!(buf_list.lines().count(), 2);
+    assert_eq!(BufList::new().lines().count(), 0);
+}
+
+#[test]
+fn test_frames() {
+    let buf_list = vec![
+        &b"\x02\x00"[..],
+        &b"\x00\x00ab\x00"[..],
+        &b"\x00\x00\x00\x01\x00\x00\x00c"[..],
+    ]
+    .into_iter()
+    .collect::<BufList>();
+
+    let frames: Vec<_> = buf_list
+        .frames(LengthPrefix::U32Le)
+        .map(|frame| {
+            let mut frame = frame.expect("frame is complete");
+            frame.copy_to_bytes(frame.remaining())
+        })
+        .collect();
+    assert_eq!(frames, vec![&b"ab"[..], &b""[..], &b"c"[..]]);
+
+    // The same data read as big-endian frames claims a frame of length 0x02000000.
+    let mut frames = buf_list.frames(LengthPrefix::U32Be);
+    let err = frames.next().unwrap().unwrap_err();
+    assert_eq!(err.position(), 0);
+    assert_eq!(err.needed(), 4 + 0x0200_0000);
+    assert_eq!(err.available(), 15);
+    assert!(frames.next().is_none());
+
+    // A zero-length frame at the end of the list is complete.
+    let buf_list = BufList::from(&b"\x01a\x00"[..]);
+    let mut frames = buf_list.frames(LengthPrefix::U8);
+    assert_eq!(frames.next().unwrap().unwrap().num_bytes(), 1);
+    assert_eq!(frames.next().unwrap().unwrap().num_bytes(), 0);
+    assert!(frames.next().is_none());
+
+    // Truncation in the length prefix: the second prefix has only 1 of its 2 bytes.
+    let buf_list = BufList::from(&b"\x00\x00\x00"[..]);
+    let err = buf_list
+        .frames(LengthPrefix::U16Le)
+        .nth(1)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err.position(), 2);
+    assert_eq!(err.needed(), 2);
+    assert_eq!(err.available(), 1);
+}
+
+#[test]
+fn test_utf8() {
+    // "€" is [0xe2, 0x82, 0xac].
+    let buf_list = vec![&b"a\xe2"[..], &b"\x82"[..], &b"\xacb"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    assert!(buf_list.is_utf8());
+    assert_eq!(buf_list.to_str_lossy(), "a€b");
+    assert_eq!(buf_list.try_to_string().unwrap(), "a€b");
+
+    // An invalid continuation in the next chunk.
+    let buf_list = vec![&b"a\xe2"[..], &b"\x82"[..], &b"b"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    assert!(!buf_list.is_utf8());
+    assert_eq!(buf_list.to_str_lossy(), "a\u{fffd}b");
+    let err = buf_list.try_to_string().unwrap_err();
+    assert_eq!(err.valid_up_to(), 1);
+    assert_eq!(err.error_len(), Some(2));
+
+    // Truncated at the end.
+    let buf_list = vec![&b"ab"[..], &b"\xe2\x82"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    assert_eq!(buf_list.to_str_lossy(), "ab\u{fffd}");
+    let err = buf_list.try_to_string().unwrap_err();
+    assert_eq!(err.valid_up_to(), 2);
+    assert_eq!(err.error_len(), None);
+
+    assert!(BufList::new().is_utf8());
+    assert_eq!(BufList::new().to_str_lossy(), "");
+}
+
+#[test]
+fn test_chars() {
+    // "€" is [0xe2, 0x82, 0xac].
+    let buf_list = vec![
+        &b"a\xe2"[..],
+        &b"\x82"[..],
+        &b"\xac\xe2\x82"[..],
+        &b"b\xe2"[..],
+    ]
+    .into_iter()
+    .collect::<BufList>();
+    let chars: Vec<_> = buf_list
+        .chars()
+        .map(|res| res.map_err(|err| (err.valid_up_to(), err.error_len())))
+        .collect();
+    assert_eq!(
+        chars,
+        vec![Ok('a'), Ok('€'), Err((4, Some(2))), Ok('b'), Err((7, None))]
+    );
+}
+
+#[test]
+fn test_display_adapters() {
+    let buf_list = vec![&b"a\xe2"[..], &b"\x82\xac"[..], &b"\t\"\xe2"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    assert_eq!(buf_list.display_lossy().to_string(), "a€\t\"\u{fffd}");
+    assert_eq!(
+        buf_list.escape_ascii().to_string(),
+        "a\\xe2\\x82\\xac\\t\\\"\\xe2"
+    );
+    assert_eq!(BufList::new().display_lossy().to_string(), "");
+}
+
+#[test]
+fn test_debug_preview() {
+    assert_eq!(
+        format!("{:?}", BufList::new().debug_preview(4)),
+        "BufList { num_chunks: 0, num_bytes: 0, contents: || }"
+    );
+
+    let buf_list = BufList::from(&b"abcdef"[..]);
+    // Exactly 2 * n bytes are shown in full.
+    assert_eq!(
+        format!("{:?}", buf_list.debug_preview(3)),
+        "BufList { num_chunks: 1, num_bytes: 6, contents: 61 62 63 64 65 66 |abcdef| }"
+    );
+    // The head and tail come from the same chunk.
+    assert_eq!(
+        format!("{:?}", buf_list.debug_preview(2)),
+        "BufList { num_chunks: 1, num_bytes: 6, head: 61 62 |ab|, omitted_bytes: 2, \
+         tail: 65 66 |ef| }"
+    );
+    assert_eq!(
+        format!("{:?}", buf_list.debug_preview(0)),
+        "BufList { num_chunks: 1, num_bytes: 6, head: ||, omitted_bytes: 6, tail: || }"
+    );
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(format!("{:x}", BufList::new()), "");
+    assert_eq!(format!("{:X}", BufList::new()), "");
+
+    let buf_list = BufList::from(&b"\x00\x0f\xf0\xff"[..]);
+    assert_eq!(format!("{:x}", buf_list), "000ff0ff");
+    assert_eq!(format!("{:X}", buf_list), "000FF0FF");
+}
+
+#[test]
+fn test_chunk_stats() {
+    let stats = BufList::new().chunk_stats();
+    assert_eq!(stats.num_chunks(), 0);
+    assert_eq!(stats.num_bytes(), 0);
+    assert_eq!(stats.min_chunk_size(), None);
+    assert_eq!(stats.max_chunk_size(), None);
+    assert_eq!(stats.mean_chunk_size(), None);
+    assert!(stats.histogram().is_empty());
+
+    let stats = BufList::from(vec![0u8; 1024]).chunk_stats();
+    assert_eq!(stats.num_chunks(), 1);
+    assert_eq!(stats.num_bytes(), 1024);
+    assert_eq!(stats.min_chunk_size(), Some(1024));
+    assert_eq!(stats.max_chunk_size(), Some(1024));
+    assert_eq!(stats.mean_chunk_size(), Some(1024.0));
+    // 1024 is the lower bound of bucket 10.
+    let mut histogram = [0; 11];
+    histogram[10] = 1;
+    assert_eq!(stats.histogram(), &histogram[..]);
+
+    let stats = BufList::from(&b"a"[..]).chunk_stats();
+    assert_eq!(stats.histogram(), &[1]);
+}
+
+#[test]
+fn test_memory_usage() {
+    let usage = BufList::new().memory_usage();
+    assert_eq!(usage.num_bytes(), 0);
+    assert_eq!(usage.num_regions(), 0);
+    assert_eq!(usage.region_bytes(), 0);
+
+    let data = Bytes::from(vec![0u8; 64]);
+    let buf_list = BufList::from(data.slice(16..32));
+    let usage = buf_list.memory_usage();
+    assert_eq!(usage.num_bytes(), 16);
+    assert_eq!(usage.num_regions(), 1);
+    assert_eq!(usage.region_bytes(), 16);
+
+    // Overlapping slices are merged into one region.
+    let buf_list: BufList = vec![data.slice(0..32), data.slice(16..48)]
+        .into_iter()
+        .collect();
+    let usage = buf_list.memory_usage();
+    assert_eq!(usage.num_bytes(), 64);
+    assert_eq!(usage.num_regions(), 1);
+    assert_eq!(usage.region_bytes(), 48);
+}
+
+#[test]
+fn test_try_push_chunk() {
+    let mut buf_list = BufList::new();
+    assert_eq!(
+        buf_list.try_push_chunk(&b"abc"[..], 5).unwrap(),
+        &b"abc"[..]
+    );
+    assert_eq!(buf_list.try_push_chunk(&b"de"[..], 5).unwrap(), &b"de"[..]);
+    // Adding an empty chunk at the limit is fine.
+    buf_list.try_push_chunk(&b""[..], 5).unwrap();
+
+    let err = buf_list.try_push_chunk(&b"f"[..], 5).unwrap_err();
+    assert_eq!(err.limit(), 5);
+    assert_eq!(err.attempted(), 6);
+    assert_eq!(
+        err.to_string(),
+        "adding data would grow BufList to 6 bytes, exceeding the limit of 5 bytes"
+    );
+    assert_eq!(buf_list.num_bytes(), 5);
+    assert_eq!(buf_list.num_chunks(), 2);
+}
+
+#[test]
+fn test_tail_buf_list() {
+    let mut tail = TailBufList::new(5);
+    tail.push_chunk(&b"ab"[..]);
+    tail.push_chunk(&b"cd"[..]);
+    assert_eq!(tail.get_ref().num_bytes(), 4);
+    assert_eq!(tail.dropped_bytes(), 0);
+
+    // This drops "a" from the first chunk.
+    tail.push_chunk(&b"ef"[..]);
+    assert_eq!(tail.get_ref().num_chunks(), 3);
+    assert_eq!(tail.get_ref().chunk(), &b"b"[..]);
+    assert_eq!(tail.dropped_bytes(), 1);
+
+    // A chunk larger than the limit only has its tail kept.
+    tail.extend(vec![&b"ghijklm"[..]]);
+    assert_eq!(tail.get_ref().num_chunks(), 1);
+    assert_eq!(tail.get_ref().chunk(), &b"ijklm"[..]);
+    assert_eq!(tail.dropped_bytes(), 8);
+
+    let tail = TailBufList::new(0);
+    assert_eq!(tail.max_bytes(), 0);
+    assert_eq!(tail.into_inner().num_bytes(), 0);
+}
+
+#[test]
+fn test_watermark_buf_list() {
+    let mut events = Vec::new();
+    {
+        let mut buf_list =
+            WatermarkBufList::with_callback(2, 6, |watermark| events.push(watermark));
+        buf_list.push_chunk(&b"abc"[..]);
+        buf_list.push_chunk(&b"def"[..]);
+        assert!(buf_list.is_full());
+
+        // Pushing while full doesn't fire the callback again.
+        buf_list.push_chunk(&b"g"[..]);
+        assert_eq!(buf_list.copy_to_bytes(4), Bytes::from_static(b"abcd"));
+        assert!(buf_list.is_full());
+        buf_list.advance(1);
+        assert!(!buf_list.is_full());
+        assert_eq!(buf_list.get_ref().num_bytes(), 2);
+
+        // Crossing the high watermark again fires the callback again.
+        buf_list.push_chunk(&b"hijk"[..]);
+        assert!(buf_list.is_full());
+    }
+    assert_eq!(
+        events,
+        vec![Watermark::High, Watermark::Low, Watermark::High]
+    );
+
+    // Without a callback, only the flag is tracked.
+    let mut buf_list = WatermarkBufList::new(0, 1);
+    assert!(!buf_list.is_full());
+    buf_list.push_chunk(&b""[..]);
+    assert!(!buf_list.is_full());
+    buf_list.push_chunk(&b"a"[..]);
+    assert!(buf_list.is_full());
+    assert_eq!(buf_list.low_watermark(), 0);
+    assert_eq!(buf_list.high_watermark(), 1);
+}
+
+#[test]
+#[should_panic = "low watermark (4) must be less than high watermark (4)"]
+fn test_watermark_buf_list_equal_watermarks() {
+    WatermarkBufList::new(4, 4);
+}
+
+#[test]
+fn test_shallow_eq() {
+    let hello = Bytes::from(b"hello".to_vec());
+    let world = Bytes::from(b"world".to_vec());
+    let a = vec![hello.clone(), world.clone()]
+        .into_iter()
+        .collect::<BufList>();
+    assert!(a.shallow_eq(&a.clone()));
+    assert!(a.shallow_eq(&vec![hello.clone(), world].into_iter().collect()));
+
+    // Slices of the same memory with different lengths aren't the same chunk.
+    let b = vec![hello.clone(), Bytes::from(b"worl".to_vec())]
+        .into_iter()
+        .collect::<BufList>();
+    assert!(!a.shallow_eq(&b));
+    let c = vec![hello.slice(..4)].into_iter().collect::<BufList>();
+    assert!(!c.shallow_eq(&BufList::from(hello)));
+
+    assert!(BufList::new().shallow_eq(&BufList::new()));
+}
+
+#[test]
+fn test_total_len() {
+    // Build a 5 GiB list out of clones of a single 1 MiB chunk.
+    let chunk = Bytes::from(vec![0u8; 1 << 20]);
+    let buf_list = std::iter::repeat(chunk).take(5 << 10).collect::<BufList>();
+    let expected = 5u64 << 30;
+    assert_eq!(buf_list.total_len(), expected);
+
+    // On 32-bit targets, `num_bytes` and `remaining` saturate.
+    let saturated = expected.min(std::usize::MAX as u64) as usize;
+    assert_eq!(buf_list.num_bytes(), saturated);
+    assert_eq!(buf_list.remaining(), saturated);
+    assert_eq!(buf_list.rfind_byte(0), Some(expected - 1));
+}
+
+#[test]
+fn test_positional() {
+    let mut buf_list = vec![&b"hello"[..], &b"world"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    assert_eq!(buf_list.byte_at(4), Some(b'o'));
+
+    // Chunks pushed after the index is built are visible to lookups.
+    buf_list.push_chunk(&b"!"[..]);
+    assert_eq!(buf_list.byte_at(10), Some(b'!'));
+    assert_eq!(buf_list.byte_at(11), None);
+
+    // Consuming bytes shifts positions.
+    buf_list.advance(3);
+    assert_eq!(buf_list.byte_at(0), Some(b'l'));
+    assert_eq!(buf_list.byte_at(7), Some(b'!'));
+
+    let mut buf = [0u8; 16];
+    assert_eq!(buf_list.read_at(1, &mut buf), 7);
+    assert_eq!(&buf[..7], b"oworld!");
+    assert_eq!(buf_list.read_at(100, &mut buf), 0);
+
+    let slice = buf_list.slice(1..=2);
+    assert_eq!(slice.num_chunks(), 2);
+    assert_eq!(slice.to_str_lossy(), "ow");
+    assert_eq!(buf_list.slice(8..).num_chunks(), 0);
+}
+
+#[test]
+#[should_panic = "range end out of bounds"]
+fn test_slice_out_of_bounds() {
+    let buf_list = BufList::from(&b"hello"[..]);
+    buf_list.slice(2..6);
+}
+
+#[test]
+fn test_cached_len() {
+    let mut buf_list = vec![&b"hello"[..], &b"world"[..], &b"!"[..]]
+        .into_iter()
+        .collect::<BufList>();
+    assert_eq!(buf_list.remaining(), 11);
+
+    buf_list.advance(2);
+    assert_eq!(buf_list.remaining(), 9);
+    assert_eq!(buf_list.copy_to_bytes(3), Bytes::from_static(b"llo"));
+    assert_eq!(buf_list.remaining(), 6);
+    assert_eq!(buf_list.copy_to_bytes(4), Bytes::from_static(b"worl"));
+    assert_eq!(buf_list.remaining(), 2);
+
+    buf_list.extend(vec![&b"abc"[..], &b""[..]]);
+    assert_eq!(buf_list.total_len(), 5);
+    buf_list.advance(5);
+    assert_eq!(buf_list.total_len(), 0);
+    assert_eq!(buf_list.num_chunks(), 0);
+}
+
+#[test]
+fn test_inline_storage() {
+    // Lists with a few chunks are stored inline, and spill onto the heap as they grow. Check that
+    // the transition is seamless.
+    let mut buf_list = BufList::new();
+    for (i, chunk) in [&b"a"[..], b"bc", b"def", b"ghij", b"klmno"]
+        .iter()
+        .enumerate()
+    {
+        buf_list.push_chunk(*chunk);
+        let cloned = buf_list.clone();
+        assert_eq!(cloned.num_chunks(), i + 1);
+        assert_eq!(cloned.iter().last().map(|chunk| &chunk[..]), Some(*chunk));
+    }
+
+    buf_list.advance(3);
+    let chunks: Vec<_> = buf_list.clone().into_iter().rev().collect();
+    assert_eq!(chunks, vec!["klmno", "ghij", "def"]);
+
+    let mut into_iter = BufList::from(&b"x"[..]).into_iter();
+    assert_eq!(into_iter.len(), 1);
+    assert_eq!(into_iter.next_back(), Some(Bytes::from_static(b"x")));
+    assert_eq!(into_iter.next(), None);
+}
+
+#[test]
+fn test_segmented_buf() {
+    let mut buf = SegmentedBuf::new();
+    buf.push_segment(BytesMut::from(&b"hello"[..]));
+    buf.push_segment(BytesMut::new());
+    buf.push_segment(BytesMut::from(&b"world"[..]));
+    assert_eq!(buf.num_chunks(), 2);
+    assert_eq!(buf.num_bytes(), 10);
+
+    // Segments keep their original type.
+    for segment in buf.iter() {
+        assert_eq!(segment.len(), 5);
+    }
+    let mut segments: Vec<BytesMut> = buf.clone().into_iter().collect();
+    segments[0].make_ascii_uppercase();
+    assert_eq!(segments[0], "HELLO");
+
+    assert_eq!(buf.copy_to_bytes(7), Bytes::from_static(b"hellowo"));
+    assert_eq!(buf.num_chunks(), 1);
+    assert_eq!(buf.chunk(), b"rld");
+
+    // Both aliases print as the underlying type.
+    assert!(format!("{:?}", buf).starts_with("SegmentedBuf { bufs: "));
+}
+
+#[test]
+fn test_as_buf() {
+    let buf_list = vec![&b"hello"[..], &b" "[..], &b"world"[..]]
+        .into_iter()
+        .collect::<BufList>();
+
+    let mut view = buf_list.as_buf();
+    assert_eq!(view.copy_to_bytes(3), Bytes::from_static(b"hel"));
+    assert_eq!(view.position(), 3);
+
+    let mut iovs = [IoSlice::new(&[]); 4];
+    assert_eq!(view.chunks_vectored(&mut iovs), 3);
+    assert_eq!(iovs[0].deref(), b"lo");
+    assert_eq!(iovs[1].deref(), b" ");
+    assert_eq!(iovs[2].deref(), b"world");
+
+    // Copying across chunks works, and leaves the list untouched.
+    assert_eq!(view.copy_to_bytes(5), Bytes::from_static(b"lo wo"));
+    assert_eq!(view.chunk(), b"rld");
+    view.advance(3);
+    assert_eq!(view.remaining(), 0);
+    assert_eq!(view.chunk(), b"");
+    assert_eq!(buf_list.num_bytes(), 11);
+
+    // Views can be handed to anything that takes a `Buf`.
+    let mut first = buf_list.as_buf();
+    let mut second = first.clone();
+    assert_eq!(first.get_u8(), b'h');
+    assert_eq!(second.copy_to_bytes(11), Bytes::from_static(b"hello world"));
+}
+
+#[test]
+fn test_buf_list_mut() {
+    let mut buf_list = BufListMut::new();
+    for chunk in &[&b"abc"[..], b"", b"def", b"gh", b"ijkl"] {
+        buf_list.push_segment(BytesMut::from(*chunk));
+    }
+    assert_eq!(buf_list.num_chunks(), 4);
+
+    for chunk in buf_list.iter_mut().rev().take(2) {
+        chunk.make_ascii_uppercase();
+    }
+    buf_list.get_chunk_mut(0).unwrap()[1] = b'-';
+    assert!(buf_list.get_chunk_mut(4).is_none());
+
+    let frozen = buf_list.freeze();
+    assert_eq!(frozen.num_chunks(), 4);
+    assert_eq!(frozen.to_str_lossy(), "a-cdefGHIJKL");
+}
+
+#[test]
+fn test_writer_buf_mut() {
+    let mut writer = Writer::new();
+    assert_eq!(writer.total_len(), 0);
+    assert_eq!(writer.clone().into_inner().num_chunks(), 0);
+
+    // Write enough to spill over several blocks.
+    for i in 0..5000u32 {
+        writer.put_u32_le(i);
+    }
+    writer.put_slice(&[0xff; 3]);
+    assert_eq!(writer.total_len(), 20003);
+
+    let mut buf_list = writer.into_inner();
+    assert!(buf_list.num_chunks() > 1);
+    for i in 0..5000u32 {
+        assert_eq!(buf_list.get_u32_le(), i);
+    }
+    assert_eq!(buf_list.copy_to_bytes(3), Bytes::from_static(&[0xff; 3]));
+    assert_eq!(buf_list.remaining(), 0);
+}
+
+#[test]
+fn test_io_write() {
+    use std::io::Write;
+
+    let mut buf_list = BufList::new();
+    buf_list.write_all(b"hello").unwrap();
+    buf_list.write_all(b"").unwrap();
+    let n = buf_list
+        .write_vectored(&[IoSlice::new(b" "), IoSlice::new(b"world")])
+        .unwrap();
+    assert_eq!(n, 6);
+    buf_list.flush().unwrap();
+
+    // Small writes are coalesced.
+    assert_eq!(buf_list.num_chunks(), 1);
+    assert_eq!(buf_list.to_str_lossy(), "hello world");
+
+    // Anything that writes to a `dyn Write` can write to a `BufList`.
+    let writer: &mut dyn Write = &mut buf_list;
+    writeln!(writer, "!").unwrap();
+    assert_eq!(buf_list.to_str_lossy(), "hello world!\n");
+
+    // write! produces lots of small fragments, which don't each end up in their own chunk.
+    let mut buf_list = BufList::new();
+    for i in 0..100 {
+        write!(buf_list, "{}: {:?}, ", i, (i, "x")).unwrap();
+    }
+    assert!(buf_list.num_bytes() > 1000);
+    assert!(
+        buf_list.num_chunks() <= 8,
+        "{} chunks",
+        buf_list.num_chunks()
+    );
+}
+
+#[cfg(feature = "tokio1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_tokio_async_write() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut buf_list = BufList::new();
+    let n = tokio::io::copy(&mut &b"hello"[..], &mut buf_list)
+        .await
+        .unwrap();
+    assert_eq!(n, 5);
+    let n = buf_list
+        .write_vectored(&[IoSlice::new(b" "), IoSlice::new(b"world")])
+        .await
+        .unwrap();
+    assert_eq!(n, 6);
+    buf_list.shutdown().await.unwrap();
+    assert_eq!(buf_list.to_str_lossy(), "hello world");
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_futures_async_write() {
+    use futures::io::AsyncWriteExt;
+
+    futures::executor::block_on(async {
+        let mut buf_list = BufList::new();
+        let n = futures::io::copy(&b"hello"[..], &mut buf_list)
+            .await
+            .unwrap();
+        assert_eq!(n, 5);
+        let n = buf_list
+            .write_vectored(&[IoSlice::new(b" "), IoSlice::new(b"world")])
+            .await
+            .unwrap();
+        assert_eq!(n, 6);
+        buf_list.close().await.unwrap();
+        assert_eq!(buf_list.to_str_lossy(), "hello world");
+    });
+}
+
+#[test]
+fn test_writer_io_write() {
+    use std::io::Write;
+
+    let mut writer = Writer::with_chunk_size(8);
+    assert_eq!(writer.chunk_size(), 8);
+    for i in 0..10 {
+        write!(writer, "{}", i).unwrap();
+    }
+    // The first 8 bytes fill up a block.
+    assert_eq!(writer.get_ref().num_chunks(), 1);
+    assert_eq!(writer.total_len(), 10);
+
+    // Flushing makes the rest visible.
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().num_chunks(), 2);
+    assert_eq!(writer.get_ref().to_str_lossy(), "0123456789");
+
+    // A large vectored write is split across blocks.
+    let n = writer
+        .write_vectored(&[IoSlice::new(b"abcdefghij"), IoSlice::new(b"klmnopqrst")])
+        .unwrap();
+    assert_eq!(n, 20);
+    let buf_list = writer.into_inner();
+    assert_eq!(buf_list.to_str_lossy(), "0123456789abcdefghijklmnopqrst");
+    assert!(buf_list.iter().all(|chunk| chunk.len() <= 8));
+}
+
+#[test]
+fn test_writer_push_chunk() {
+    let mut writer = Writer::with_chunk_sizes(4, 6);
+    assert_eq!(writer.min_chunk_size(), 4);
+    writer.extend(vec![&b"ab"[..], b"c", b"def", b"gh"]);
+    // "abcdef" filled up a block.
+    assert_eq!(writer.get_ref().num_chunks(), 1);
+
+    let large = Bytes::from_static(b"ijkl");
+    writer.push_chunk(large.clone());
+    writer.push_chunk(&b""[..]);
+    writer.push_chunk(&b"m"[..]);
+
+    let buf_list = writer.into_inner();
+    let chunks: Vec<_> = buf_list.iter().cloned().collect();
+    assert_eq!(chunks, vec!["abcdef", "gh", "ijkl", "m"]);
+    assert_eq!(chunks[2].as_ptr(), large.as_ptr());
+
+    // By default, all chunks are added as is.
+    let mut writer = Writer::new();
+    writer.push_chunk(&b"a"[..]);
+    writer.push_chunk(&b"b"[..]);
+    assert_eq!(writer.into_inner().num_chunks(), 2);
+}
+
+#[test]
+fn test_buf_list_builder() {
+    use std::io::Write;
+
+    let mut writer = BufList::builder()
+        .max_bytes(10)
+        .min_chunk_size(2)
+        .chunk_size(4)
+        .chunk_capacity(8)
+        .build();
+    assert_eq!(writer.max_bytes(), Some(10));
+    assert_eq!(writer.min_chunk_size(), 2);
+    assert_eq!(writer.chunk_size(), 4);
+
+    writer.push_chunk(&b"a"[..]);
+    writer.push_chunk(&b"bcd"[..]);
+    assert_eq!(writer.remaining_mut(), 6);
+
+    let err = writer.try_push_chunk(&b"efghijk"[..]).unwrap_err();
+    assert_eq!(err.limit(), 10);
+    assert_eq!(err.attempted(), 11);
+    assert_eq!(writer.total_len(), 4);
+
+    assert_eq!(
+        writer
+            .write_vectored(&[IoSlice::new(b"ef"), IoSlice::new(b"ghijk")])
+            .unwrap(),
+        6
+    );
+    assert_eq!(writer.remaining_mut(), 0);
+    assert_eq!(writer.chunk_mut().len(), 0);
+    assert!(writer.write(b"l").is_err());
+    assert_eq!(writer.write(b"").unwrap(), 0);
+    writer.push_chunk(&b""[..]);
+
+    let buf_list = writer.into_inner();
+    let chunks: Vec<_> = buf_list.iter().cloned().collect();
+    // The rest of the first block is reused after it was frozen.
+    assert_eq!(chunks, vec!["a", "bcd", "efg", "hij"]);
+
+    // Without a limit, the builder produces a writer equivalent to Writer::new().
+    let writer = BufList::builder().build();
+    assert_eq!(writer.max_bytes(), None);
+    assert_eq!(writer.chunk_size(), Writer::new().chunk_size());
+}
+
+#[test]
+#[should_panic = "exceeding the limit of 2 bytes"]
+fn test_buf_list_builder_put_over_limit() {
+    let mut writer = BufList::builder().max_bytes(2).build();
+    writer.put_u32(0);
+}
+
+#[test]
+fn test_writer_extend_from_slice() {
+    let mut writer = Writer::with_chunk_size(8);
+    for i in 0..10u8 {
+        writer.extend_from_slice(&[i, i]);
+    }
+    writer.extend_from_slice(&[]);
+    assert_eq!(writer.total_len(), 20);
+    // Only the full blocks have been frozen so far.
+    assert_eq!(writer.get_ref().num_chunks(), 2);
+
+    let buf_list = writer.into_inner();
+    assert_eq!(buf_list.num_chunks(), 3);
+    let bytes = buf_list.clone().copy_to_bytes(20);
+    let expected: Vec<u8> = (0..10u8).flat_map(|i| vec![i, i]).collect();
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn test_buf_list_extend_from_slice() {
+    let mut buf_list = BufList::new();
+    // Build the chunk index, to check that it's kept up to date as the tail grows.
+    assert_eq!(buf_list.byte_at(0), None);
+
+    let mut expected = Vec::new();
+    for i in 0..1000u32 {
+        let data = [i as u8, (i >> 8) as u8];
+        buf_list.extend_from_slice(&data);
+        expected.extend_from_slice(&data);
+    }
+    buf_list.extend_from_slice(&[]);
+    assert_eq!(buf_list.num_bytes(), 2000);
+    assert_eq!(buf_list.num_chunks(), 1);
+    assert_eq!(buf_list.byte_at(1999), Some(3));
+    assert_eq!(buf_list.clone().copy_to_bytes(2000), expected);
+
+    // The tail is extended in place, without moving the bytes already in it.
+    let ptr = buf_list.get_chunk(0).unwrap().as_ptr();
+    buf_list.extend_from_slice(b"!");
+    assert_eq!(buf_list.get_chunk(0).unwrap().as_ptr(), ptr);
+    assert_eq!(buf_list.num_chunks(), 1);
+
+    // Once the tail is full, a new one is started.
+    buf_list.extend_from_slice(&[0; 8000]);
+    assert_eq!(buf_list.num_chunks(), 2);
+    assert_eq!(buf_list.byte_at(2000), Some(b'!'));
+    assert_eq!(buf_list.num_bytes(), 10_001);
+
+    // Chunks that were pushed as is aren't copied or extended.
+    let mut buf_list = BufList::new();
+    let hello = Bytes::from_static(b"hello ");
+    buf_list.push_chunk(hello.clone());
+    buf_list.extend_from_slice(b"world");
+    assert_eq!(buf_list.num_chunks(), 2);
+    assert_eq!(buf_list.get_chunk(0).unwrap().as_ptr(), hello.as_ptr());
+    assert_eq!(buf_list.get_chunk(1).unwrap(), &b"world"[..]);
+
+    // Neither is a tail followed by a pushed chunk.
+    buf_list.push_chunk(&b"!"[..]);
+    buf_list.extend_from_slice(b"?");
+    assert_eq!(buf_list.num_chunks(), 4);
+
+    // A tail that's shared with another handle isn't written to.
+    let snapshot = buf_list.clone();
+    buf_list.extend_from_slice(b"?");
+    assert_eq!(buf_list.num_chunks(), 5);
+    assert_eq!(snapshot.get_chunk(3).unwrap(), &b"?"[..]);
+    assert_eq!(buf_list.to_str_lossy(), "hello world!??");
+
+    // Slices that are too big to fit in a tail buffer are added as new chunks.
+    buf_list.extend_from_slice(&[0; 8192]);
+    assert_eq!(buf_list.num_chunks(), 6);
+    buf_list.extend_from_slice(b"!");
+    assert_eq!(buf_list.num_chunks(), 7);
+    assert_eq!(buf_list.byte_at(8206), Some(b'!'));
+}
+
+#[test]
+fn test_chunk_pool() {
+    use buf_list::ChunkPool;
+    use std::io::Write;
+
+    let pool = ChunkPool::new(8, 2);
+    assert_eq!(pool.block_size(), 8);
+    assert_eq!(pool.max_blocks(), 2);
+
+    let mut writer = Writer::with_pool(pool.clone());
+    assert_eq!(writer.chunk_size(), 8);
+    writer.write_all(b"hello world").unwrap();
+    let buf_list = writer.into_inner();
+    assert_eq!(buf_list.num_chunks(), 2);
+    let ptrs: Vec<_> = buf_list.iter().map(|chunk| chunk.as_ptr()).collect();
+
+    // While the list is alive, the pool can't reuse its blocks.
+    let block = pool.get();
+    assert!(!ptrs.contains(&block.as_ptr()));
+    assert_eq!(block.capacity(), 8);
+    drop(block);
+    drop(buf_list);
+
+    // The pool rotates through the blocks it tracks: the first block was replaced above, but the
+    // second one can now be reused.
+    let mut writer = BufList::builder().pool(pool.clone()).chunk_size(1).build();
+    assert_eq!(writer.chunk_size(), 8);
+    writer.put_slice(b"a");
+    let buf_list = writer.into_inner();
+    assert_eq!(buf_list.chunk().as_ptr(), ptrs[1]);
+
+    // A pool that doesn't track any blocks always allocates.
+    let pool = ChunkPool::new(4, 0);
+    assert_eq!(pool.get().capacity(), 4);
+}
+
+#[cfg(feature = "zeroize1")]
+#[test]
+fn test_zeroizing_buf_list() {
+    use buf_list::ZeroizingBufList;
+    use zeroize::Zeroize;
+
+    let mut buf_list = ZeroizingBufList::new();
+    buf_list.push_chunk(&b"hello"[..]);
+    buf_list.push_chunk(Bytes::new());
+    buf_list.push_chunk(&b"world"[..]);
+    assert_eq!(buf_list.get_ref().num_chunks(), 2);
+    assert_eq!(
+        format!("{:?}", buf_list),
+        "ZeroizingBufList { num_chunks: 2, total_len: 10 }"
+    );
+
+    assert_eq!(buf_list.copy_to_bytes(7), "hellowo");
+    assert_eq!(buf_list.chunk(), b"rld");
+
+    buf_list.zeroize();
+    assert_eq!(buf_list.remaining(), 0);
+    assert_eq!(buf_list.get_ref().num_chunks(), 0);
+}
+
+#[test]
+fn test_cursor_write() {
+    use buf_list::Cursor;
+    use std::io::{Seek, SeekFrom, Write};
+
+    let mut buf_list = BufListMut::new();
+    buf_list.push_segment(BytesMut::from(&b"abc"[..]));
+    buf_list.push_segment(BytesMut::from(&b"def"[..]));
+
+    let mut cursor = Cursor::new(&mut buf_list);
+    cursor.set_position(2);
+    // Overwrite across a chunk boundary, and extend the last chunk.
+    cursor.write_all(b"XYZ").unwrap();
+    assert_eq!(cursor.position(), 5);
+    cursor.write_all(b"uvw").unwrap();
+    assert_eq!(cursor.position(), 8);
+
+    // Writing past the end fills the gap with zeroes.
+    assert_eq!(cursor.seek(SeekFrom::End(2)).unwrap(), 10);
+    cursor.write_all(b"!").unwrap();
+
+    assert_eq!(buf_list.num_chunks(), 2);
+    assert_eq!(buf_list.total_len(), 11);
+    assert_eq!(buf_list.freeze().to_str_lossy(), "abXYZuvw\0\0!");
+}
+
+#[test]
+fn test_reserve() {
+    let mut buf_list = BufList::new();
+    buf_list.reserve(0);
+    buf_list.push_chunk(&b"a"[..]);
+    // Reserving past the inline capacity moves the chunks to the heap.
+    buf_list.reserve(8);
+    for _ in 0..8 {
+        buf_list.push_chunk(&b"b"[..]);
+    }
+    assert_eq!(buf_list.num_chunks(), 9);
+    assert_eq!(buf_list.to_str_lossy(), "abbbbbbbb");
+
+    let buf_list: BufList = (0..16).map(|_| &b"c"[..]).collect();
+    assert_eq!(buf_list.num_chunks(), 16);
+
+    let mut writer = Writer::with_chunk_size(4);
+    writer.put_slice(b"ab");
+    // The current block has enough space.
+    writer.reserve(2);
+    writer.put_slice(b"cd");
+    // A larger block is allocated.
+    writer.reserve(6);
+    writer.put_slice(b"efghij");
+    writer.reserve(1);
+    writer.put_slice(b"k");
+
+    let chunks: Vec<_> = writer.into_inner().into_iter().collect();
+    assert_eq!(chunks, vec!["abcd", "efghij", "k"]);
+}
+
+#[test]
+fn test_cursor_refresh() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, Read};
+
+    let mut cursor = Cursor::new(BufList::from(&b"abc"[..]));
+    cursor.set_position(5);
+
+    // Append data while the cursor is past the end of the list.
+    cursor.get_mut().push_chunk(&b"defg"[..]);
+    cursor.get_mut().push_chunk(&b"hi"[..]);
+    cursor.refresh();
+    assert_eq!(cursor.fill_buf().unwrap(), b"fg");
+
+    let mut buf = Vec::new();
+    cursor.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"fghi");
+
+    // Other modifications are also picked up.
+    cursor.set_position(4);
+    cursor.get_mut().advance(2);
+    cursor.refresh();
+    assert_eq!(cursor.fill_buf().unwrap(), b"g");
+}
+
+#[test]
+fn test_cursor_len_accessors() {
+    use buf_list::Cursor;
+    use std::io::Read;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list);
+    assert_eq!(cursor.total_len(), 5);
+    assert_eq!(cursor.remaining(), 5);
+    assert!(!cursor.is_empty());
+
+    let mut buf = [0; 4];
+    cursor.read_exact(&mut buf).unwrap();
+    assert_eq!(cursor.remaining(), 1);
+    assert!(!cursor.is_empty());
+
+    cursor.set_position(10);
+    assert_eq!(cursor.total_len(), 5);
+    assert_eq!(cursor.remaining(), 0);
+    assert!(cursor.is_empty());
+
+    let cursor = Cursor::new(BufList::new());
+    assert!(cursor.is_empty());
+}
+
+#[test]
+fn test_cursor_read_bytes() {
+    use buf_list::Cursor;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list);
+    assert_eq!(cursor.read_bytes(0).unwrap(), Bytes::new());
+
+    let ab = cursor.read_bytes(2).unwrap();
+    assert_eq!(ab, "ab");
+    assert_eq!(ab.as_ptr(), buf_list.get_chunk(0).unwrap().as_ptr());
+
+    // Ends exactly at the chunk boundary.
+    assert_eq!(cursor.read_bytes(1).unwrap(), "c");
+    assert_eq!(cursor.position(), 3);
+
+    let err = cursor.read_bytes(5).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(cursor.position(), 3);
+
+    cursor.set_position(2);
+    assert_eq!(cursor.read_bytes(5).unwrap(), "cdefg");
+    assert!(cursor.is_empty());
+    assert_eq!(cursor.read_bytes(0).unwrap(), Bytes::new());
+}
+
+#[test]
+fn test_cursor_read_buf_list() {
+    use buf_list::Cursor;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list);
+    assert_eq!(cursor.read_buf_list(0).unwrap().num_chunks(), 0);
+
+    cursor.set_position(1);
+    let read = cursor.read_buf_list(4).unwrap();
+    assert_eq!(read.num_chunks(), 2);
+    assert_eq!(read.to_str_lossy(), "bcde");
+    assert_eq!(
+        read.chunk().as_ptr(),
+        buf_list.get_chunk(0).unwrap()[1..].as_ptr()
+    );
+    assert_eq!(cursor.position(), 5);
+
+    let err = cursor.read_buf_list(3).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(cursor.position(), 5);
+
+    assert_eq!(cursor.read_buf_list(2).unwrap().to_str_lossy(), "fg");
+    assert!(cursor.is_empty());
+}
+
+#[test]
+fn test_cursor_split_at_current() {
+    use buf_list::Cursor;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..]].into_iter().collect();
+    for (pos, consumed, rest) in vec![
+        (0, "", "abcdefg"),
+        (2, "ab", "cdefg"),
+        (3, "abc", "defg"),
+        (7, "abcdefg", ""),
+        (10, "abcdefg", ""),
+    ] {
+        let mut cursor = Cursor::new(&buf_list);
+        cursor.set_position(pos);
+        let (a, b) = cursor.split_at_current();
+        assert_eq!(a.to_str_lossy(), consumed, "consumed for position {}", pos);
+        assert_eq!(b.to_str_lossy(), rest, "rest for position {}", pos);
+        assert_eq!(a.num_chunks() + b.num_chunks(), 2 + (pos == 2) as usize);
+    }
+}
+
+#[test]
+fn test_cursor_remaining_chunks() {
+    use buf_list::Cursor;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..], &b"fgh"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    for (pos, expected) in vec![
+        (0, vec!["abc", "de", "fgh"]),
+        (1, vec!["bc", "de", "fgh"]),
+        (3, vec!["de", "fgh"]),
+        (7, vec!["h"]),
+        (8, vec![]),
+        (12, vec![]),
+    ] {
+        cursor.set_position(pos);
+        let chunks = cursor.remaining_chunks();
+        assert_eq!(chunks.len(), expected.len(), "len for position {}", pos);
+        assert_eq!(chunks.collect::<Vec<_>>(), expected, "position {}", pos);
+        assert_eq!(cursor.position(), pos);
+    }
+
+    cursor.set_position(4);
+    let mut chunks = cursor.remaining_chunks();
+    assert_eq!(chunks.next_back().unwrap(), "fgh");
+    assert_eq!(chunks.next_back().unwrap(), "e");
+    assert_eq!(chunks.next_back(), None);
+    assert_eq!(chunks.next(), None);
+
+    // Iterating from both ends meets in the middle.
+    cursor.set_position(1);
+    let mut chunks = cursor.remaining_chunks();
+    assert_eq!(chunks.next().unwrap(), "bc");
+    assert_eq!(chunks.next_back().unwrap(), "fgh");
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks.next().unwrap(), "de");
+    assert_eq!(chunks.len(), 0);
+    assert_eq!(chunks.next_back(), None);
+}
+
+#[test]
+fn test_cursor_peek() {
+    use buf_list::Cursor;
+    use std::io::Read;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(2);
+
+    let mut buf = [0; 2];
+    assert_eq!(cursor.peek(&mut buf), 2);
+    assert_eq!(&buf, b"cd");
+    cursor.peek_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"cd");
+    assert_eq!(cursor.position(), 2);
+
+    // Peeking doesn't affect later reads.
+    let mut buf = [0; 4];
+    assert_eq!(cursor.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"cde");
+
+    // At the end, peek returns nothing.
+    assert_eq!(cursor.peek(&mut buf), 0);
+    let err = cursor.peek_exact(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    cursor.peek_exact(&mut []).unwrap();
+}
+
+#[test]
+fn test_cursor_typed_reads() {
+    use buf_list::Cursor;
+
+    let mut writer = Writer::with_chunk_size(3);
+    writer.put_u8(0xff);
+    writer.put_i8(-2);
+    writer.put_u16(0x0102);
+    writer.put_i16_le(-3);
+    writer.put_u32(0x0304_0506);
+    writer.put_i32_le(-4);
+    writer.put_u64(0x0708_090a_0b0c_0d0e);
+    writer.put_i64_le(-5);
+    writer.put_u128(0x0f10_1112_1314_1516_1718_191a_1b1c_1d1e);
+    writer.put_i128_le(-6);
+    writer.put_f32(1.5);
+    writer.put_f32_le(-2.5);
+    writer.put_f64(3.25);
+    writer.put_f64_le(-4.75);
+    let buf_list = writer.into_inner();
+    // Most values span chunk boundaries.
+    assert!(buf_list.num_chunks() > 10);
+
+    let mut cursor = Cursor::new(&buf_list);
+    assert_eq!(cursor.read_u8().unwrap(), 0xff);
+    assert_eq!(cursor.read_i8().unwrap(), -2);
+    assert_eq!(cursor.read_u16_be().unwrap(), 0x0102);
+    assert_eq!(cursor.read_i16_le().unwrap(), -3);
+    assert_eq!(cursor.read_u32_be().unwrap(), 0x0304_0506);
+    assert_eq!(cursor.read_i32_le().unwrap(), -4);
+    assert_eq!(cursor.read_u64_be().unwrap(), 0x0708_090a_0b0c_0d0e);
+    assert_eq!(cursor.read_i64_le().unwrap(), -5);
+    assert_eq!(
+        cursor.read_u128_be().unwrap(),
+        0x0f10_1112_1314_1516_1718_191a_1b1c_1d1e
+    );
+    assert_eq!(cursor.read_i128_le().unwrap(), -6);
+    assert_eq!(cursor.read_f32_be().unwrap(), 1.5);
+    assert_eq!(cursor.read_f32_le().unwrap(), -2.5);
+    assert_eq!(cursor.read_f64_be().unwrap(), 3.25);
+    assert_eq!(cursor.read_f64_le().unwrap(), -4.75);
+    assert!(cursor.is_empty());
+
+    // Short reads don't advance the cursor.
+    cursor.set_position(buf_list.total_len() - 3);
+    let err = cursor.read_u32_le().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(cursor.remaining(), 3);
+    assert_eq!(
+        cursor.read_u16_le().unwrap(),
+        u16::from_le_bytes([0x00, 0x13])
+    );
+}
+
+#[test]
+fn test_cursor_typed_peeks() {
+    use buf_list::Cursor;
+
+    let mut writer = Writer::with_chunk_size(3);
+    writer.put_u8(0xfe);
+    writer.put_u32_le(0x0102_0304);
+    writer.put_f64(-1.25);
+    let buf_list = writer.into_inner();
+
+    let mut cursor = Cursor::new(&buf_list);
+    assert_eq!(cursor.peek_u8().unwrap(), 0xfe);
+    assert_eq!(cursor.peek_i8().unwrap(), -2);
+    assert_eq!(cursor.peek_u16_be().unwrap(), 0xfe04);
+    assert_eq!(cursor.position(), 0);
+
+    cursor.set_position(1);
+    assert_eq!(cursor.peek_u32_le().unwrap(), 0x0102_0304);
+    assert_eq!(cursor.peek_u32_be().unwrap(), 0x0403_0201);
+    assert_eq!(cursor.read_u32_le().unwrap(), 0x0102_0304);
+    assert_eq!(cursor.peek_f64_be().unwrap(), -1.25);
+    assert_eq!(cursor.position(), 5);
+
+    // Short peeks fail without moving the cursor.
+    cursor.set_position(7);
+    let err = cursor.peek_u64_le().unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(cursor.position(), 7);
+}
+
+#[test]
+fn test_cursor_read_varint() {
+    use buf_list::Cursor;
+    use std::io::ErrorKind;
+
+    fn encode(mut n: u64, out: &mut Vec<u8>) {
+        while n >= 0x80 {
+            out.push((n as u8) | 0x80);
+            n >>= 7;
+        }
+        out.push(n as u8);
+    }
+
+    let values = [
+        0,
+        1,
+        127,
+        128,
+        300,
+        1 << 35,
+        u64::max_value() - 1,
+        u64::max_value(),
+    ];
+    let mut encoded = Vec::new();
+    for &value in &values {
+        encode(value, &mut encoded);
+    }
+    for &value in &[0i64, -1, 1, i64::min_value(), i64::max_value()] {
+        encode(((value << 1) ^ (value >> 63)) as u64, &mut encoded);
+    }
+
+    // Split the data into 1- and 2-byte chunks so that most varints span chunk boundaries.
+    let buf_list: BufList = encoded
+        .chunks(2)
+        .flat_map(|chunk| chunk.chunks(if chunk[0] % 2 == 0 { 1 } else { 2 }))
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    for &value in &values {
+        assert_eq!(cursor.read_varint_u64().unwrap(), value);
+    }
+    for &value in &[0i64, -1, 1, i64::min_value(), i64::max_value()] {
+        assert_eq!(cursor.read_varint_i64().unwrap(), value);
+    }
+    assert!(cursor.is_empty());
+
+    // A truncated varint doesn't advance the cursor.
+    let buf_list: BufList = vec![&b"\x05\xff"[..], &b"\xff"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list);
+    assert_eq!(cursor.read_varint_u64().unwrap(), 5);
+    let err = cursor.read_varint_u64().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(cursor.position(), 1);
+
+    // Neither does one that overflows a u64.
+    let buf_list = BufList::from(&b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"[..]);
+    let mut cursor = Cursor::new(&buf_list);
+    let err = cursor.read_varint_u64().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(cursor.position(), 0);
+}
+
+#[test]
+fn test_cursor_buf() {
+    use buf_list::Cursor;
+    use std::io::{IoSlice, Seek, SeekFrom};
+
+    let buf_list: BufList = vec![&b"hello"[..], &b" "[..], &b"world"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(3);
+
+    let mut buf = cursor.as_buf();
+    assert_eq!(buf.remaining(), 8);
+    assert_eq!(buf.chunk(), b"lo");
+
+    let mut iovs = [IoSlice::new(&[]); 4];
+    assert_eq!(buf.chunks_vectored(&mut iovs), 3);
+    assert_eq!(&*iovs[0], b"lo");
+    assert_eq!(&*iovs[1], b" ");
+    assert_eq!(&*iovs[2], b"world");
+    let mut iovs = [IoSlice::new(&[]); 2];
+    assert_eq!(buf.chunks_vectored(&mut iovs), 2);
+
+    buf.advance(3);
+    assert_eq!(buf.position(), 6);
+    assert_eq!(buf.chunk(), b"world");
+    // Within a single chunk, copy_to_bytes doesn't copy.
+    let bytes = buf.copy_to_bytes(3);
+    assert_eq!(bytes, &b"wor"[..]);
+    assert_eq!(bytes.as_ptr(), buf_list.get_chunk(2).unwrap()[..].as_ptr());
+    // Advancing the Buf advances the cursor.
+    assert_eq!(cursor.position(), 9);
+
+    // Any Buf-consuming API can be used, and the list is left untouched.
+    cursor.seek(SeekFrom::Start(0)).unwrap();
+    let mut out = Vec::new();
+    out.put(cursor.as_buf());
+    assert_eq!(out, b"hello world");
+    assert_eq!(buf_list.num_bytes(), 11);
+    assert_eq!(cursor.position(), 11);
+
+    let buf = cursor.as_buf();
+    assert!(!buf.has_remaining());
+    assert_eq!(buf.chunk(), b"");
+    let mut iovs = [IoSlice::new(&[]); 2];
+    assert_eq!(buf.chunks_vectored(&mut iovs), 0);
+}
+
+#[test]
+#[should_panic = "cannot advance past `remaining`: 4 <= 3"]
+fn test_cursor_buf_advance_past_end() {
+    use buf_list::Cursor;
+
+    let mut cursor = Cursor::new(BufList::from(&b"abc"[..]));
+    cursor.as_buf().advance(4);
+}
+
+#[test]
+fn test_cursor_take() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, ErrorKind, Read, Seek, SeekFrom};
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..], &b"hij"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(2);
+
+    let mut take = cursor.take_window(5);
+    assert_eq!(take.limit(), 5);
+    assert_eq!(take.fill_buf().unwrap(), b"c");
+    take.consume(1);
+    assert_eq!(take.fill_buf().unwrap(), b"defg");
+    take.consume(10);
+    assert_eq!(take.position(), 5);
+    assert_eq!(take.fill_buf().unwrap(), b"");
+
+    // Seeks are relative to the window and clamped to it.
+    assert_eq!(take.seek(SeekFrom::Start(1)).unwrap(), 1);
+    let mut buf = [0; 8];
+    assert_eq!(take.read(&mut buf).unwrap(), 4);
+    assert_eq!(&buf[..4], b"defg");
+    assert_eq!(take.read(&mut buf).unwrap(), 0);
+    assert_eq!(take.seek(SeekFrom::Current(-3)).unwrap(), 2);
+    assert_eq!(take.seek(SeekFrom::End(4)).unwrap(), 5);
+    assert_eq!(take.seek(SeekFrom::Start(100)).unwrap(), 5);
+    let err = take.seek(SeekFrom::Current(-6)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    take.seek(SeekFrom::Start(0)).unwrap();
+    let err = take.read_exact(&mut [0; 6]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    let mut buf = [0; 5];
+    take.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"cdefg");
+
+    // The underlying cursor is positioned at the end of the window.
+    let mut cursor = take.into_inner();
+    assert_eq!(cursor.position(), 7);
+    let mut rest = Vec::new();
+    cursor.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"hij");
+
+    // Windows past the end of the list are clamped to it.
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(8);
+    let mut take = cursor.take_window(10);
+    assert_eq!(take.seek(SeekFrom::End(0)).unwrap(), 2);
+    take.seek(SeekFrom::Start(0)).unwrap();
+    let mut rest = Vec::new();
+    take.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"ij");
+
+    // Read::take is still reachable through method syntax.
+    let mut io_take: std::io::Take<Cursor<&BufList>> = Cursor::new(&buf_list).take(4);
+    let mut out = String::new();
+    io_take.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "abcd");
+}
+
+#[test]
+fn test_cursor_chain() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, ErrorKind, Read, Seek, SeekFrom};
+
+    let first: BufList = vec![&b"abc"[..], &b"de"[..]].into_iter().collect();
+    let second: BufList = vec![&b"fg"[..], &b"hij"[..]].into_iter().collect();
+
+    let mut first_cursor = Cursor::new(&first);
+    first_cursor.set_position(1);
+    let mut chain = first_cursor.chain_cursor(Cursor::new(&second));
+    assert_eq!(chain.total_len(), 10);
+    assert_eq!(chain.position(), 1);
+
+    // read_exact spans the boundary between the lists.
+    let mut buf = [0; 6];
+    chain.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"bcdefg");
+    assert_eq!(chain.position(), 7);
+    assert_eq!(chain.fill_buf().unwrap(), b"hij");
+
+    // Seek back into the first list.
+    assert_eq!(chain.seek(SeekFrom::Current(-4)).unwrap(), 3);
+    assert_eq!(chain.fill_buf().unwrap(), b"de");
+    let mut rest = Vec::new();
+    chain.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"defghij");
+    assert_eq!(chain.position(), 10);
+
+    assert_eq!(chain.seek(SeekFrom::Start(5)).unwrap(), 5);
+    assert_eq!(chain.fill_buf().unwrap(), b"fg");
+    assert_eq!(chain.seek(SeekFrom::End(2)).unwrap(), 12);
+    assert_eq!(chain.fill_buf().unwrap(), b"");
+    let err = chain.seek(SeekFrom::End(-11)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+
+    chain.seek(SeekFrom::Start(8)).unwrap();
+    let err = chain.read_exact(&mut [0; 3]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(chain.position(), 8);
+
+    let (first_cursor, second_cursor) = chain.into_inner();
+    assert_eq!(first_cursor.position(), 5);
+    assert_eq!(second_cursor.position(), 3);
+
+    // Read::chain is still reachable through method syntax.
+    let mut io_chain: std::io::Chain<Cursor<&BufList>, std::io::Empty> =
+        Cursor::new(&first).chain(std::io::empty());
+    let mut out = String::new();
+    io_chain.read_to_string(&mut out).unwrap();
+    assert_eq!(out, "abcde");
+
+    // read_exact reads everything it needs from the first cursor, even if its reads are short.
+    let hello_world: BufList = vec![&b"hello"[..], &b"world"[..]].into_iter().collect();
+    let xyz = BufList::from(&b"XYZ"[..]);
+    let mut chain = Cursor::new(&hello_world)
+        .with_max_read_size(2)
+        .chain_cursor(Cursor::new(&xyz));
+    let mut buf = [0; 6];
+    chain.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hellow");
+    let mut buf = [0; 6];
+    chain.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"orldXY");
+    assert_eq!(chain.position(), 12);
+}
+
+#[test]
+fn test_cursor_slice() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, Read, Seek, SeekFrom};
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..], &b"hij"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(9);
+
+    let mut slice = cursor.slice(2..8);
+    assert_eq!(slice.total_len(), 6);
+    assert_eq!(slice.fill_buf().unwrap(), b"c");
+    assert_eq!(slice.seek(SeekFrom::End(-1)).unwrap(), 5);
+    assert_eq!(slice.fill_buf().unwrap(), b"h");
+    assert_eq!(slice.seek(SeekFrom::Start(1)).unwrap(), 1);
+    let mut rest = Vec::new();
+    slice.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"defgh");
+
+    // The original cursor isn't moved.
+    assert_eq!(cursor.position(), 9);
+    assert_eq!(cursor.slice(..).total_len(), 10);
+    assert!(cursor.slice(10..).is_empty());
+}
+
+#[test]
+fn test_read_exact_at() {
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..]].into_iter().collect();
+
+    let mut buf = [0; 3];
+    buf_list.read_exact_at(2, &mut buf).unwrap();
+    assert_eq!(&buf, b"cde");
+    buf_list.read_exact_at(4, &mut buf).unwrap();
+    assert_eq!(&buf, b"efg");
+    let err = buf_list.read_exact_at(5, &mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    let err = buf_list.read_exact_at(100, &mut buf).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    buf_list.read_exact_at(100, &mut []).unwrap();
+}
+
+#[cfg(feature = "positioned-io02")]
+#[test]
+fn test_positioned_io() {
+    use positioned_io_02::{ReadAt, Size};
+    use std::sync::Arc;
+
+    let buf_list: Arc<BufList> = Arc::new(vec![&b"hello"[..], &b"world"[..]].into_iter().collect());
+    assert_eq!(Size::size(&*buf_list).unwrap(), Some(10));
+
+    // Several threads can read from the same list without any locking.
+    let handles: Vec<_> = (0..4u64)
+        .map(|i| {
+            let buf_list = buf_list.clone();
+            std::thread::spawn(move || {
+                let mut buf = [0; 3];
+                ReadAt::read_exact_at(&*buf_list, i * 2, &mut buf).unwrap();
+                buf
+            })
+        })
+        .collect();
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(results, vec![*b"hel", *b"llo", *b"owo", *b"orl"]);
+
+    let mut buf = [0; 4];
+    assert_eq!(ReadAt::read_at(&*buf_list, 8, &mut buf).unwrap(), 2);
+    assert!(ReadAt::read_exact_at(&*buf_list, 8, &mut buf).is_err());
+}
+
+#[test]
+fn test_cursor_seek_to_chunk() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, ErrorKind, Read};
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..], &b"fghi"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+
+    assert_eq!(cursor.seek_to_chunk(2).unwrap(), 5);
+    assert_eq!(cursor.chunk_index(), 2);
+    assert_eq!(cursor.fill_buf().unwrap(), b"fghi");
+    assert_eq!(cursor.seek_to_chunk(1).unwrap(), 3);
+    let mut buf = [0; 3];
+    cursor.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"def");
+    assert_eq!(cursor.chunk_index(), 2);
+
+    assert_eq!(cursor.seek_to_chunk(3).unwrap(), 9);
+    assert_eq!(cursor.chunk_index(), 3);
+    assert!(cursor.is_empty());
+
+    let err = cursor.seek_to_chunk(4).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert_eq!(cursor.position(), 9);
+
+    // Past the end of the list, the chunk index is the number of chunks.
+    cursor.set_position(20);
+    assert_eq!(cursor.chunk_index(), 3);
+    assert_eq!(cursor.seek_to_chunk(0).unwrap(), 0);
+    assert_eq!(cursor.fill_buf().unwrap(), b"abc");
+}
+
+#[test]
+fn test_cursor_fill_buf_at_least() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, ErrorKind};
+
+    let buf_list: BufList = vec![&b"ab"[..], &b"c"[..], &b"defgh"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+
+    // Within a chunk, the rest of the chunk is borrowed.
+    let buf = cursor.fill_buf_at_least(1).unwrap();
+    assert_eq!(buf, b"ab");
+    assert_eq!(buf.as_ptr(), buf_list.get_chunk(0).unwrap().as_ptr());
+    assert_eq!(cursor.fill_buf_at_least(0).unwrap(), b"ab");
+
+    // Across chunks, exactly n bytes are copied.
+    assert_eq!(cursor.fill_buf_at_least(4).unwrap(), b"abcd");
+    assert_eq!(cursor.position(), 0);
+    cursor.consume(1);
+    assert_eq!(cursor.fill_buf_at_least(7).unwrap(), b"bcdefgh");
+
+    let err = cursor.fill_buf_at_least(8).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    cursor.consume(2);
+    assert_eq!(cursor.fill_buf_at_least(3).unwrap(), b"defgh");
+
+    cursor.set_position(8);
+    assert_eq!(cursor.fill_buf_at_least(0).unwrap(), b"");
+    assert!(cursor.fill_buf_at_least(1).is_err());
+}
+
+#[test]
+fn test_cursor_read_to_end() {
+    use buf_list::Cursor;
+    use std::io::Read;
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"defg"[..], &b"hij"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(2);
+
+    let mut buf = Vec::new();
+    assert_eq!(cursor.read_to_end(&mut buf).unwrap(), 8);
+    assert_eq!(buf, b"cdefghij");
+    // Exactly the right amount of space is reserved.
+    assert_eq!(buf.capacity(), 8);
+
+    assert_eq!(cursor.read_to_end(&mut buf).unwrap(), 0);
+    cursor.set_position(20);
+    assert_eq!(cursor.read_to_end(&mut buf).unwrap(), 0);
+    assert_eq!(buf, b"cdefghij");
+}
+
+#[test]
+fn test_cursor_read_to_string() {
+    use buf_list::Cursor;
+    use std::io::{ErrorKind, Read};
+
+    // "héllo wörld", with code points split across chunks.
+    let buf_list: BufList = vec![
+        &b"h\xc3"[..],
+        &b"\xa9llo w"[..],
+        &b"\xc3"[..],
+        &b"\xb6rld"[..],
+    ]
+    .into_iter()
+    .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    let mut s = String::from(">");
+    assert_eq!(cursor.read_to_string(&mut s).unwrap(), 13);
+    assert_eq!(s, ">héllo wörld");
+
+    // Starting in the middle of a code point is invalid.
+    cursor.set_position(2);
+    let err = cursor.read_to_string(&mut s).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(s, ">héllo wörld");
+    assert_eq!(cursor.position(), 2);
+
+    // So is ending in the middle of one.
+    let buf_list: BufList = vec![&b"ab"[..], &b"\xc3"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list);
+    let mut s = String::new();
+    assert!(cursor.read_to_string(&mut s).is_err());
+    assert_eq!(s, "");
+}
+
+#[test]
+fn test_cursor_read_vectored() {
+    use buf_list::Cursor;
+    use std::io::{IoSliceMut, Read};
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..], &b"fghij"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+    cursor.set_position(1);
+
+    let (mut a, mut b, mut c, mut d) = ([0; 4], [0; 0], [0; 3], [0; 4]);
+    let mut bufs = [
+        IoSliceMut::new(&mut a),
+        IoSliceMut::new(&mut b),
+        IoSliceMut::new(&mut c),
+        IoSliceMut::new(&mut d),
+    ];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap(), 9);
+    assert_eq!(&a, b"bcde");
+    assert_eq!(&c, b"fgh");
+    assert_eq!(&d[..2], b"ij");
+    assert_eq!(cursor.position(), 10);
+    assert_eq!(cursor.chunk_index(), 3);
+
+    let mut bufs = [IoSliceMut::new(&mut a)];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap(), 0);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_cursor_stream() {
+    use buf_list::Cursor;
+    use futures::{Stream, TryStreamExt};
+    use std::io::{Seek, SeekFrom};
+
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..], &b"fghij"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+
+    // Seek, then stream the rest of the list from there.
+    cursor.seek(SeekFrom::Start(4)).unwrap();
+    assert_eq!(cursor.size_hint(), (2, Some(2)));
+    let chunks: Vec<Bytes> =
+        futures::executor::block_on(TryStreamExt::try_collect(&mut cursor)).unwrap();
+    assert_eq!(chunks, vec![&b"e"[..], &b"fghij"[..]]);
+    assert_eq!(cursor.position(), 10);
+    assert_eq!(cursor.size_hint(), (0, Some(0)));
+
+    cursor.set_position(20);
+    let chunks: Vec<Bytes> =
+        futures::executor::block_on(TryStreamExt::try_collect(&mut cursor)).unwrap();
+    assert!(chunks.is_empty());
+}
+
+#[test]
+fn test_cursor_arc_shares_index() {
+    use buf_list::Cursor;
+    use std::{io::Read, sync::Arc};
+
+    let buf_list: BufList = (0..64u8).map(|i| Bytes::from(vec![i; 4])).collect();
+    let cursor = Cursor::new(Arc::new(buf_list));
+
+    // Clones share the list, so seeking in one builds the index for all of them.
+    let mut clones: Vec<_> = (0..4).map(|_| cursor.clone()).collect();
+    for (i, clone) in clones.iter_mut().enumerate() {
+        assert!(Arc::ptr_eq(clone.get_ref(), cursor.get_ref()));
+        clone.set_position(i as u64 * 64 + 2);
+        let mut buf = [0; 4];
+        clone.read_exact(&mut buf).unwrap();
+        let chunk = i as u8 * 16;
+        assert_eq!(buf, [chunk, chunk, chunk + 1, chunk + 1]);
+    }
+    assert_eq!(cursor.position(), 0);
+}
+
+#[test]
+fn test_cursor_position_of() {
+    use buf_list::Cursor;
+
+    let buf_list: BufList = vec![&b"ab|c"[..], &b"d|"[..], &b"|ef"[..]]
+        .into_iter()
+        .collect();
+    let mut cursor = Cursor::new(&buf_list);
+
+    assert_eq!(cursor.position_of(b'|'), Some(2));
+    assert_eq!(cursor.position_of_slice(b"||"), Some(5));
+    assert_eq!(cursor.position_of_slice(b"cd|"), Some(3));
+
+    cursor.set_position(3);
+    assert_eq!(cursor.position_of(b'|'), Some(5));
+    assert_eq!(cursor.position_of(b'a'), None);
+    assert_eq!(cursor.position_of_slice(b""), Some(3));
+    assert_eq!(cursor.position_of_slice(b"ab"), None);
+    // The cursor isn't moved.
+    assert_eq!(cursor.position(), 3);
+
+    cursor.set_position(9);
+    assert_eq!(cursor.position_of(b'f'), None);
+    assert_eq!(cursor.position_of_slice(b""), Some(9));
+    cursor.set_position(10);
+    assert_eq!(cursor.position_of_slice(b""), None);
+}
+
+#[test]
+fn test_cursor_max_read_size() {
+    use buf_list::Cursor;
+    use std::io::{BufRead, IoSliceMut, Read};
+
+    let buf_list: BufList = vec![&b"hello"[..], &b" world"[..]].into_iter().collect();
+    let mut cursor = Cursor::new(&buf_list).with_max_read_size(3);
+
+    let mut buf = [0; 8];
+    assert_eq!(cursor.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"hel");
+    assert_eq!(cursor.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"lo ");
+    assert_eq!(cursor.fill_buf().unwrap(), b"wor");
+
+    let (mut a, mut b) = ([0; 2], [0; 8]);
+    let mut bufs = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)];
+    assert_eq!(cursor.read_vectored(&mut bufs).unwrap(), 2);
+    assert_eq!(&a, b"wo");
+
+    // Clones keep the limit.
+    let mut clone = cursor.clone();
+    assert_eq!(clone.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"rld");
+
+    // Reads that fill the whole buffer aren't affected.
+    let mut s = String::new();
+    cursor.read_to_string(&mut s).unwrap();
+    assert_eq!(s, "rld");
+}
+
+#[cfg(feature = "tokio1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_cursor_max_read_size_tokio() {
+    use buf_list::Cursor;
+    use tokio::io::AsyncReadExt;
+
+    let buf_list = BufList::from(&b"hello world"[..]);
+    let mut cursor = Cursor::new(&buf_list).with_max_read_size(4);
+
+    let mut buf = [0; 8];
+    assert_eq!(cursor.read(&mut buf).await.unwrap(), 4);
+    assert_eq!(&buf[..4], b"hell");
+    cursor.read_exact(&mut buf[..7]).await.unwrap();
+    assert_eq!(&buf[..7], b"o world");
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_into_stream() {
+    use futures::{executor::block_on, stream::FusedStream, Stream, StreamExt};
+
+    let buf_list: BufList = vec![&b"ab"[..], &b"cde"[..], &b"f"[..]]
+        .into_iter()
+        .collect();
+    let mut stream = buf_list.into_stream();
+    assert_eq!(stream.size_hint(), (3, Some(3)));
+    assert!(!stream.is_terminated());
+
+    assert_eq!(block_on(stream.next()), Some(Bytes::from_static(b"ab")));
+    assert_eq!(stream.size_hint(), (2, Some(2)));
+
+    let rest: Vec<_> = block_on(stream.by_ref().collect());
+    assert_eq!(rest, vec![&b"cde"[..], &b"f"[..]]);
+    assert!(stream.is_terminated());
+    assert_eq!(block_on(stream.next()), None);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_into_try_stream() {
+    use futures::{executor::block_on, stream::FusedStream, Stream, TryStreamExt};
+
+    #[derive(Debug)]
+    struct NotClone;
+
+    let buf_list: BufList = vec![&b"ab"[..], &b"cde"[..]].into_iter().collect();
+    let mut stream = buf_list.into_try_stream::<NotClone>();
+    assert_eq!(stream.size_hint(), (2, Some(2)));
+
+    // The error type doesn't need to implement Clone.
+    let clone = stream.clone();
+    assert_eq!(
+        block_on(stream.try_next()).unwrap(),
+        Some(Bytes::from_static(b"ab"))
+    );
+    assert_eq!(
+        block_on(stream.try_next()).unwrap(),
+        Some(Bytes::from_static(b"cde"))
+    );
+    assert_eq!(block_on(stream.try_next()).unwrap(), None);
+    assert!(stream.is_terminated());
+
+    let buf_list: BufList = block_on(clone.try_collect()).unwrap();
+    assert_eq!(buf_list.to_str_lossy(), "abcde");
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_try_collect_limited() {
+    use buf_list::TryCollectLimitedError;
+    use futures::{executor::block_on, stream, StreamExt};
+
+    let chunks = || {
+        vec![
+            Ok(&b"hello"[..]),
+            Ok(&b" "[..]),
+            Err("error"),
+            Ok(&b"world"[..]),
+        ]
+    };
+
+    // The limit is checked before the error is reached.
+    let err = block_on(BufList::try_collect_limited(stream::iter(chunks()), 5)).unwrap_err();
+    match err {
+        TryCollectLimitedError::LimitExceeded(err) => {
+            assert_eq!(err.limit(), 5);
+            assert_eq!(err.attempted(), 6);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    let err = block_on(BufList::try_collect_limited(stream::iter(chunks()), 6)).unwrap_err();
+    assert_eq!(err, TryCollectLimitedError::Stream("error"));
+
+    let mut stream = stream::iter(chunks()).filter(|chunk| futures::future::ready(chunk.is_ok()));
+    let buf_list = block_on(BufList::try_collect_limited(&mut stream, 11)).unwrap();
+    assert_eq!(buf_list.to_str_lossy(), "hello world");
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_try_collect_coalesced() {
+    use futures::{executor::block_on, stream};
+
+    let large = Bytes::from(vec![b'x'; 16]);
+    let chunks = vec![
+        Ok::<_, ()>(Bytes::from_static(b"ab")),
+        Ok(Bytes::from_static(b"cd")),
+        Ok(large.clone()),
+        Ok(Bytes::from_static(b"ef")),
+    ];
+    let buf_list = block_on(BufList::try_collect_coalesced(stream::iter(chunks), 4)).unwrap();
+    assert_eq!(buf_list.num_bytes(), 22);
+    assert_eq!(buf_list.num_chunks(), 3);
+    assert_eq!(buf_list.get_chunk(0).unwrap(), &b"abcd"[..]);
+    // Chunks that are large enough aren't copied.
+    assert_eq!(buf_list.get_chunk(1).unwrap().as_ptr(), large.as_ptr());
+    assert_eq!(buf_list.get_chunk(2).unwrap(), &b"ef"[..]);
+
+    let chunks = vec![Ok(Bytes::from_static(b"ab")), Err("error")];
+    let err = block_on(BufList::try_collect_coalesced(stream::iter(chunks), 4)).unwrap_err();
+    assert_eq!(err, "error");
+}
+
+#[cfg(feature = "tokio1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_tokio_async_read() {
+    use tokio::io::AsyncReadExt;
+
+    let mut buf_list: BufList = vec![&b"hello"[..], &b" "[..], &b"world"[..]]
+        .into_iter()
+        .collect();
+
+    let mut buf = [0; 7];
+    buf_list.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"hello w");
+    // The chunks that were read are consumed.
+    assert_eq!(buf_list.num_chunks(), 1);
+    assert_eq!(buf_list.num_bytes(), 4);
+
+    let mut s = String::new();
+    buf_list.read_to_string(&mut s).await.unwrap();
+    assert_eq!(s, "orld");
+    assert_eq!(buf_list.num_chunks(), 0);
+    assert_eq!(buf_list.read(&mut buf).await.unwrap(), 0);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_futures_async_read() {
+    use futures::io::AsyncReadExt;
+
+    futures::executor::block_on(async {
+        let mut buf_list: BufList = vec![&b"hello"[..], &b" "[..], &b"world"[..]]
+            .into_iter()
+            .collect();
+
+        let mut buf = [0; 7];
+        assert_eq!(buf_list.read(&mut buf).await.unwrap(), 7);
+        assert_eq!(&buf, b"hello w");
+        // The chunks that were read are consumed.
+        assert_eq!(buf_list.num_chunks(), 1);
+        assert_eq!(buf_list.num_bytes(), 4);
+
+        let mut s = String::new();
+        buf_list.read_to_string(&mut s).await.unwrap();
+        assert_eq!(s, "orld");
+        assert_eq!(buf_list.num_chunks(), 0);
+        assert_eq!(buf_list.read(&mut buf).await.unwrap(), 0);
+    });
+}
+
+#[test]
+fn test_into_reader() {
+    use std::io::{BufRead, Read};
+
+    let buf_list: BufList = vec![&b"hello"[..], &b" "[..], &b"world"[..]]
+        .into_iter()
+        .collect();
+    let mut reader = buf_list.into_reader();
+
+    let mut buf = [0; 7];
+    assert_eq!(reader.read(&mut buf).unwrap(), 7);
+    assert_eq!(&buf, b"hello w");
+    assert_eq!(reader.remaining(), 4);
+    assert_eq!(reader.get_ref().num_chunks(), 1);
+
+    assert_eq!(reader.fill_buf().unwrap(), b"orld");
+    // Consuming more than is left is clamped.
+    reader.consume(2);
+    assert_eq!(reader.fill_buf().unwrap(), b"ld");
+    reader.consume(10);
+    assert_eq!(reader.fill_buf().unwrap(), b"");
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.into_inner().num_chunks(), 0);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_into_reader_futures() {
+    use futures::io::{AsyncBufReadExt, AsyncReadExt};
+
+    futures::executor::block_on(async {
+        let buf_list: BufList = vec![&b"hello\n"[..], &b"wor"[..], &b"ld"[..]]
+            .into_iter()
+            .collect();
+        let mut reader = buf_list.into_reader();
+
+        let mut line = String::new();
+        reader.read_line(&mut line).await.unwrap();
+        assert_eq!(line, "hello\n");
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).await.unwrap();
+        assert_eq!(rest, b"world");
+    });
+}
+
+#[cfg(feature = "tokio1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_into_reader_tokio() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let buf_list: BufList = vec![&b"hello\n"[..], &b"wor"[..], &b"ld"[..]]
+        .into_iter()
+        .collect();
+    let mut reader = buf_list.into_reader();
+
+    let mut line = String::new();
+    reader.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "hello\n");
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).await.unwrap();
+    assert_eq!(rest, b"world");
+}
+
+#[cfg(feature = "tokio1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_pipe_tokio() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+    let (mut writer, mut reader) = buf_list::pipe(8);
+    assert_eq!(writer.capacity(), 8);
+
+    // Writes are cut short at the capacity.
+    assert_eq!(writer.write(b"hello world").await.unwrap(), 8);
+    let chunk = Bytes::from_static(b"0123456789");
+    let producer = tokio::spawn(async move {
+        // This chunk is larger than the capacity, so it's added once the pipe is empty.
+        writer.push_chunk(chunk).await.unwrap();
+        writer.write_all(b"!\n").await.unwrap();
+        writer.shutdown().await.unwrap();
+        assert!(writer.write(b"x").await.is_err());
+    });
+
+    assert_eq!(reader.fill_buf().await.unwrap(), b"hello wo");
+    reader.consume(6);
+    let mut buf = [0; 4];
+    assert_eq!(reader.read(&mut buf).await.unwrap(), 2);
+    assert_eq!(&buf[..2], b"wo");
+
+    let mut line = String::new();
+    reader.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "0123456789!\n");
+    assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+    producer.await.unwrap();
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_pipe_stream() {
+    use futures::{executor::block_on, io::AsyncWriteExt, StreamExt};
+
+    let (mut writer, mut reader) = buf_list::pipe(16);
+    let chunk = Bytes::from_static(b"hello");
+    block_on(async {
+        writer.push_chunk(chunk.clone()).await.unwrap();
+        writer.write_all(b" world").await.unwrap();
+        drop(writer);
+
+        // Chunks pushed into the pipe come out of it without being copied.
+        let first = reader.next().await.unwrap();
+        assert_eq!(first.as_ptr(), chunk.as_ptr());
+        assert_eq!(reader.next().await.unwrap(), &b" world"[..]);
+        assert_eq!(reader.next().await, None);
+    });
+
+    // Dropping the reader breaks the pipe.
+    let (mut writer, reader) = buf_list::pipe(16);
+    drop(reader);
+    let err = block_on(writer.write(b"hello")).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_broadcast() {
+    use futures::executor::block_on;
+    use std::{future::Future, pin::Pin, task::Context};
+
+    let (mut writer, mut reader) = buf_list::broadcast();
+    assert_eq!(reader.try_next_chunk(), None);
+
+    writer.push_chunk(&b"hello"[..]);
+    writer.push_chunk(&b""[..]);
+    writer.push_chunk(&b" world"[..]);
+    assert_eq!(writer.num_bytes(), 11);
+
+    assert_eq!(
+        block_on(reader.next_chunk()),
+        Some(Bytes::from_static(b"hello"))
+    );
+    assert_eq!(reader.chunk_index(), 1);
+
+    // Clones start at the same position, and new subscribers start at the beginning.
+    let mut clone = reader.clone();
+    let mut subscriber = writer.subscribe();
+    assert_eq!(clone.try_next_chunk(), Some(Bytes::from_static(b" world")));
+    assert_eq!(
+        subscriber.try_next_chunk(),
+        Some(Bytes::from_static(b"hello"))
+    );
+
+    // Chunks are shared rather than copied.
+    let chunk = Bytes::from_static(b"!");
+    writer.push_chunk(chunk.clone());
+    assert_eq!(writer.to_buf_list().to_str_lossy(), "hello world!");
+    assert_eq!(reader.try_next_chunk(), Some(Bytes::from_static(b" world")));
+    assert_eq!(reader.try_next_chunk().unwrap().as_ptr(), chunk.as_ptr());
+
+    // Readers wait for more chunks until the writer is dropped.
+    let waker = dummy_waker::dummy_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut next = reader.next_chunk();
+    assert!(Pin::new(&mut next).poll(&mut cx).is_pending());
+    drop(writer);
+    assert_eq!(block_on(next), None);
+    assert_eq!(block_on(clone.next_chunk()), Some(Bytes::from_static(b"!")));
+    assert_eq!(block_on(clone.next_chunk()), None);
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_broadcast_stream() {
+    use futures::{executor::block_on, StreamExt};
+
+    let (mut writer, reader) = buf_list::broadcast();
+    let readers: Vec<_> = (0..3).map(|_| reader.clone()).collect();
+    writer.push_chunk(&b"hello"[..]);
+    writer.push_chunk(&b" world"[..]);
+    drop(writer);
+
+    for reader in readers {
+        let buf_list: BufList = block_on(reader.collect());
+        assert_eq!(buf_list.to_str_lossy(), "hello world");
+    }
+}
+
+#[cfg(feature = "tokio1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_broadcast_async_read() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let (mut writer, mut reader) = buf_list::broadcast();
+    let mut other = reader.clone();
+    let producer = tokio::spawn(async move {
+        for line in &["hello\n", "wor", "ld\n"] {
+            writer.push_chunk(line.as_bytes());
+            tokio::task::yield_now().await;
+        }
+        writer.finish();
+    });
+
+    // Reads wait for more data rather than returning end-of-file.
+    let mut line = String::new();
+    reader.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "hello\n");
+    line.clear();
+    reader.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "world\n");
+    assert_eq!(reader.read(&mut [0; 4]).await.unwrap(), 0);
+    producer.await.unwrap();
+
+    // Partly read chunks are picked up by next_chunk.
+    let mut buf = [0; 2];
+    other.read_exact(&mut buf).await.unwrap();
+    assert_eq!(other.next_chunk().await, Some(Bytes::from_static(b"llo\n")));
+
+    // Dropping the writer without finishing is an error.
+    let (writer, mut reader) = buf_list::broadcast();
+    drop(writer);
+    let err = reader.read(&mut buf).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[cfg(feature = "tokio-util07")]
+#[test]
+fn test_length_delimited_decoder() {
+    use buf_list::LengthDelimitedDecoder;
+    use tokio_util_07::codec::Decoder;
+
+    let mut decoder = LengthDelimitedDecoder::new().with_max_frame_length(8);
+    assert_eq!(decoder.max_frame_length(), 8);
+
+    // Feed in a frame a few bytes at a time.
+    let mut src = BytesMut::new();
+    src.extend_from_slice(b"\x00\x00");
+    assert!(decoder.decode(&mut src).unwrap().is_none());
+    src.extend_from_slice(b"\x00\x05he");
+    assert!(decoder.decode(&mut src).unwrap().is_none());
+    // The data that has arrived is moved out of the read buffer.
+    assert!(src.is_empty());
+    src.extend_from_slice(b"llo\x00\x00\x00\x00\x00\x00");
+    let frame = decoder.decode(&mut src).unwrap().unwrap();
+    assert_eq!(frame.num_chunks(), 2);
+    assert_eq!(frame.to_str_lossy(), "hello");
+
+    // Empty frames are decoded too.
+    let frame = decoder.decode(&mut src).unwrap().unwrap();
+    assert_eq!(frame.num_bytes(), 0);
+
+    // The stream ends partway through a frame.
+    assert!(decoder.decode(&mut src).unwrap().is_none());
+    let err = decoder.decode_eof(&mut src).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+
+    let mut decoder = LengthDelimitedDecoder::new().with_max_frame_length(8);
+    let mut src = BytesMut::from(&b"\x00\x00\x00\x09"[..]);
+    let err = decoder.decode(&mut src).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(decoder.decode_eof(&mut BytesMut::new()).unwrap().is_none());
+}
+
+#[cfg(feature = "futures03")]
+#[test]
+fn test_into_stream_chunked() {
+    use futures::{executor::block_on, stream::FusedStream, Stream, StreamExt};
+
+    let large = Bytes::from_static(b"0123456789");
+    let buf_list: BufList = vec![large.clone(), Bytes::from_static(b"ab")]
+        .into_iter()
+        .collect();
+    let mut stream = buf_list.into_stream_chunked(4);
+    assert_eq!(stream.max_frame(), 4);
+    assert_eq!(stream.size_hint(), (2, None));
+
+    // Large chunks are split without copying them.
+    let first = block_on(stream.next()).unwrap();
+    assert_eq!(first, &b"0123"[..]);
+    assert_eq!(first.as_ptr(), large.as_ptr());
+    assert_eq!(stream.size_hint(), (3, None));
+
+    let rest: Vec<_> = block_on(stream.by_ref().collect());
+    assert_eq!(rest, vec![&b"4567"[..], &b"89"[..], &b"ab"[..]]);
+    assert!(stream.is_terminated());
+}
+
+#[cfg(feature = "tokio-util07")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_into_stream_reader() {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+
+    let buf_list: BufList = vec![&b"hello "[..], &b""[..], &b"world"[..]]
+        .into_iter()
+        .collect();
+    let mut reader = buf_list.into_stream_reader();
+
+    assert_eq!(reader.fill_buf().await.unwrap(), b"hello ");
+    reader.consume(2);
+    let mut s = String::new();
+    reader.read_to_string(&mut s).await.unwrap();
+    assert_eq!(s, "llo world");
+
+    // The underlying stream has been exhausted.
+    let mut buf = [0; 4];
+    assert_eq!(reader.read(&mut buf).await.unwrap(), 0);
+}
+
+#[cfg(feature = "http-body04")]
+#[test]
+fn test_http_body04() {
+    use futures::{executor::block_on, future::poll_fn};
+    use http_body_04::Body;
+    use std::pin::Pin;
+
+    let hello = Bytes::from_static(b"hello ");
+    let mut buf_list: BufList = vec![hello.clone(), Bytes::from_static(b"world")]
+        .into_iter()
+        .collect();
+    assert!(!buf_list.is_end_stream());
+    assert_eq!(Body::size_hint(&buf_list).exact(), Some(11));
+
+    // Each chunk is returned without copying it.
+    let data = block_on(poll_fn(|cx| Pin::new(&mut buf_list).poll_data(cx)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(data.as_ptr(), hello.as_ptr());
+    assert_eq!(Body::size_hint(&buf_list).exact(), Some(5));
+
+    let data = block_on(poll_fn(|cx| Pin::new(&mut buf_list).poll_data(cx)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(data, "world");
+    assert!(buf_list.is_end_stream());
+    assert!(block_on(poll_fn(|cx| Pin::new(&mut buf_list).poll_data(cx))).is_none());
+    let trailers = block_on(poll_fn(|cx| Pin::new(&mut buf_list).poll_trailers(cx)));
+    assert_eq!(trailers, Ok(None));
+}
+
+#[cfg(feature = "http-body1")]
+#[test]
+fn test_http_body1() {
+    use buf_list::BufListBody;
+    use futures::{executor::block_on, future::poll_fn};
+    use http_1::{HeaderMap, HeaderValue};
+    use http_body_1::Body;
+    use std::pin::Pin;
+
+    let hello = Bytes::from_static(b"hello ");
+    let buf_list: BufList = vec![hello.clone(), Bytes::from_static(b"world")]
+        .into_iter()
+        .collect();
+
+    let mut body = buf_list.clone();
+    assert_eq!(Body::size_hint(&body).exact(), Some(11));
+    let frame = block_on(poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)))
+        .unwrap()
+        .unwrap();
+    // Each chunk is returned without copying it.
+    assert_eq!(frame.into_data().unwrap().as_ptr(), hello.as_ptr());
+    assert!(!body.is_end_stream());
+    let frame = block_on(poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame.into_data().unwrap(), "world");
+    assert!(body.is_end_stream());
+    assert!(block_on(poll_fn(|cx| Pin::new(&mut body).poll_frame(cx))).is_none());
+
+    // Trailers are sent after the data.
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static("0"));
+    let mut body = BufListBody::new(buf_list).with_trailers(trailers.clone());
+    let mut frames = Vec::new();
+    while let Some(frame) = block_on(poll_fn(|cx| Pin::new(&mut body).poll_frame(cx))) {
+        frames.push(frame.unwrap());
+    }
+    assert_eq!(frames.len(), 3);
+    assert!(frames[1].is_data());
+    assert_eq!(frames[2].trailers_ref(), Some(&trailers));
+    assert!(body.is_end_stream());
+    assert!(body.trailers().is_none());
+
+    let body = BufListBody::from(BufList::new());
+    assert!(body.is_end_stream());
+}
+
+#[cfg(feature = "http-body1")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_from_body() {
+    use buf_list::BufListBody;
+    use http_1::HeaderMap;
+
+    let hello = Bytes::from_static(b"hello ");
+    let buf_list: BufList = vec![hello.clone(), Bytes::from_static(b"world")]
+        .into_iter()
+        .collect();
+    let body = BufListBody::new(buf_list).with_trailers(HeaderMap::new());
+
+    // Chunk boundaries are preserved, and chunks aren't copied.
+    let buf_list = BufList::from_body(body).await.unwrap();
+    assert_eq!(buf_list.num_chunks(), 2);
+    assert_eq!(buf_list.get_chunk(0).unwrap().as_ptr(), hello.as_ptr());
+    assert_eq!(buf_list.to_str_lossy(), "hello world");
+}
+
+#[cfg(feature = "hyper014")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_hyper014_body() {
+    use http_body_04::Body;
+
+    let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    let body = hyper_014::Body::from(buf_list);
+    let bytes = hyper_014::body::to_bytes(body).await.unwrap();
+    assert_eq!(bytes, "hello world");
+
+    // A single chunk keeps its length.
+    let buf_list: BufList = vec![&b"hello"[..]].into_iter().collect();
+    let body = hyper_014::Body::from(buf_list);
+    assert_eq!(body.size_hint().exact(), Some(5));
+    let bytes = hyper_014::body::to_bytes(body).await.unwrap();
+    assert_eq!(bytes, "hello");
+}
+
+#[cfg(feature = "axum08")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_axum_into_response() {
+    use axum_core_05::response::IntoResponse;
+    use buf_list::BufListBody;
+    use http_1::HeaderMap;
+    use http_body_1::Body;
+
+    let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    let response = buf_list.clone().into_response();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/octet-stream"
+    );
+    assert_eq!(response.body().size_hint().exact(), Some(11));
+
+    // Chunk boundaries are preserved through the response body.
+    let collected = BufList::from_body(response.into_body()).await.unwrap();
+    assert_eq!(collected.num_chunks(), 2);
+    assert_eq!(collected.to_str_lossy(), "hello world");
+
+    let response = BufListBody::new(buf_list)
+        .with_trailers(HeaderMap::new())
+        .into_response();
+    assert_eq!(response.body().size_hint().exact(), Some(11));
+}
+
+#[cfg(feature = "actix-web4")]
+#[test]
+fn test_actix_message_body() {
+    use actix_http_3::body::{BodySize, MessageBody};
+    use futures::{executor::block_on, future::poll_fn};
+    use std::pin::Pin;
+
+    let hello = Bytes::from_static(b"hello ");
+    let mut buf_list: BufList = vec![hello.clone(), Bytes::from_static(b"world")]
+        .into_iter()
+        .collect();
+    assert_eq!(buf_list.size(), BodySize::Sized(11));
+
+    // Lists with several chunks can't be turned into a single `Bytes` without copying.
+    let buf_list2 = buf_list.clone().try_into_bytes().unwrap_err();
+    assert_eq!(buf_list2.num_chunks(), 2);
+
+    // Each chunk is sent without copying it.
+    let chunk = block_on(poll_fn(|cx| Pin::new(&mut buf_list).poll_next(cx)))
+        .unwrap()
+        .unwrap();
+    assert_eq!(chunk.as_ptr(), hello.as_ptr());
+    assert_eq!(buf_list.size(), BodySize::Sized(5));
+
+    let bytes = buf_list.try_into_bytes().unwrap();
+    assert_eq!(bytes, "world");
+
+    let mut buf_list = BufList::new();
+    assert_eq!(buf_list.size(), BodySize::Sized(0));
+    assert!(block_on(poll_fn(|cx| Pin::new(&mut buf_list).poll_next(cx))).is_none());
+}
+
+#[cfg(feature = "reqwest012")]
+#[tokio::test(flavor = "current_thread")]
+async fn test_reqwest_body() {
+    use http_body_1::Body;
+
+    let hello = Bytes::from_static(b"hello ");
+    let buf_list: BufList = vec![hello.clone(), Bytes::from_static(b"world")]
+        .into_iter()
+        .collect();
+    let body = reqwest_012::Body::from(buf_list);
+    assert_eq!(body.size_hint().exact(), Some(11));
+
+    // The chunks are streamed out without copying them.
+    let buf_list = BufList::from_body(body).await.unwrap();
+    assert_eq!(buf_list.num_chunks(), 2);
+    assert_eq!(buf_list.get_chunk(0).unwrap().as_ptr(), hello.as_ptr());
+    assert_eq!(buf_list.to_str_lossy(), "hello world");
+}
+
+#[cfg(feature = "prost014")]
+#[test]
+fn test_encode_prost() {
+    use prost_014::Message;
+
+    // Small messages are encoded into a single chunk.
+    let msg = "hello".to_owned();
+    let buf_list = BufList::encode_prost(&msg);
+    assert_eq!(buf_list.num_chunks(), 1);
+    assert_eq!(String::decode(buf_list).unwrap(), msg);
+
+    // Large messages are split across chunks.
+    let msg = Bytes::from(vec![7; 20_000]);
+    let buf_list = BufList::encode_prost(&msg);
+    assert_eq!(buf_list.num_chunks(), 3);
+    assert_eq!(buf_list.num_bytes(), msg.encoded_len());
+    assert_eq!(Bytes::decode(buf_list).unwrap(), msg);
+
+    let msg = 1234_u32;
+    let mut buf_list = BufList::encode_prost_length_delimited(&msg);
+    buf_list.push_chunk(&b"trailing"[..]);
+    assert_eq!(u32::decode_length_delimited(&mut buf_list).unwrap(), msg);
+    assert_eq!(buf_list.to_str_lossy(), "trailing");
+}
+
+#[test]
+fn test_drain_chunks() {
+    let hello = Bytes::from_static(b"hello ");
+    let mut buf_list: BufList = vec![
+        hello.clone(),
+        Bytes::from_static(b"world"),
+        Bytes::from_static(b"!"),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(buf_list.drain_chunks(0), Vec::<Bytes>::new());
+    assert_eq!(buf_list.num_bytes(), 12);
+
+    // Chunks are returned without copying them.
+    let chunks = buf_list.drain_chunks(6);
+    assert_eq!(chunks, vec![&b"hello "[..]]);
+    assert_eq!(chunks[0].as_ptr(), hello.as_ptr());
+    assert_eq!(buf_list.num_chunks(), 2);
+
+    // The boundary chunk is split.
+    assert_eq!(buf_list.drain_chunks(3), vec![&b"wor"[..]]);
+    assert_eq!(buf_list.num_bytes(), 3);
+    let mut cursor = buf_list::Cursor::new(&buf_list);
+    let mut out = Vec::new();
+    std::io::Read::read_to_end(&mut cursor, &mut out).unwrap();
+    assert_eq!(out, b"ld!");
+
+    assert_eq!(buf_list.drain_chunks(100), vec![&b"ld"[..], &b"!"[..]]);
+    assert_eq!(buf_list.num_bytes(), 0);
+    assert_eq!(buf_list.num_chunks(), 0);
+    assert_eq!(buf_list.drain_chunks(100), Vec::<Bytes>::new());
+}
+
+#[test]
+fn test_mask() {
+    let key = [1, 2, 3, 4];
+    let data: Vec<u8> = (0..23).collect();
+    let expected: Vec<u8> = data
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| byte ^ key[i % 4])
+        .collect();
+
+    // Chunk lengths that aren't multiples of 4 exercise the mask phase.
+    let buf_list: BufList = vec![&data[..1], &data[1..7], &data[7..8], &data[8..]]
+        .into_iter()
+        .collect();
+    let masked = buf_list.masked(key);
+    assert_eq!(masked.num_chunks(), 4);
+    assert_eq!(masked.num_bytes(), 23);
+    let mut flattened = Vec::new();
+    for chunk in masked.iter() {
+        flattened.extend_from_slice(chunk);
+    }
+    assert_eq!(flattened, expected);
+
+    let mut buf_list_mut = BufListMut::new();
+    for range in &[0..3, 3..4, 4..13, 13..23] {
+        buf_list_mut.push_segment(BytesMut::from(&data[range.clone()]));
+    }
+    buf_list_mut.apply_mask(key);
+    let mut masked = buf_list_mut.freeze();
+    assert_eq!(masked.copy_to_bytes(23), &expected[..]);
+
+    assert_eq!(BufList::new().masked(key).num_chunks(), 0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn test_serde() {
+    use serde::{
+        de::value::{BytesDeserializer, Error, StrDeserializer},
+        Deserialize,
+    };
+    use serde_test::{assert_ser_tokens, Token};
+
+    let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    assert_ser_tokens(&buf_list, &[Token::Bytes(b"hello world")]);
+    assert_ser_tokens(&BufList::new(), &[Token::Bytes(b"")]);
+
+    let deserialized =
+        BufList::deserialize(BytesDeserializer::<Error>::new(b"hello world")).unwrap();
+    assert_eq!(deserialized.num_chunks(), 1);
+    assert_eq!(deserialized.to_str_lossy(), "hello world");
+    let deserialized = BufList::deserialize(StrDeserializer::<Error>::new("hello")).unwrap();
+    assert_eq!(deserialized.to_str_lossy(), "hello");
+
+    // Human-readable formats represent the bytes as an array of numbers.
+    let json = serde_json::to_string(&deserialized).unwrap();
+    assert_eq!(json, "[104,101,108,108,111]");
+    let deserialized: BufList = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.num_chunks(), 1);
+    assert_eq!(deserialized.to_str_lossy(), "hello");
+
+    let deserialized: BufList = serde_json::from_str("[]").unwrap();
+    assert_eq!(deserialized.num_chunks(), 0);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn test_serde_seed() {
+    use bincode::Options;
+    use buf_list::BufListSeed;
+
+    let buf_list: BufList = vec![&b"hello "[..], &b"world"[..]].into_iter().collect();
+    let input = Bytes::from(bincode::serialize(&buf_list).unwrap());
+
+    // Deserializing through the seed borrows from the input.
+    let deserialized = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .deserialize_seed(BufListSeed::new(input.clone()), &input)
+        .unwrap();
+    assert_eq!(deserialized.num_chunks(), 1);
+    let chunk = deserialized.get_chunk(0).unwrap();
+    assert_eq!(chunk, &b"hello world"[..]);
+    assert_eq!(chunk.as_ptr(), input[8..].as_ptr());
+
+    // Bytes borrowed from a different buffer are copied.
+    let other = input.to_vec();
+    let deserialized = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .deserialize_seed(BufListSeed::new(input), &other)
+        .unwrap();
+    assert_eq!(deserialized.to_str_lossy(), "hello world");
+    assert_ne!(
+        deserialized.get_chunk(0).unwrap().as_ptr(),
+        other[8..].as_ptr()
+    );
+
+    // The Deserialize implementation copies borrowed bytes.
+    let deserialized: BufList = bincode::deserialize(&other).unwrap();
+    assert_eq!(deserialized.to_str_lossy(), "hello world");
+}
+
+#[cfg(feature = "rkyv08")]
+#[test]
+fn test_rkyv() {
+    use buf_list::ArchivedBufList;
+    use rkyv_08::rancor::Error;
+
+    let buf_list: BufList = vec![&b"hello "[..], &b"wor"[..], &b"ld"[..]]
+        .into_iter()
+        .collect();
+    let bytes = rkyv_08::to_bytes::<Error>(&buf_list).unwrap();
+
+    let archived = rkyv_08::access::<ArchivedBufList, Error>(&bytes).unwrap();
+    assert_eq!(archived.as_bytes(), b"hello world");
+    assert_eq!(archived.num_bytes(), 11);
+    assert_eq!(archived.num_chunks(), 3);
+    let chunks: Vec<_> = archived.iter().collect();
+    assert_eq!(chunks, vec![&b"hello "[..], &b"wor"[..], &b"ld"[..]]);
+    assert_eq!(archived.get_chunk(3), None);
+
+    // Deserializing preserves chunk boundaries.
+    let deserialized = rkyv_08::deserialize::<BufList, Error>(archived).unwrap();
+    assert_eq!(deserialized.num_chunks(), 3);
+    assert_eq!(deserialized.get_chunk(1).unwrap(), &b"wor"[..]);
+
+    let bytes = rkyv_08::to_bytes::<Error>(&BufList::new()).unwrap();
+    let archived = rkyv_08::access::<ArchivedBufList, Error>(&bytes).unwrap();
+    assert_eq!(archived.num_chunks(), 0);
+    assert_eq!(archived.iter().next(), None);
+}
+
+#[cfg(feature = "proptest1")]
+mod proptest_strategies {
+    use buf_list::{proptest_strategies, BufList};
+    use bytes::Buf;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn proptest_buf_list(buf_list in proptest_strategies::buf_list()) {
+            prop_assert!(buf_list.num_chunks() < 32);
+            for chunk in buf_list.iter() {
+                prop_assert!(!chunk.is_empty() && chunk.len() < 128);
+            }
+        }
+
+        #[test]
+        fn proptest_buf_list_of(
+            buf_list in proptest_strategies::buf_list_of(
+                prop::collection::vec(b'0'..=b'9', 1..=2),
+                4..=8,
+            ),
+        ) {
+            let buf_list: BufList = buf_list;
+            prop_assert!((4..=8).contains(&buf_list.num_chunks()));
+            prop_assert!(buf_list.chunk().iter().all(u8::is_ascii_digit));
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck1")]
+#[test]
+fn test_quickcheck_arbitrary() {
+    use quickcheck_1::{Arbitrary, QuickCheck};
+
+    fn prop_remaining(buf_list: BufList) -> bool {
+        buf_list.iter().map(|chunk| chunk.len()).sum::<usize>() == buf_list.remaining()
+    }
+    QuickCheck::new().quickcheck(prop_remaining as fn(BufList) -> bool);
+
+    // Shrinking reduces both the number of chunks and their contents.
+    let buf_list: BufList = vec![&b"abc"[..], &b"de"[..]].into_iter().collect();
+    let shrunk: Vec<BufList> = buf_list.shrink().collect();
+    assert!(shrunk.iter().any(|list| list.num_chunks() < 2));
+    assert!(shrunk
+        .iter()
+        .any(|list| list.num_chunks() == 2 && list.num_bytes() < 5));
+    assert!(shrunk.iter().all(|list| list.num_bytes() <= 5));
+    assert_eq!(BufList::new().shrink().count(), 0);
+}
+
+#[test]
+fn test_framed() {
+    let chunk = Bytes::from(vec![b'x'; 300]);
+    let buf_list: BufList = vec![Bytes::from_static(b"a"), chunk, Bytes::from_static(b"bc")]
+        .into_iter()
+        .collect();
+    let mut out = Vec::new();
+    buf_list.write_framed(&mut out).unwrap();
+    // The count, then the lengths (300 is encoded as [0xac, 0x02]), then the payloads.
+    assert_eq!(&out[..5], b"\x03\x01\xac\x02\x02");
+    assert_eq!(out.len(), 5 + 303);
+
+    // Data following the list is left alone.
+    out.extend_from_slice(b"rest");
+    let mut reader = &out[..];
+    let read_back = BufList::read_framed(&mut reader).unwrap();
+    assert_eq!(reader, b"rest");
+    let chunks: Vec<_> = read_back.iter().map(|chunk| chunk.len()).collect();
+    assert_eq!(chunks, vec![1, 300, 2]);
+    assert_eq!(read_back.get_chunk(2).unwrap(), &b"bc"[..]);
+
+    let mut out = Vec::new();
+    BufList::new().write_framed(&mut out).unwrap();
+    assert_eq!(out, b"\x00");
+    assert_eq!(BufList::read_framed(&out[..]).unwrap().num_chunks(), 0);
+
+    // Truncated input.
+    let err = BufList::read_framed(&b"\x02\x01\x05a"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    let err = BufList::read_framed(&b"\x02\x01"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    let err = BufList::read_framed(&b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02"[..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "base64022")]
+#[test]
+fn test_base64() {
+    use base64_022::{engine::general_purpose::URL_SAFE_NO_PAD, DecodeError};
+
+    // Chunk boundaries don't line up with base64's 3-byte groups.
+    let data: Vec<u8> = (0..=255).cycle().take(20_000).collect();
+    let buf_list: BufList = data.chunks(7).map(Bytes::copy_from_slice).collect();
+    let encoded = buf_list.encode_base64();
+    assert_eq!(encoded.num_bytes(), 26_668);
+    assert!(encoded.num_chunks() > 1);
+
+    // Split the encoded data at boundaries that don't line up with 4-byte groups either.
+    let mut encoded_bytes = Vec::new();
+    for chunk in encoded.iter() {
+        encoded_bytes.extend_from_slice(chunk);
+    }
+    let encoded: BufList = encoded_bytes
+        .chunks(5)
+        .map(Bytes::copy_from_slice)
+        .collect();
+    let mut decoded = encoded.decode_base64().unwrap();
+    assert_eq!(decoded.copy_to_bytes(decoded.remaining()), &data[..]);
+
+    let buf_list: BufList = vec![&b"\xfb"[..], &b"\xff"[..]].into_iter().collect();
+    let encoded = buf_list.encode_base64_with(&URL_SAFE_NO_PAD);
+    assert_eq!(encoded.to_str_lossy(), "-_8");
+    let decoded = encoded.decode_base64_with(&URL_SAFE_NO_PAD).unwrap();
+    assert_eq!(decoded.to_str_lossy(), buf_list.to_str_lossy());
+
+    assert_eq!(BufList::new().encode_base64().num_bytes(), 0);
+
+    // Errors are reported at their offset in the list.
+    let invalid: BufList = vec![&b"aGVs"[..], &b"b!8="[..]].into_iter().collect();
+    assert_eq!(
+        invalid.decode_base64().unwrap_err(),
+        DecodeError::InvalidByte(5, b'!')
+    );
+}
+
+/// Returns `len` bytes cycling through every byte value.
+#[cfg(any(
+    feature = "crc32c06",
+    feature = "crc32fast1",
+    feature = "digest010",
+    feature = "xxhash08"
+))]
+fn cycled_bytes(len: usize) -> Vec<u8> {
+    (0..=255).cycle().take(len).collect()
+}
+
+/// Returns 100,000 bytes that compress well, but not trivially.
+#[cfg(any(feature = "flate21", feature = "lz4-flex011", feature = "zstd013"))]
+fn compressible_bytes() -> Vec<u8> {
+    (0..100_000u32)
+        .map(|i| (i % 251) as u8 ^ (i / 1000) as u8)
+        .collect()
+}
+
+/// Splits `data` into a list for each of `chunk_sizes`, for checking that a result doesn't depend
+/// on how a list is split into chunks.
+#[cfg(any(
+    feature = "crc32c06",
+    feature = "crc32fast1",
+    feature = "digest010",
+    feature = "flate21",
+    feature = "lz4-flex011",
+    feature = "xxhash08",
+    feature = "zstd013"
+))]
+fn split_by_chunk_sizes<'a>(
+    data: &'a [u8],
+    chunk_sizes: &'a [usize],
+) -> impl Iterator<Item = (usize, BufList)> + 'a {
+    chunk_sizes.iter().map(move |&chunk_size| {
+        let buf_list = data
+            .chunks(chunk_size)
+            .map(Bytes::copy_from_slice)
+            .collect();
+        (chunk_size, buf_list)
+    })
+}
+
+#[cfg(feature = "digest010")]
+#[test]
+fn test_digest() {
+    use sha2::{Digest, Sha256};
+
+    let data = cycled_bytes(1000);
+    let expected = Sha256::digest(&data);
+
+    for (_, buf_list) in split_by_chunk_sizes(&data, &[1, 7, 64, 1000]) {
+        assert_eq!(buf_list.digest::<Sha256>(), expected);
+
+        let mut hasher = Sha256::new();
+        buf_list.update_digest(&mut hasher);
+        assert_eq!(hasher.finalize(), expected);
+    }
+    assert_eq!(BufList::new().digest::<Sha256>(), Sha256::digest(b""));
+}
+
+#[cfg(any(feature = "crc32fast1", feature = "crc32c06"))]
+#[test]
+fn test_crc32() {
+    let data = cycled_bytes(1000);
+    let whole: BufList = vec![Bytes::from(data.clone())].into_iter().collect();
+
+    for (_, buf_list) in split_by_chunk_sizes(&data, &[1, 7, 64]) {
+        #[cfg(feature = "crc32fast1")]
+        assert_eq!(buf_list.crc32(), whole.crc32());
+        #[cfg(feature = "crc32c06")]
+        assert_eq!(buf_list.crc32c(), whole.crc32c());
+    }
+
+    #[cfg(feature = "crc32fast1")]
+    assert_eq!(BufList::new().crc32(), 0);
+    #[cfg(feature = "crc32c06")]
+    assert_eq!(BufList::new().crc32c(), 0);
+}
+
+#[cfg(feature = "xxhash08")]
+#[test]
+fn test_fast_hash() {
+    let data = cycled_bytes(1000);
+    let whole: BufList = vec![Bytes::from(data.clone())].into_iter().collect();
+
+    for (_, buf_list) in split_by_chunk_sizes(&data, &[1, 7, 64, 300]) {
+        assert_eq!(buf_list.fast_hash(), whole.fast_hash());
+        assert_eq!(
+            buf_list.fast_hash_with_seed(42),
+            whole.fast_hash_with_seed(42)
+        );
+    }
+    assert_ne!(whole.fast_hash(), whole.fast_hash_with_seed(42));
+
+    // Known value for XXH3-64 of the empty input.
+    assert_eq!(BufList::new().fast_hash(), 0x2d06_8005_38d3_94c2);
+}
+
+#[cfg(feature = "flate21")]
+#[test]
+fn test_gzip() {
+    use std::io::Read;
+
+    let data = compressible_bytes();
+
+    for (chunk_size, buf_list) in split_by_chunk_sizes(&data, &[1, 7, 8192, 100_000]) {
+        let compressed = buf_list.compress_gzip();
+        let mut decompressed = compressed.decompress_gzip().expect("valid gzip data");
+        assert_eq!(
+            decompressed.num_bytes(),
+            data.len(),
+            "chunk size {}",
+            chunk_size
+        );
+        assert!(decompressed.num_chunks() > 1, "output is split into chunks");
+        assert_eq!(
+            decompressed.copy_to_bytes(decompressed.num_bytes()),
+            &data[..]
+        );
+
+        // The output is readable by flate2 directly.
+        let mut decoder = flate2_1::read::GzDecoder::new(compressed.into_reader());
+        let mut out = Vec::new();
+        decoder.read_to_end(&mut out).unwrap();
+        assert_eq!(out, data);
+    }
+
+    let empty = BufList::new().compress_gzip();
+    assert!(empty.has_remaining());
+    assert_eq!(empty.decompress_gzip().unwrap().num_bytes(), 0);
+
+    let garbage: BufList = vec![&b"not gzip data"[..]].into_iter().collect();
+    garbage.decompress_gzip().unwrap_err();
+
+    // Truncated input is an error.
+    let mut truncated = BufList::from(data.clone()).compress_gzip();
+    let keep = truncated.num_bytes() / 2;
+    let truncated = truncated.copy_to_bytes(keep);
+    BufList::from(truncated).decompress_gzip().unwrap_err();
+}
+
+#[cfg(feature = "zstd013")]
+#[test]
+fn test_zstd() {
+    let data = compressible_bytes();
+
+    for (chunk_size, buf_list) in split_by_chunk_sizes(&data, &[1, 7, 8192, 100_000]) {
+        for level in &[0, 1, 19] {
+            let compressed = buf_list
+                .compress_zstd(*level)
+                .expect("compression succeeded");
+            let mut decompressed = compressed.decompress_zstd().expect("valid zstd data");
+            assert_eq!(
+                decompressed.num_bytes(),
+                data.len(),
+                "chunk size {}",
+                chunk_size
+            );
+            assert!(decompressed.num_chunks() > 1, "output is split into chunks");
+            assert_eq!(
+                decompressed.copy_to_bytes(decompressed.num_bytes()),
+                &data[..]
+            );
+
+            // The output is readable by zstd directly.
+            let out = zstd_013::decode_all(compressed.into_reader()).unwrap();
+            assert_eq!(out, data);
+        }
+    }
+
+    // Concatenated frames are decompressed back to back.
+    let mut concatenated = BufList::from(&b"hello "[..]).compress_zstd(0).unwrap();
+    concatenated.extend(
+        BufList::from(&b"world"[..])
+            .compress_zstd(0)
+            .unwrap()
+            .iter()
+            .cloned(),
+    );
+    assert_eq!(
+        concatenated.decompress_zstd().unwrap().to_str_lossy(),
+        "hello world"
+    );
+
+    // Dictionaries must match on both ends.
+    let dictionary = b"a raw content dictionary with some shared content";
+    let buf_list = BufList::from(&b"some shared content, some shared content"[..]);
+    let compressed = buf_list
+        .compress_zstd_with_dictionary(3, dictionary)
+        .unwrap();
+    let decompressed = compressed
+        .decompress_zstd_with_dictionary(dictionary)
+        .unwrap();
+    assert_eq!(decompressed.to_str_lossy(), buf_list.to_str_lossy());
+    compressed.decompress_zstd().unwrap_err();
+
+    let garbage = BufList::from(&b"not zstd data"[..]);
+    garbage.decompress_zstd().unwrap_err();
+
+    // Truncated input is an error.
+    let mut truncated = BufList::from(data).compress_zstd(0).unwrap();
+    let keep = truncated.num_bytes() / 2;
+    let truncated = truncated.copy_to_bytes(keep);
+    BufList::from(truncated).decompress_zstd().unwrap_err();
+}
+
+#[cfg(feature = "lz4-flex011")]
+#[test]
+fn test_lz4() {
+    use lz4_flex_011::frame::{BlockSize, FrameInfo};
+
+    let data = compressible_bytes();
+
+    for (chunk_size, buf_list) in split_by_chunk_sizes(&data, &[1, 7, 8192, 100_000]) {
+        let frame_infos = vec![
+            None,
+            Some(
+                FrameInfo::new()
+                    .block_size(BlockSize::Max64KB)
+                    .content_checksum(true),
+            ),
+        ];
+        for frame_info in frame_infos {
+            let compressed = match frame_info {
+                Some(frame_info) => buf_list.compress_lz4_with(frame_info),
+                None => buf_list.compress_lz4(),
+            };
+            let mut decompressed = compressed.decompress_lz4().expect("valid lz4 data");
+            assert_eq!(
+                decompressed.num_bytes(),
+                data.len(),
+                "chunk size {}",
+                chunk_size
+            );
+            assert!(decompressed.num_chunks() > 1, "output is split into chunks");
+            assert_eq!(
+                decompressed.copy_to_bytes(decompressed.num_bytes()),
+                &data[..]
+            );
+        }
+    }
+
+    let empty = BufList::new().compress_lz4();
+    assert!(empty.has_remaining());
+    assert_eq!(empty.decompress_lz4().unwrap().num_bytes(), 0);
+
+    let garbage = BufList::from(&b"not lz4 data"[..]);
+    garbage.decompress_lz4().unwrap_err();
+
+    // Truncated input is an error.
+    let mut truncated = BufList::from(data).compress_lz4();
+    let keep = truncated.num_bytes() / 2;
+    let truncated = truncated.copy_to_bytes(keep);
+    BufList::from(truncated).decompress_lz4().unwrap_err();
+}